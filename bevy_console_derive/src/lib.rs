@@ -0,0 +1,83 @@
+//! Derive macros for `bevy_console_two`.
+//!
+//! Enable the `derive` feature of `bevy_console_two` rather than depending on
+//! this crate directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Implement `ConVarValue` for an enum whose variants have no fields.
+///
+/// Values parse from the variant name, ignoring case, and format as the
+/// variant name. Also generates an `ALLOWED_VALUES` constant listing the
+/// variant names. The enum must also implement `Clone` and `PartialEq`.
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq, ConVarValue)]
+/// enum Quality { Low, Medium, High }
+///
+/// assert_eq!(Quality::parse("medium"), Some(Quality::Medium));
+/// assert_eq!(Quality::ALLOWED_VALUES, &["Low", "Medium", "High"]);
+/// ```
+#[proc_macro_derive(ConVarValue)]
+pub fn derive_convar_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_convar_value(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_convar_value(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(&input.ident, "ConVarValue can only be derived for enums"));
+    };
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(&input.ident, "ConVarValue requires at least one variant"));
+    }
+
+    let mut idents = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(variant, "ConVarValue variants cannot have fields"));
+        }
+        idents.push(&variant.ident);
+    }
+    let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ty #ty_generics #where_clause {
+            /// Variant names accepted by the console.
+            pub const ALLOWED_VALUES: &'static [&'static str] = &[#(#names),*];
+        }
+
+        impl #impl_generics ::bevy_console_two::ConVarValue for #ty #ty_generics #where_clause {
+            fn parse(s: &str) -> ::core::option::Option<Self> {
+                let s = s.trim();
+                #(
+                    if s.eq_ignore_ascii_case(#names) {
+                        return ::core::option::Option::Some(Self::#idents);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+
+            fn format(&self) -> ::std::string::String {
+                match self {
+                    #(Self::#idents => ::std::string::String::from(#names),)*
+                }
+            }
+
+            fn clamp(self, _min: ::core::option::Option<&Self>, _max: ::core::option::Option<&Self>) -> Self {
+                self
+            }
+
+            fn allowed_values() -> &'static [&'static str] {
+                Self::ALLOWED_VALUES
+            }
+        }
+    })
+}
@@ -28,8 +28,8 @@ fn main() {
             ..default()
         }))
         .add_plugins(bevy_egui::EguiPlugin::default())
-        .add_plugins(bevy_console_two::ConsolePlugin)
-        .add_systems(Startup, setup)
+        .add_plugins(bevy_console_two::ConsolePlugin::default())
+        .add_systems(Startup, setup.in_set(ConsoleSet::Register))
         .run();
 }
 
@@ -38,7 +38,7 @@ fn main() {
 struct DemoEntity;
 
 fn setup(mut commands: Commands, mut console: Console) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Server convars
     console.register_var(
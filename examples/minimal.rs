@@ -11,8 +11,8 @@ use bevy_console_two::prelude::*;
 fn main() {
     App::new()
         .add_plugins(MinimalPlugins)
-        .add_plugins(bevy_console_two::ConsolePlugin)
-        .add_systems(Startup, setup)
+        .add_plugins(bevy_console_two::ConsolePlugin::default())
+        .add_systems(Startup, setup.in_set(ConsoleSet::Register))
         .add_systems(Update, process_outputs)
         .add_systems(Update, send_test_commands.run_if(run_once))
         .run();
@@ -62,6 +62,7 @@ fn process_outputs(mut events: MessageReader<ConsoleOutputEvent>) {
             ConsoleOutputLevel::Info => "[INFO]",
             ConsoleOutputLevel::Warn => "[WARN]",
             ConsoleOutputLevel::Error => "[ERROR]",
+            ConsoleOutputLevel::Success => "[OK]",
             ConsoleOutputLevel::Command => "[$]",
             ConsoleOutputLevel::Result => "[>]",
         };
@@ -13,7 +13,7 @@
 //! - `quit` - Exit the application
 
 use bevy::prelude::*;
-use bevy_console_two::{Console, ConVar, ConVarFlags, ConCommand, ConsoleRegistry};
+use bevy_console_two::{Console, ConVar, ConVarFlags, ConCommand, ConsoleRegistry, ConsoleSet};
 
 fn main() {
     println!("=== Terminal Console Example ===");
@@ -26,8 +26,8 @@ fn main() {
 
     App::new()
         .add_plugins(MinimalPlugins)
-        .add_plugins(bevy_console_two::ConsolePlugin)
-        .add_systems(Startup, setup)
+        .add_plugins(bevy_console_two::ConsolePlugin::default())
+        .add_systems(Startup, setup.in_set(ConsoleSet::Register))
         .run();
 }
 
@@ -0,0 +1,1030 @@
+//! Built-in console commands such as `help`, `echo`, `find` and `exec`.
+
+use bevy::prelude::*;
+
+use crate::core::{
+    self, evaluate_arithmetic, expand_vars, CommandHandlers, CommandStats, ConCommand, ConEntry, ConVar,
+    ConVarFlags, ConsoleAuditLog, ConsoleClearEvent, ConsoleLocalization, ConsoleOutputBuffer, ConsoleOutputEvent,
+    ConsoleOutputLevel, ConsoleRegistry, ConsoleWhitelist, PermissionLevel,
+};
+#[cfg(feature = "persist")]
+use crate::core::{CommandSource, ConsoleInputEvent};
+#[cfg(feature = "egui")]
+use crate::config::{self, ConsoleConfig};
+#[cfg(feature = "persist")]
+use crate::persist;
+use crate::{format_numeric, macros, register_cmd, set_var, tee, PendingCommands};
+
+/// Lines printed by `help <name>` for a single entry.
+pub(crate) fn help_lines(name: &str, entry: &ConEntry, localization: &ConsoleLocalization) -> Vec<String> {
+    let desc = entry.description_localized(localization);
+    let desc = if desc.is_empty() { "No description" } else { desc };
+    let mut lines = vec![format!("{} - {}", name, desc)];
+
+    if let ConEntry::Cmd(meta) = entry
+        && let Some(replacement) = meta.deprecated_replacement
+    {
+        lines.push(format!("[DEPRECATED: use {}]", replacement));
+    }
+
+    if let ConEntry::Cmd(meta) = entry
+        && let Some(usage) = meta.usage()
+    {
+        lines.push(format!("Usage: {}", usage));
+        let width = meta.schema.iter().map(|arg| arg.name.len()).max().unwrap_or(0);
+        for arg in meta.schema.iter().filter(|arg| !arg.description.is_empty()) {
+            lines.push(format!("  {:<width$}  {}", arg.name, arg.description));
+        }
+    }
+
+    if let Some(help_text) = entry.help_text() {
+        lines.push(String::new());
+        lines.extend(help_text.lines().map(str::to_string));
+    }
+
+    if let ConEntry::Var(meta) = entry {
+        lines.push(format!("  Current: {}", meta.get_string()));
+        lines.push(format!("  Default: {}", meta.default_string()));
+
+        let constraints = meta.constraint_description();
+        if !constraints.is_empty() {
+            lines.push(format!("  Constraints: {}", constraints));
+        }
+
+        if let Some(list) = meta.downcast_ref::<Vec<String>>() {
+            lines.push(format!("  Items ({}):", list.get_ref().len()));
+            lines.extend(list.get_ref().iter().map(|item| format!("    {}", item)));
+        }
+    }
+    lines
+}
+
+/// Shorten a `std::any::type_name` by dropping module paths, e.g. `Vec<String>`.
+pub(crate) fn short_type_name(type_name: &str) -> String {
+    let mut out = String::with_capacity(type_name.len());
+    let mut segment = String::new();
+    let mut chars = type_name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            out.push_str(&segment);
+            segment.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(&segment);
+    out
+}
+
+/// Escape text for use inside a Markdown table cell.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Markdown table of every non-hidden command and variable, for `help --markdown`.
+fn markdown_table(registry: &ConsoleRegistry) -> String {
+    let mut entries: Vec<_> = registry
+        .iter()
+        .filter(|(_, entry)| !entry.flags().contains(ConVarFlags::HIDDEN))
+        .collect();
+    entries.sort_by_key(|(name, _)| *name);
+
+    let mut out = String::from("| Name | Type | Default | Description |\n|------|------|---------|-------------|\n");
+    for (name, entry) in entries {
+        let (ty, default) = match entry {
+            ConEntry::Var(meta) => (short_type_name(meta.type_name), meta.default_string()),
+            ConEntry::Cmd(_) => ("command".to_string(), String::new()),
+        };
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            name, markdown_cell(&ty), markdown_cell(&default), markdown_cell(entry.description())
+        ));
+    }
+    out
+}
+
+/// Markdown section describing one entry, for `help --markdown <name>`.
+fn markdown_entry(name: &str, entry: &ConEntry) -> String {
+    let mut out = format!("## {}\n\n", name);
+    if !entry.description().is_empty() {
+        out.push_str(&format!("{}\n\n", entry.description()));
+    }
+    out.push_str("| Property | Value |\n|----------|-------|\n");
+    match entry {
+        ConEntry::Var(meta) => {
+            out.push_str(&format!("| Type | {} |\n", markdown_cell(&short_type_name(meta.type_name))));
+            out.push_str(&format!("| Default | `{}` |\n", markdown_cell(&meta.default_string())));
+            out.push_str(&format!("| Current | `{}` |\n", markdown_cell(&meta.get_string())));
+        }
+        ConEntry::Cmd(meta) => {
+            out.push_str("| Type | command |\n");
+            if let Some(usage) = meta.usage() {
+                out.push_str(&format!("| Usage | `{}` |\n", markdown_cell(&usage)));
+            }
+            if let Some(replacement) = meta.deprecated_replacement {
+                out.push_str(&format!("| Deprecated | use `{}` |\n", replacement));
+            }
+        }
+    }
+    out.push_str(&format!("| Permission | {} |\n", entry.required_permission()));
+    if let Some(help_text) = entry.help_text() {
+        out.push_str(&format!("\n```\n{}\n```\n", help_text));
+    }
+    out
+}
+
+/// Register built-in console commands.
+pub(crate) fn register_builtin_commands(
+    mut registry: ResMut<ConsoleRegistry>,
+    mut handlers: ResMut<CommandHandlers>,
+) {
+    // sv_cheats - Enable cheat-protected commands and variables
+    registry.register_var(
+        ConVar::new("sv_cheats", 0i32)
+            .description("Enable cheat-protected commands and variables")
+            .min(0)
+            .max(1)
+            .permission(PermissionLevel::Admin)
+    );
+
+    // help - Show help for a command or list all commands
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("help", |args, world| {
+        let registry = world.resource::<ConsoleRegistry>();
+        let show_deprecated = args.flag("--deprecated");
+        let name = args.iter().find(|arg| !arg.starts_with("--"));
+
+        if args.flag("--markdown") {
+            let markdown = match name {
+                Some(name) => match registry.get_entry(name) {
+                    Some(entry) => markdown_entry(name, entry),
+                    None => {
+                        warn!("Unknown command or variable: {}", name);
+                        return;
+                    }
+                },
+                None => markdown_table(registry),
+            };
+            world.resource_mut::<PendingCommands>().outputs.push(ConsoleOutputEvent::result(markdown));
+            return;
+        }
+
+        if let Some(name) = name {
+            // Show help for specific command/var
+            if let Some(entry) = registry.get_entry(name) {
+                for line in help_lines(name, entry, world.resource::<ConsoleLocalization>()) {
+                    info!("{}", line);
+                }
+            } else {
+                warn!("Unknown command or variable: {}", name);
+            }
+        } else {
+            // List all commands
+            info!("Commands:");
+            for (name, meta) in registry.cmds() {
+                if show_deprecated || !meta.flags.contains(ConVarFlags::DEPRECATED) {
+                    info!("  {}", name);
+                }
+            }
+            info!("Use 'help <name>' for details, 'cvarlist' for variables");
+        }
+    }).description("Show help for a command or list all commands")
+        .help_text("Usage:\n  help               list commands\n  help --deprecated  include deprecated commands\n  help <name>        show details for a command or variable\n  help --markdown [name]  print commands and variables as Markdown"));
+
+    // cvarlist - List all console variables
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("cvarlist", |args, world| {
+        let registry = world.resource::<ConsoleRegistry>();
+        let localization = world.resource::<ConsoleLocalization>();
+        let show_deprecated = args.iter().any(|arg| arg == "--deprecated");
+        let prefix = args.iter().find(|arg| *arg != "--deprecated").unwrap_or("");
+        #[cfg(feature = "egui")]
+        let columns = world
+            .get_resource::<ConsoleConfig>()
+            .map_or(1, |config| config.cvarlist_columns.clamp(1, config::MAX_CVARLIST_COLUMNS));
+        #[cfg(not(feature = "egui"))]
+        let columns = 1;
+
+        let mut count = 0;
+        let mut cells = Vec::new();
+        for (name, meta) in registry.vars() {
+            let deprecated = meta.flags.contains(ConVarFlags::DEPRECATED);
+            if name.starts_with(prefix)
+                && !meta.flags.contains(ConVarFlags::HIDDEN)
+                && (show_deprecated || !deprecated)
+            {
+                let modified = if meta.is_modified() { "*" } else { "" };
+                let value = match meta.downcast_ref::<Vec<String>>() {
+                    Some(list) => format!("[{} items]", list.get_ref().len()),
+                    None => format!("\"{}\"", meta.get_string()),
+                };
+                let desc = meta.description_localized(localization);
+                count += 1;
+                if columns > 1 {
+                    cells.push(format!("{}{} = {}", name, modified, value));
+                    continue;
+                }
+                // Logged as a result so the egui UI can make the name clickable
+                if desc.is_empty() {
+                    info!(name: "console_result", "{}{} = {}", name, modified, value);
+                } else {
+                    info!(name: "console_result", "{}{} = {} - {}", name, modified, value, desc);
+                }
+            }
+        }
+        for row in format_columns(&cells, columns) {
+            info!("{}", row);
+        }
+        info!("{} convars", count);
+    }).description("List console variables (--deprecated to include deprecated ones)"));
+
+    // stats - Show command execution statistics
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("stats", |args, world| {
+        if args.get(0) == Some("reset") {
+            world.resource_mut::<CommandStats>().reset();
+            info!(name: "console_success", "Command statistics reset");
+            return;
+        }
+
+        let stats = world.resource::<CommandStats>();
+        info!(
+            "{} executed, {} errors, {} permission denials",
+            stats.total_executed, stats.total_errors, stats.total_permission_denials
+        );
+        info!("Most called:");
+        for (name, count) in stats.most_called(10) {
+            info!("  {:<24} {}", name, count);
+        }
+        info!("Slowest (total time):");
+        for (name, total) in stats.slowest(10) {
+            info!("  {:<24} {:?}", name, total);
+        }
+    }).description("Show command execution statistics ('stats reset' to clear)"));
+
+    // grep - Search recent console output
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("grep", |args, world| {
+        let mut case_sensitive = false;
+        let mut count_only = false;
+        let mut after = 0;
+        let mut pattern = None;
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg {
+                "--case" => case_sensitive = true,
+                "--count" => count_only = true,
+                "--after" => match iter.next().and_then(|n| n.parse().ok()) {
+                    Some(n) => after = n,
+                    None => {
+                        warn!("--after expects a line count");
+                        return;
+                    }
+                },
+                _ if pattern.is_none() => pattern = Some(arg),
+                _ => {}
+            }
+        }
+        let Some(pattern) = pattern else {
+            warn!("Usage: grep [--case] [--count] [--after N] <pattern>");
+            return;
+        };
+
+        let lines = world.resource::<ConsoleOutputBuffer>().grep(pattern, case_sensitive, after);
+        let matches = lines.iter().filter(|line| line.is_match).count();
+        if !count_only {
+            for line in &lines {
+                info!(name: "console_result", "{}", line.text);
+            }
+        }
+        info!("{} matches", matches);
+    }).description("Search recent console output")
+        .help_text("Usage: grep [--case] [--count] [--after N] <pattern>\n  --case     match case exactly\n  --count    only print the number of matches\n  --after N  also print N lines after each match"));
+
+    // find - Search commands and variables
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("find", |args, world| {
+        let registry = world.resource::<ConsoleRegistry>();
+
+        // The query is the first argument that is neither an option nor an option's value
+        let mut query = None;
+        let mut rest = args.iter();
+        while let Some(arg) = rest.next() {
+            match arg {
+                "--type" | "--flag" => {
+                    rest.next();
+                }
+                arg if arg.starts_with("--") => {}
+                arg => {
+                    query.get_or_insert(arg);
+                }
+            }
+        }
+        let Some(query) = query else {
+            warn!("Usage: find [--type var|cmd] [--flag <FLAG>] [--description] [--verbose] <search term>");
+            return;
+        };
+
+        let want_var = match args.flag_value("--type") {
+            None => None,
+            Some("var") => Some(true),
+            Some("cmd") => Some(false),
+            Some(other) => {
+                warn!("Unknown type '{}', expected 'var' or 'cmd'", other);
+                return;
+            }
+        };
+        let flag = match args.flag_value("--flag") {
+            None => None,
+            Some(name) => match ConVarFlags::from_name(name) {
+                Some(flag) => Some(flag),
+                None => {
+                    warn!("Unknown flag '{}'", name);
+                    return;
+                }
+            },
+        };
+        let descriptions_only = args.flag("--description");
+        let verbose = args.flag("--verbose");
+        let query_lower = query.to_lowercase();
+
+        let results: Vec<_> = registry
+            .search(query)
+            .into_iter()
+            .filter(|(_, entry)| want_var.is_none_or(|want_var| entry.is_var() == want_var))
+            .filter(|(_, entry)| flag.is_none_or(|flag| entry.flags().contains(flag)))
+            .filter(|(_, entry)| !descriptions_only || entry.description().to_lowercase().contains(&query_lower))
+            .collect();
+
+        for (name, entry) in &results {
+            let label = match entry {
+                ConEntry::Var(meta) if verbose => format!("[var] {} = \"{}\"", name, meta.get_string()),
+                ConEntry::Var(_) => format!("[var] {}", name),
+                ConEntry::Cmd(_) => format!("[cmd] {}", name),
+            };
+            let desc = entry.description();
+            if desc.is_empty() {
+                info!("{}", label);
+            } else {
+                info!("{} - {}", label, desc);
+            }
+        }
+        info!("{} results", results.len());
+    }).description("Search commands and variables by name or description")
+        .help_text("Usage:\n  find <term>                search names and descriptions\n  find --type var|cmd <term> only variables or only commands\n  find --flag <FLAG> <term>  only entries with a flag set, e.g. ARCHIVE\n  find --description <term>  search descriptions only\n  find --verbose <term>      show current values of variables"));
+
+    // echo - Print text to console
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("echo", |args, world| {
+        let registry = world.resource::<ConsoleRegistry>();
+        let text = expand_vars(&args.join(" "), |name| {
+            let value = registry.get_string(name);
+            if value.is_none() {
+                warn!("Unknown variable '{}'", name);
+            }
+            value
+        });
+        info!("{}", text);
+    }).description("Print text to console, replacing ${var} with ConVar values"));
+
+    // clear - Clear console output, optionally only one level
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("clear", |args, world| {
+        let clear = match args.get(0) {
+            None | Some("all") => ConsoleClearEvent::all(),
+            Some("error") => ConsoleClearEvent::level(ConsoleOutputLevel::Error),
+            Some("warn") => ConsoleClearEvent::level(ConsoleOutputLevel::Warn),
+            Some("info") => ConsoleClearEvent::level(ConsoleOutputLevel::Info),
+            Some("debug") => ConsoleClearEvent::level(ConsoleOutputLevel::Debug),
+            Some(_) => {
+                warn!("Usage: clear [all|error|warn|info|debug]");
+                return;
+            }
+        };
+        world.resource_mut::<PendingCommands>().clears.push(clear);
+    }).description("Clear console output")
+        .help_text("Usage:\n  clear          remove all output\n  clear <level>  remove only error, warn, info or debug output"));
+
+    // clear_errors - Remove only error output
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("clear_errors", |_args, world| {
+        world.resource_mut::<PendingCommands>().clears.push(ConsoleClearEvent::level(ConsoleOutputLevel::Error));
+    }).description("Remove error messages from the console output"));
+
+    // tee - Mirror console output to a file
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("tee", |args, world| {
+        let (append, filename) = match (args.get(0), args.get(1)) {
+            (Some("stop"), None) => {
+                match world.resource_mut::<tee::TeeState>().stop() {
+                    Some(path) => info!(name: "console_success", "Stopped writing output to '{}'", path.display()),
+                    None => warn!("tee is not active"),
+                }
+                return;
+            }
+            (Some("--append"), Some(filename)) => (true, filename),
+            (Some(filename), None) if filename != "--append" => (false, filename),
+            _ => {
+                warn!("Usage: tee [--append] <filename> | tee stop");
+                return;
+            }
+        };
+
+        match world.resource_mut::<tee::TeeState>().start(filename, append) {
+            Ok(()) => info!(name: "console_success", "Writing output to '{}'", filename),
+            Err(e) => error!("Failed to open '{}': {}", filename, e),
+        }
+    }).description("Mirror console output to a file ('tee stop' to end)")
+        .help_text("Usage:\n  tee <filename>           write output to a new file\n  tee --append <filename>  append output to a file\n  tee stop                 stop writing output"));
+
+    // sandbox_mode - Enforce the command whitelist
+    register_cmd(&mut registry, &mut handlers, ConCommand::new(core::SANDBOX_MODE_COMMAND, |args, world| {
+        let mut whitelist = world.resource_mut::<ConsoleWhitelist>();
+        match args.get(0) {
+            Some("on" | "1") => whitelist.enabled = true,
+            Some("off" | "0") => whitelist.enabled = false,
+            Some(_) => {
+                warn!("Usage: sandbox_mode <on|off>");
+                return;
+            }
+            None => {}
+        }
+        info!(
+            "sandbox_mode is {} ({} commands allowed)",
+            if whitelist.enabled { "on" } else { "off" },
+            whitelist.allowed.len()
+        );
+    }).description("Restrict commands to the ConsoleWhitelist (on|off)")
+        .permission(PermissionLevel::Server));
+
+    // sandbox_allow - Add a command to the whitelist
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("sandbox_allow", |args, world| {
+        let Some(name) = args.get(0) else {
+            warn!("Usage: sandbox_allow <command>");
+            return;
+        };
+        if !matches!(world.resource::<ConsoleRegistry>().get_entry(name), Some(ConEntry::Cmd(_))) {
+            warn!("Unknown command '{}'", name);
+        }
+        world.resource_mut::<ConsoleWhitelist>().allow(name);
+        info!(name: "console_success", "Allowed '{}' in sandbox mode", name);
+    }).description("Allow a command while sandbox_mode is on")
+        .permission(PermissionLevel::Server));
+
+    // sandbox_deny - Remove a command from the whitelist
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("sandbox_deny", |args, world| {
+        let Some(name) = args.get(0) else {
+            warn!("Usage: sandbox_deny <command>");
+            return;
+        };
+        let mut whitelist = world.resource_mut::<ConsoleWhitelist>();
+        if whitelist.allowed.contains(name) {
+            whitelist.deny(name);
+            info!(name: "console_success", "Denied '{}' in sandbox mode", name);
+        } else {
+            warn!("'{}' is not allowed in sandbox mode", name);
+        }
+    }).description("Stop allowing a command while sandbox_mode is on")
+        .permission(PermissionLevel::Server));
+
+    // sandbox_list - Show the whitelist
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("sandbox_list", |_args, world| {
+        let mut allowed: Vec<_> = world.resource::<ConsoleWhitelist>().allowed.iter().cloned().collect();
+        allowed.sort();
+        for name in &allowed {
+            info!(name: "console_result", "  {}", name);
+        }
+        info!("{} commands allowed in sandbox mode", allowed.len());
+    }).description("List the commands allowed while sandbox_mode is on")
+        .permission(PermissionLevel::Server));
+
+    // audit_export - Write the audit log to a file
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("audit_export", |args, world| {
+        let Some(filename) = args.get(0) else {
+            warn!("Usage: audit_export <filename.json|filename.csv>");
+            return;
+        };
+        let log = world.resource::<ConsoleAuditLog>();
+        let is_csv = std::path::Path::new(filename).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let contents = if is_csv {
+            log.to_csv()
+        } else {
+            #[cfg(feature = "persist")]
+            match log.to_json() {
+                Ok(json) => json,
+                Err(e) => {
+                    error!("Failed to serialize audit log: {}", e);
+                    return;
+                }
+            }
+            #[cfg(not(feature = "persist"))]
+            {
+                warn!("JSON export requires the 'persist' feature; use a .csv filename");
+                return;
+            }
+        };
+        match std::fs::write(filename, contents) {
+            Ok(()) => info!(name: "console_success", "Exported {} audit records to '{}'", log.len(), filename),
+            Err(e) => error!("Failed to write '{}': {}", filename, e),
+        }
+    }).description("Export the command audit log as JSON, or CSV for a .csv filename")
+        .permission(PermissionLevel::Server));
+
+    // audit_stats - Summarize the audit log
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("audit_stats", |_args, world| {
+        let log = world.resource::<ConsoleAuditLog>();
+        info!("{} accesses, {} denied", log.len(), log.denied_count());
+        for (name, count) in log.counts() {
+            info!(name: "console_result", "  {:<24} {}", name, count);
+        }
+    }).description("Show command access counts from the audit log")
+        .permission(PermissionLevel::Server));
+
+    // audit_clear - Clear the audit log
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("audit_clear", |_args, world| {
+        world.resource_mut::<ConsoleAuditLog>().clear();
+        info!(name: "console_success", "Audit log cleared");
+    }).description("Clear the command audit log")
+        .permission(PermissionLevel::Server));
+
+    // quit - Exit the application immediately
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("quit", |args, _world| {
+        std::process::exit(args.parse::<i32>(0).unwrap_or(0));
+    }).description("Exit the application immediately with an optional exit code"));
+
+    // quit_clean - Exit after Bevy's shutdown systems run
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("quit_clean", |_args, world| {
+        world.write_message(AppExit::Success);
+    }).description("Exit the application once the current frame finishes"));
+
+    // toggle - Toggle a boolean convar
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("toggle", |args, world| {
+        if let Some(name) = args.get(0) {
+            let mut registry = world.resource_mut::<ConsoleRegistry>();
+
+            if let Some(current) = registry.get::<bool>(name) {
+                registry.set(name, !current);
+                info!("{} = {}", name, if !current { "1" } else { "0" });
+            } else if let Some(current) = registry.get::<i32>(name) {
+                let new_val = if current == 0 { 1 } else { 0 };
+                registry.set(name, new_val);
+                info!("{} = {}", name, new_val);
+            } else {
+                warn!("Cannot toggle '{}': not a boolean or integer", name);
+            }
+        } else {
+            warn!("Usage: toggle <convar>");
+        }
+    }).description("Toggle a boolean convar"));
+
+    // reset - Reset a convar to default
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("reset", |args, world| {
+        if let Some(name) = args.get(0) {
+            let mut registry = world.resource_mut::<ConsoleRegistry>();
+
+            if registry.reset(name) {
+                info!(name: "console_success", "{} reset to \"{}\"", name, registry.get_string(name).unwrap_or_default());
+            } else {
+                warn!("Unknown variable: {}", name);
+            }
+        } else {
+            warn!("Usage: reset <convar>");
+        }
+    }).description("Reset a convar to its default value"));
+
+    // set - Assign a convar, evaluating arithmetic for numeric types
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("set", |args, world| {
+        let Some(name) = args.get(0).filter(|_| args.len() > 1) else {
+            warn!("Usage: set <convar> <value>");
+            return;
+        };
+        let type_name = match world.resource::<ConsoleRegistry>().get_entry(name) {
+            Some(ConEntry::Var(meta)) => meta.type_name,
+            _ => {
+                warn!("Unknown variable: {}", name);
+                return;
+            }
+        };
+
+        let registry = world.resource::<ConsoleRegistry>();
+        let expr = args.join_from(1, " ");
+        // Only numeric types evaluate expressions; others take the text as-is
+        let value = match evaluate_arithmetic(&expr, |var| registry.get_string(var)?.parse().ok()) {
+            Ok(result) => match format_numeric(result, type_name) {
+                Some(Ok(value)) => value,
+                Some(Err(msg)) => {
+                    error!("Cannot set '{}': {}", name, msg);
+                    return;
+                }
+                None => expr,
+            },
+            Err(err) if matches!(type_name, "i32" | "i64" | "f32" | "f64") => {
+                error!("Cannot evaluate '{}': {}", expr, err);
+                return;
+            }
+            Err(_) => expr,
+        };
+
+        match set_var(world, name, &value) {
+            Ok(new_value) => world.resource_mut::<PendingCommands>().outputs.push(ConsoleOutputEvent::result(
+                format!("\"{}\" = \"{}\"", name, new_value)
+            )),
+            Err((_, msg)) => error!("Cannot set '{}': {}", name, msg),
+        }
+    }).description("Set a convar; numeric convars accept arithmetic like 'sv_gravity+100'")
+        .help_text("Expressions support + - * / and parentheses, and may refer to other numeric convars by name. The result is clamped to the convar's min/max."));
+
+    // differences - Show convars that differ from default
+    register_cmd(&mut registry, &mut handlers, ConCommand::new("differences", |_args, world| {
+        let registry = world.resource::<ConsoleRegistry>();
+
+        let mut count = 0;
+        for (name, meta) in registry.modified_vars_by_recency() {
+            info!("{} = \"{}\" (default: \"{}\")",
+                name, meta.get_string(), meta.default_string());
+            count += 1;
+        }
+
+        if count == 0 {
+            info!("No modified convars");
+        } else {
+            info!("{} modified convars", count);
+        }
+    }).description("Show convars with non-default values, most recently changed first"));
+
+    register_macro_commands(&mut registry, &mut handlers);
+
+    // UI commands (only with egui feature)
+    #[cfg(feature = "egui")]
+    register_ui_commands(&mut registry, &mut handlers);
+
+    // Persistence commands (only with persist feature)
+    #[cfg(feature = "persist")]
+    register_persist_commands(&mut registry, &mut handlers);
+}
+
+/// Register commands that configure the egui UI.
+#[cfg(feature = "egui")]
+fn register_ui_commands(
+    registry: &mut ConsoleRegistry,
+    handlers: &mut CommandHandlers,
+) {
+    // console_key - Change the key that opens the console
+    register_cmd(registry, handlers, ConCommand::new("console_key", |args, world| {
+        let mut ui_config = world.resource_mut::<ConsoleConfig>();
+        let Some(name) = args.get(0) else {
+            let current = config::key_code_name(ui_config.open_key)
+                .map_or_else(|| format!("{:?}", ui_config.open_key), str::to_string);
+            info!("console_key = {}", current);
+            return;
+        };
+        match config::parse_key_code(name) {
+            Some(key) => {
+                ui_config.open_key = key;
+                info!(name: "console_success", "Console key set to {}", name);
+            }
+            None => warn!("Unknown key '{}'", name),
+        }
+    }).description("Show or change the key that opens the console, e.g. 'console_key F1'"));
+}
+
+/// Register macro recording and playback commands.
+fn register_macro_commands(
+    registry: &mut ConsoleRegistry,
+    handlers: &mut CommandHandlers,
+) {
+    // record - Capture submitted commands into a macro
+    register_cmd(registry, handlers, ConCommand::new("record", |args, world| {
+        let mut macros = world.resource_mut::<macros::ConsoleMacros>();
+        match args.get(0) {
+            Some("stop") => match macros.stop_recording() {
+                Some((name, count)) => info!(name: "console_success", "Recorded {} commands into '{}'", count, name),
+                None => warn!("Not recording"),
+            },
+            Some(name) => {
+                if let Some(previous) = macros.recording() {
+                    warn!("Discarding unfinished recording '{}'", previous);
+                }
+                macros.start_recording(name);
+                info!(name: "console_success", "Recording macro '{}' ('record stop' to finish)", name);
+            }
+            None => warn!("Usage: record <name> | record stop"),
+        }
+    }).description("Record submitted commands into a macro ('record stop' to finish)"));
+
+    // playback - Replay a recorded macro
+    register_cmd(registry, handlers, ConCommand::new("playback", |args, world| {
+        let timed = args.get(0) == Some("--timed");
+        let Some(name) = args.get(if timed { 1 } else { 0 }) else {
+            warn!("Usage: playback [--timed] <name>");
+            return;
+        };
+        if !world.resource_mut::<macros::ConsoleMacros>().play(name, timed) {
+            warn!("Macro '{}' not found", name);
+        }
+    }).description("Replay a recorded macro")
+        .help_text("Usage:\n  playback <name>          submit all commands at once\n  playback --timed <name>  keep the recorded delays between commands"));
+
+    // macro_list - List recorded macros
+    register_cmd(registry, handlers, ConCommand::new("macro_list", |_args, world| {
+        let macros = world.resource::<macros::ConsoleMacros>();
+        let mut count = 0;
+        for (name, console_macro) in macros.iter() {
+            info!("  {} ({} commands)", name, console_macro.steps.len());
+            count += 1;
+        }
+        info!("{} macros", count);
+    }).description("List recorded macros"));
+
+    // macro_save - Write a macro to a script file
+    register_cmd(registry, handlers, ConCommand::new("macro_save", |args, world| {
+        let (Some(name), Some(filename)) = (args.get(0), args.get(1)) else {
+            warn!("Usage: macro_save <name> <file>");
+            return;
+        };
+        let macros = world.resource::<macros::ConsoleMacros>();
+        let Some(console_macro) = macros.get(name) else {
+            warn!("Macro '{}' not found", name);
+            return;
+        };
+        match console_macro.save(filename) {
+            Ok(()) => info!(name: "console_success", "Saved macro '{}' to '{}'", name, filename),
+            Err(e) => error!("Failed to save '{}': {}", filename, e),
+        }
+    }).description("Save a macro to a file"));
+
+    // macro_load - Read a macro from a script file, named after the file
+    register_cmd(registry, handlers, ConCommand::new("macro_load", |args, world| {
+        let Some(filename) = args.get(0) else {
+            warn!("Usage: macro_load <file>");
+            return;
+        };
+        let path = std::path::Path::new(filename);
+        let name = path.file_stem().map_or_else(|| filename.to_string(), |s| s.to_string_lossy().into_owned());
+        match macros::ConsoleMacro::load(path) {
+            Ok(console_macro) => {
+                info!(name: "console_success", "Loaded macro '{}' ({} commands)", name, console_macro.steps.len());
+                world.resource_mut::<macros::ConsoleMacros>().insert(name, console_macro);
+            }
+            Err(e) => error!("Failed to read '{}': {}", filename, e),
+        }
+    }).description("Load a macro from a file, named after the file"));
+}
+
+/// Register persistence-related commands.
+#[cfg(feature = "persist")]
+fn register_persist_commands(
+    registry: &mut ConsoleRegistry,
+    handlers: &mut CommandHandlers,
+) {
+    // exec - Execute commands from a file
+    register_cmd(registry, handlers, ConCommand::new("exec", |args, world| {
+        if let Some(filename) = args.get(0) {
+            let path = world.resource::<persist::ExecSearchPath>()
+                .resolve(filename)
+                .unwrap_or_else(|| filename.into());
+            queue_script(world, filename, &path);
+        } else {
+            warn!("Usage: exec <filename>");
+        }
+    }).description("Execute commands from a file, searching ExecSearchPath for bare names")
+        .help_text("Usage:\n  exec <name>       search ExecSearchPath, trying <name> then <name>.ron\n  exec <dir>/<file> run that exact file"));
+
+    // source / . - Like exec, but only runs files found through the search path
+    for name in ["source", "."] {
+        register_cmd(registry, handlers, ConCommand::new(name, |args, world| {
+            let Some(filename) = args.get(0) else {
+                warn!("Usage: source <filename>");
+                return;
+            };
+            // Nested sources resolve against the same top-level search path
+            match world.resource::<persist::ExecSearchPath>().resolve(filename) {
+                Some(path) => queue_script(world, filename, &path),
+                None => error!("'{}' not found in exec search path", filename),
+            }
+        }).description("Execute commands from a file found in ExecSearchPath"));
+    }
+
+    // host_writeconfig - Save ARCHIVE convars to file
+    register_cmd(registry, handlers, ConCommand::new("host_writeconfig", |args, world| {
+        let config_path = world.resource::<persist::ConfigPath>();
+        let filename = args.get(0).unwrap_or(&config_path.0);
+
+        let registry = world.resource::<ConsoleRegistry>();
+        let aliases = world.resource::<persist::CommandAliases>();
+
+        #[cfg(feature = "egui")]
+        let ui_settings = {
+            let ui_config = world.resource::<ConsoleConfig>();
+            let ui_state = world.get_resource::<crate::ui::ConsoleUiState>();
+            let (window_pos, window_size) = ui_state.map_or((None, None), persist::window_layout);
+            persist::UiSettings {
+                theme_name: ui_config.theme_name.as_deref(),
+                word_wrap: ui_state.map(|state| state.word_wrap),
+                open_key: config::key_code_name(ui_config.open_key),
+                window_pos,
+                window_size,
+                log_filter: ui_state.map(|state| state.log_filter),
+            }
+        };
+        #[cfg(not(feature = "egui"))]
+        let ui_settings = persist::UiSettings::default();
+
+        match persist::save_config(registry, aliases, ui_settings, filename) {
+            Ok(()) => {
+                info!(name: "console_success", "Saved config to '{}'", filename);
+            }
+            Err(e) => {
+                error!("Failed to save config: {}", e);
+            }
+        }
+    }).description("Save ARCHIVE convars to config file"));
+
+    // cvar_dump - Machine-readable JSON dump of all visible convars
+    register_cmd(registry, handlers, ConCommand::new("cvar_dump", |args, world| {
+        let json = match persist::dump_convars_json(world.resource::<ConsoleRegistry>()) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("Failed to dump convars: {}", e);
+                return;
+            }
+        };
+
+        if args.flag("--file") {
+            let Some(path) = args.flag_value("--file") else {
+                warn!("Usage: cvar_dump [--file <path>]");
+                return;
+            };
+            match std::fs::write(path, json) {
+                Ok(()) => info!(name: "console_success", "Dumped convars to '{}'", path),
+                Err(e) => error!("Failed to write '{}': {}", path, e),
+            }
+            return;
+        }
+
+        let outputs = &world.resource::<PendingCommands>().outputs;
+        outputs.push(ConsoleOutputEvent::result("BEGIN_JSON"));
+        outputs.push(ConsoleOutputEvent::result(json));
+        outputs.push(ConsoleOutputEvent::result("END_JSON"));
+    }).description("Print all non-hidden convars as JSON")
+        .help_text("Usage:\n  cvar_dump               print JSON between BEGIN_JSON and END_JSON lines\n  cvar_dump --file <path> write the JSON to a file"));
+
+    // alias - Create or list command aliases
+    register_cmd(registry, handlers, ConCommand::new("alias", |args, world| {
+        let mut aliases = world.resource_mut::<persist::CommandAliases>();
+
+        match (args.get(0), args.get(1)) {
+            (None, None) => {
+                // List all aliases
+                if aliases.is_empty() {
+                    info!("No aliases defined");
+                } else {
+                    info!("Aliases:");
+                    for (name, command) in aliases.iter() {
+                        info!("  {} -> {}", name, command);
+                    }
+                }
+            }
+            (Some(name), None) => {
+                // Show specific alias
+                if let Some(command) = aliases.get(name) {
+                    info!("{} -> {}", name, command);
+                } else {
+                    warn!("Alias '{}' not found", name);
+                }
+            }
+            (Some(name), Some(_)) => {
+                // Create alias (join remaining args as the command)
+                let command = args.join_from(1, " ");
+                aliases.add(name.to_string(), command.clone());
+                info!(name: "console_success", "Alias '{}' set to '{}'", name, command);
+            }
+            (None, Some(_)) => unreachable!(),
+        }
+    }).description("Create or list command aliases")
+        .help_text("Usage:\n  alias                  list all aliases\n  alias <name>           show one alias\n  alias <name> <command> define an alias\n\nThe command may use $1..$9, $@ (all args) and $# (arg count):\n  alias greet \"echo Hello $1!\""));
+
+    // alias_expand - Show how an alias chain expands without running it
+    register_cmd(registry, handlers, ConCommand::new("alias_expand", |args, world| {
+        if args.is_empty() {
+            warn!("Usage: alias_expand <name> [args...]");
+            return;
+        }
+
+        let input = args.join_from(0, " ");
+        let aliases = world.resource::<persist::CommandAliases>();
+        let (steps, end) = aliases.trace(&input);
+
+        for (i, step) in steps.iter().enumerate() {
+            info!(name: "console_result", "step {}: \"{}\" -> \"{}\"", i + 1, step.from, step.to);
+        }
+        match end {
+            persist::AliasTraceEnd::Resolved => {
+                let last = steps.last().map_or(input.as_str(), |step| step.to.as_str());
+                info!(name: "console_result", "step {}: \"{}\" -> (no further alias)", steps.len() + 1, last);
+            }
+            persist::AliasTraceEnd::Cycle => {
+                warn!("Alias cycle detected, stopping");
+            }
+            persist::AliasTraceEnd::DepthLimit => {
+                warn!("Stopped after {} expansions", persist::MAX_ALIAS_DEPTH);
+            }
+        }
+    }).description("Show the expansion of an alias without executing it"));
+
+    // unalias - Remove a command alias
+    register_cmd(registry, handlers, ConCommand::new("unalias", |args, world| {
+        if let Some(name) = args.get(0) {
+            let mut aliases = world.resource_mut::<persist::CommandAliases>();
+
+            if aliases.remove(name).is_some() {
+                info!(name: "console_success", "Removed alias '{}'", name);
+            } else {
+                warn!("Alias '{}' not found", name);
+            }
+        } else {
+            warn!("Usage: unalias <name>");
+        }
+    }).description("Remove a command alias"));
+}
+
+/// Lay `cells` out row by row in `columns` left-aligned, space-padded columns.
+fn format_columns(cells: &[String], columns: usize) -> Vec<String> {
+    let width = cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0) + 2;
+    cells
+        .chunks(columns.max(1))
+        .map(|row| {
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                if i + 1 < row.len() {
+                    line.push_str(&format!("{:<width$}", cell));
+                } else {
+                    line.push_str(cell);
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// Read a script file and submit each command line as script input.
+#[cfg(feature = "persist")]
+fn queue_script(world: &mut World, filename: &str, path: &std::path::Path) {
+    // We need to queue the commands, not execute them directly
+    // So we'll read the file and send input events
+    match std::fs::read_to_string(path) {
+        Ok(contents) => {
+            info!("Executing '{}'...", filename);
+            let mut count = 0;
+
+            // Submit each line as script input so it goes through the normal pipeline
+            let source = CommandSource::Script { file: filename.into() };
+            for line in contents.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+                    continue;
+                }
+
+                world.write_message(ConsoleInputEvent::new(line).with_source(source.clone()));
+                count += 1;
+            }
+
+            info!("Queued {} commands from '{}'", count, filename);
+        }
+        Err(e) => {
+            error!("Failed to read '{}': {}", filename, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_columns() {
+        let cells: Vec<String> = ["a = 1", "long_name = 2", "b = 3", "c = 4"].map(String::from).into();
+        assert_eq!(format_columns(&cells, 3), [
+            "a = 1          long_name = 2  b = 3",
+            "c = 4",
+        ]);
+        assert_eq!(format_columns(&cells, 1), cells);
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        assert_eq!(short_type_name("alloc::vec::Vec<alloc::string::String>"), "Vec<String>");
+        assert_eq!(short_type_name("f32"), "f32");
+
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32).description("World gravity"));
+        registry.register_var(ConVar::new("sv_secret", 1i32).flags(ConVarFlags::HIDDEN));
+        let _ = registry.register_cmd(ConCommand::new("kick", |_args, _world| {}).description("Kick a | player"));
+
+        let table = markdown_table(&registry);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[0], "| Name | Type | Default | Description |");
+        assert_eq!(lines[2], "| `kick` | command |  | Kick a \\| player |");
+        assert_eq!(lines[3], "| `sv_gravity` | f32 | 800 | World gravity |");
+        assert_eq!(lines.len(), 4);
+
+        let section = markdown_entry("sv_gravity", registry.get_entry("sv_gravity").unwrap());
+        assert!(section.starts_with("## sv_gravity\n\nWorld gravity\n"));
+        assert!(section.contains("| Default | `800` |"));
+    }
+}
@@ -1,11 +1,20 @@
 //! Configuration structs for the developer console.
 
+use std::time::Duration;
+
 use bevy::log::Level;
 use bevy::prelude::*;
-use bevy_egui::egui::{Color32, FontId, TextFormat};
+use bevy_egui::egui::{Color32, FontId, Stroke, TextFormat};
+
+/// Most columns `cvarlist` uses, see [`ConsoleConfig::cvarlist_columns`].
+pub const MAX_CVARLIST_COLUMNS: usize = 3;
 
 /// The configuration of the developer console.
+///
+/// Registered for reflection so it can be inspected and edited at runtime,
+/// e.g. with `bevy_inspector_egui`'s resource inspector.
 #[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
 pub struct ConsoleConfig {
     /// The colors used in the developer console.
     pub theme: ConsoleTheme,
@@ -13,6 +22,35 @@ pub struct ConsoleConfig {
     pub open_key: KeyCode,
     /// The key used to submit a command to the command parser.
     pub submit_key: KeyCode,
+    /// The smallest size the console window can be resized to.
+    ///
+    /// If the window still ends up smaller, e.g. because the game window
+    /// shrank, it is resized back on the next frame.
+    pub min_size: Vec2,
+    /// The largest size the console window can be resized to. Unbounded by default.
+    pub max_size: Vec2,
+    /// Whether to show the `?` button for the keyboard shortcut reference.
+    pub show_help_button: bool,
+    /// Name of the active theme preset, if any. Saved to and restored from the config file.
+    pub theme_name: Option<String>,
+    /// How long the window takes to grow to full height when opened. Zero opens instantly.
+    pub open_animation_duration: Duration,
+    /// Whether to render `**bold**`, `*italic*`, `` `code` `` and `---` in output lines.
+    pub render_markdown: bool,
+    /// The most autocomplete suggestions shown at once.
+    pub max_completions: usize,
+    /// Autocomplete suggestions scoring below this are hidden, so that weak
+    /// fuzzy matches don't crowd out the useful ones.
+    pub autocomplete_min_score: i32,
+    /// Whether autocomplete also matches the end of names and text in descriptions,
+    /// so `gravity` suggests `sv_gravity`.
+    pub autocomplete_suffix_search: bool,
+    /// Number of columns `cvarlist` lays variables out in, from 1 to [`MAX_CVARLIST_COLUMNS`].
+    ///
+    /// With more than one column, descriptions are left out to keep rows short.
+    pub cvarlist_columns: usize,
+    /// Hint shown in the command input while it is empty.
+    pub input_placeholder: String,
 }
 
 impl Default for ConsoleConfig {
@@ -21,12 +59,127 @@ impl Default for ConsoleConfig {
             theme: ConsoleTheme::ONE_DARK,
             open_key: KeyCode::Backquote,
             submit_key: KeyCode::Enter,
+            min_size: Vec2::new(400.0, 200.0),
+            max_size: Vec2::INFINITY,
+            show_help_button: true,
+            theme_name: None,
+            open_animation_duration: Duration::ZERO,
+            render_markdown: true,
+            max_completions: crate::ui::MAX_COMPLETION_SUGGESTIONS,
+            autocomplete_min_score: 5,
+            autocomplete_suffix_search: true,
+            cvarlist_columns: 1,
+            input_placeholder: "Type a command or 'help' to list commands...".to_string(),
         }
     }
 }
 
+impl ConsoleConfig {
+    /// Set the theme, recording its preset name if it matches one.
+    pub fn set_theme(&mut self, theme: ConsoleTheme) {
+        self.theme_name = theme.preset_name().map(str::to_string);
+        self.theme = theme;
+    }
+
+    /// Set the theme from a preset name.
+    ///
+    /// Returns `false` if no preset has that name.
+    pub fn set_theme_by_name(&mut self, name: &str) -> bool {
+        match ConsoleTheme::from_name(name) {
+            Some(theme) => {
+                self.theme = theme;
+                self.theme_name = Some(name.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Key names accepted by [`parse_key_code`]. The first name for a key is its canonical name.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Grave", KeyCode::Backquote),
+    ("Backquote", KeyCode::Backquote),
+    ("Tilde", KeyCode::Backquote),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("Tab", KeyCode::Tab),
+    ("Insert", KeyCode::Insert),
+    ("Delete", KeyCode::Delete),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Pause", KeyCode::Pause),
+    ("ScrollLock", KeyCode::ScrollLock),
+    ("Backslash", KeyCode::Backslash),
+    ("Slash", KeyCode::Slash),
+    ("Minus", KeyCode::Minus),
+    ("Equal", KeyCode::Equal),
+    ("Space", KeyCode::Space),
+    ("A", KeyCode::KeyA),
+    ("B", KeyCode::KeyB),
+    ("C", KeyCode::KeyC),
+    ("D", KeyCode::KeyD),
+    ("E", KeyCode::KeyE),
+    ("F", KeyCode::KeyF),
+    ("G", KeyCode::KeyG),
+    ("H", KeyCode::KeyH),
+    ("I", KeyCode::KeyI),
+    ("J", KeyCode::KeyJ),
+    ("K", KeyCode::KeyK),
+    ("L", KeyCode::KeyL),
+    ("M", KeyCode::KeyM),
+    ("N", KeyCode::KeyN),
+    ("O", KeyCode::KeyO),
+    ("P", KeyCode::KeyP),
+    ("Q", KeyCode::KeyQ),
+    ("R", KeyCode::KeyR),
+    ("S", KeyCode::KeyS),
+    ("T", KeyCode::KeyT),
+    ("U", KeyCode::KeyU),
+    ("V", KeyCode::KeyV),
+    ("W", KeyCode::KeyW),
+    ("X", KeyCode::KeyX),
+    ("Y", KeyCode::KeyY),
+    ("Z", KeyCode::KeyZ),
+    ("0", KeyCode::Digit0),
+    ("1", KeyCode::Digit1),
+    ("2", KeyCode::Digit2),
+    ("3", KeyCode::Digit3),
+    ("4", KeyCode::Digit4),
+    ("5", KeyCode::Digit5),
+    ("6", KeyCode::Digit6),
+    ("7", KeyCode::Digit7),
+    ("8", KeyCode::Digit8),
+    ("9", KeyCode::Digit9),
+];
+
+/// Parse a [`KeyCode`] from a name such as `"Grave"`, `"F1"`, or `"K"`, ignoring case.
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+/// The canonical name of a key accepted by [`parse_key_code`].
+pub fn key_code_name(key: KeyCode) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(_, k)| *k == key).map(|(name, _)| *name)
+}
+
 /// The colors used by the text in the developer console.
-#[derive(Reflect, Debug)]
+#[derive(Reflect, Debug, Clone, PartialEq)]
 pub struct ConsoleTheme {
     /// The font used in the developer console.
     #[reflect(ignore)]
@@ -45,6 +198,16 @@ pub struct ConsoleTheme {
     pub debug: Color,
     /// The color of the "trace" level.
     pub trace: Color,
+    /// The color of success confirmations.
+    pub success: Color,
+    /// The color of the command name in the input field.
+    pub command: Color,
+    /// The color of quoted string arguments in the input field.
+    pub string_arg: Color,
+    /// The color of numeric arguments in the input field.
+    pub number_arg: Color,
+    /// The background briefly shown behind an output line after it is copied.
+    pub copy_highlight_color: Color,
 }
 
 /// Helper trait that allows conversion between [`bevy::Color`](Color) and [`egui::Color32`].
@@ -94,6 +257,11 @@ impl ConsoleTheme {
         info: Color::srgb(0.55, 0.76, 0.4),
         debug: Color::srgb(0.29, 0.65, 0.94),
         trace: Color::srgb(0.78, 0.45, 0.89),
+        success: Color::srgb(0.6, 0.85, 0.45),
+        command: Color::srgb(0.34, 0.71, 0.76),
+        string_arg: Color::srgb(0.9, 0.75, 0.48),
+        number_arg: Color::srgb(0.82, 0.6, 0.4),
+        copy_highlight_color: Color::srgba(0.34, 0.71, 0.76, 0.3),
     };
 
     /// High contrast theme, might help some people.
@@ -106,8 +274,78 @@ impl ConsoleTheme {
         info: Color::srgb(0.0, 1.0, 0.0),
         debug: Color::srgb(0.25, 0.25, 1.0),
         trace: Color::srgb(1.0, 0.0, 1.0),
+        success: Color::srgb(0.0, 1.0, 0.0),
+        command: Color::srgb(0.0, 1.0, 1.0),
+        string_arg: Color::srgb(1.0, 1.0, 0.0),
+        number_arg: Color::srgb(1.0, 0.5, 0.0),
+        copy_highlight_color: Color::srgba(0.0, 1.0, 1.0, 0.4),
     };
 
+    /// Names of the built-in presets, accepted by [`Self::from_name`].
+    pub const PRESET_NAMES: &[&str] = &["dark", "light", "solarized_dark", "high_contrast"];
+
+    /// The default dark theme (One Dark).
+    pub fn dark() -> Self {
+        Self::ONE_DARK
+    }
+
+    /// A light theme for bright backgrounds.
+    pub fn light() -> Self {
+        Self {
+            font: FontId::monospace(14.0),
+            dark: Color::srgb(0.55, 0.55, 0.58),
+            text_color: Color::srgb(0.2, 0.2, 0.22),
+            error: Color::srgb(0.8, 0.1, 0.1),
+            warning: Color::srgb(0.75, 0.45, 0.0),
+            info: Color::srgb(0.1, 0.55, 0.1),
+            debug: Color::srgb(0.1, 0.35, 0.8),
+            trace: Color::srgb(0.55, 0.2, 0.65),
+            success: Color::srgb(0.15, 0.6, 0.2),
+            command: Color::srgb(0.0, 0.5, 0.55),
+            string_arg: Color::srgb(0.6, 0.45, 0.0),
+            number_arg: Color::srgb(0.75, 0.35, 0.0),
+            copy_highlight_color: Color::srgba(0.0, 0.5, 0.55, 0.25),
+        }
+    }
+
+    /// Ethan Schoonover's Solarized Dark palette.
+    pub fn solarized_dark() -> Self {
+        Self {
+            font: FontId::monospace(14.0),
+            dark: Color::srgb(0.35, 0.43, 0.46),
+            text_color: Color::srgb(0.51, 0.58, 0.59),
+            error: Color::srgb(0.86, 0.2, 0.18),
+            warning: Color::srgb(0.71, 0.54, 0.0),
+            info: Color::srgb(0.52, 0.6, 0.0),
+            debug: Color::srgb(0.15, 0.55, 0.82),
+            trace: Color::srgb(0.83, 0.21, 0.51),
+            success: Color::srgb(0.52, 0.6, 0.0),
+            command: Color::srgb(0.16, 0.63, 0.6),
+            string_arg: Color::srgb(0.71, 0.54, 0.0),
+            number_arg: Color::srgb(0.8, 0.29, 0.09),
+            copy_highlight_color: Color::srgba(0.16, 0.63, 0.6, 0.3),
+        }
+    }
+
+    /// Get a preset theme by name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized_dark" => Some(Self::solarized_dark()),
+            "high_contrast" => Some(Self::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the preset this theme matches, if any.
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESET_NAMES
+            .iter()
+            .copied()
+            .find(|name| Self::from_name(name).as_ref() == Some(self))
+    }
+
     /// Returns a [`Color32`] based on the `level`
     pub fn color_level(&self, level: Level) -> Color32 {
         match level {
@@ -145,10 +383,94 @@ impl ConsoleTheme {
         }
     }
 
+    /// Returns a [`TextFormat`] for search matches, drawn on a yellow background.
+    pub fn format_highlight(&self) -> TextFormat {
+        TextFormat {
+            font_id: self.font.clone(),
+            color: Color32::BLACK,
+            background: Color32::YELLOW,
+            ..default()
+        }
+    }
+
+    /// Returns a [`TextFormat`] for links, drawn blue and underlined.
+    pub fn format_link(&self) -> TextFormat {
+        let color = Color32::from_rgb(0x4d, 0x9d, 0xf0);
+        TextFormat {
+            font_id: self.font.clone(),
+            color,
+            underline: Stroke::new(1.0, color),
+            ..default()
+        }
+    }
+
     define_text_format_method!(format_dark, dark);
     define_text_format_method!(format_error, error);
     define_text_format_method!(format_warning, warning);
     define_text_format_method!(format_info, info);
     define_text_format_method!(format_debug, debug);
     define_text_format_method!(format_trace, trace);
+    define_text_format_method!(format_success, success);
+    define_text_format_method!(format_command, command);
+    define_text_format_method!(format_string_arg, string_arg);
+    define_text_format_method!(format_number_arg, number_arg);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::TypeId;
+
+    #[test]
+    fn test_parse_key_code() {
+        assert_eq!(parse_key_code("grave"), Some(KeyCode::Backquote));
+        assert_eq!(parse_key_code("F1"), Some(KeyCode::F1));
+        assert_eq!(parse_key_code("k"), Some(KeyCode::KeyK));
+        assert_eq!(parse_key_code("NotAKey"), None);
+
+        assert_eq!(key_code_name(KeyCode::Backquote), Some("Grave"));
+        for (name, key) in KEY_NAMES {
+            assert_eq!(parse_key_code(name), Some(*key));
+            assert_eq!(parse_key_code(key_code_name(*key).unwrap()), Some(*key));
+        }
+    }
+
+    #[test]
+    fn test_theme_presets() {
+        for name in ConsoleTheme::PRESET_NAMES {
+            let theme = ConsoleTheme::from_name(name).unwrap();
+            assert_eq!(theme.preset_name(), Some(*name));
+        }
+        assert!(ConsoleTheme::from_name("unknown").is_none());
+
+        let custom = ConsoleTheme {
+            error: Color::srgb(0.1, 0.2, 0.3),
+            ..ConsoleTheme::light()
+        };
+        assert_eq!(custom.preset_name(), None);
+    }
+
+    #[test]
+    fn test_config_set_theme() {
+        let mut config = ConsoleConfig::default();
+
+        config.set_theme(ConsoleTheme::light());
+        assert_eq!(config.theme_name.as_deref(), Some("light"));
+
+        assert!(config.set_theme_by_name("solarized_dark"));
+        assert_eq!(config.theme, ConsoleTheme::solarized_dark());
+
+        assert!(!config.set_theme_by_name("unknown"));
+        assert_eq!(config.theme_name.as_deref(), Some("solarized_dark"));
+    }
+
+    #[test]
+    fn test_config_reflect_resource() {
+        let mut registry = bevy::reflect::TypeRegistry::default();
+        registry.register::<ConsoleConfig>();
+
+        assert!(registry.get_type_data::<ReflectResource>(TypeId::of::<ConsoleConfig>()).is_some());
+        // Nested theme is registered alongside the config
+        assert!(registry.get(TypeId::of::<ConsoleTheme>()).is_some());
+    }
 }
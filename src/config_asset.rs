@@ -0,0 +1,228 @@
+//! Loading [`ConsoleConfig`] from a RON asset.
+//!
+//! Enabled with [`ConsolePlugin::config_asset`](crate::ConsolePlugin::config_asset).
+//! The asset is applied once it loads and again whenever it changes, so with
+//! Bevy's `file_watcher` feature the console can be restyled without
+//! recompiling. Settings it sets override those restored from the
+//! [`ConsoleConfigFile`](crate::ConsoleConfigFile).
+//!
+//! ```ron
+//! (
+//!     theme: Some("solarized_dark"),
+//!     open_key: Some("F1"),
+//!     min_size: Some((500.0, 250.0)),
+//!     input_placeholder: Some("Try 'spawn', 'status', or 'help'"),
+//! )
+//! ```
+
+use std::time::Duration;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::config::{parse_key_code, ConsoleConfig};
+use crate::persist::ConfigError;
+
+/// Plugin that loads a [`ConsoleConfigAsset`] and applies it to [`ConsoleConfig`].
+pub(crate) struct ConsoleConfigAssetPlugin {
+    /// Asset path of the config.
+    pub path: String,
+}
+
+impl Plugin for ConsoleConfigAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ConsoleConfigAsset>()
+            .register_asset_loader(ConsoleConfigAssetLoader)
+            .insert_resource(ConsoleConfigAssetPath(self.path.clone()))
+            .add_systems(Startup, load_config_asset)
+            .add_systems(Update, apply_config_asset);
+    }
+}
+
+/// The settings of [`ConsoleConfig`] that can be loaded from an asset.
+///
+/// Fields left out of the file keep their current value.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ConsoleConfigAsset {
+    /// Name of a theme preset, see [`ConsoleTheme::from_name`](crate::ConsoleTheme::from_name).
+    pub theme: Option<String>,
+    /// Name of the key that opens the console, see [`parse_key_code`].
+    pub open_key: Option<String>,
+    /// Name of the key that submits a command.
+    pub submit_key: Option<String>,
+    /// The smallest window size, as `(width, height)`.
+    pub min_size: Option<(f32, f32)>,
+    /// The largest window size, as `(width, height)`.
+    pub max_size: Option<(f32, f32)>,
+    /// Whether to show the `?` button for the keyboard shortcut reference.
+    pub show_help_button: Option<bool>,
+    /// Length of the open and close animation in seconds.
+    pub open_animation_duration: Option<f32>,
+    /// Whether to render Markdown in output lines.
+    pub render_markdown: Option<bool>,
+    /// The most autocomplete suggestions shown at once.
+    pub max_completions: Option<usize>,
+    /// Autocomplete suggestions scoring below this are hidden.
+    pub autocomplete_min_score: Option<i32>,
+    /// Whether autocomplete also matches name suffixes and descriptions.
+    pub autocomplete_suffix_search: Option<bool>,
+    /// Number of columns `cvarlist` uses.
+    pub cvarlist_columns: Option<usize>,
+    /// Hint shown in the empty command input.
+    pub input_placeholder: Option<String>,
+}
+
+impl ConsoleConfigAsset {
+    /// Copy the set fields into `config`, warning about unknown themes and keys.
+    pub fn apply_to(&self, config: &mut ConsoleConfig) {
+        if let Some(name) = &self.theme
+            && !config.set_theme_by_name(name)
+        {
+            warn!("Unknown console theme '{}'", name);
+        }
+        for (name, key) in [(&self.open_key, &mut config.open_key), (&self.submit_key, &mut config.submit_key)] {
+            if let Some(name) = name {
+                match parse_key_code(name) {
+                    Some(code) => *key = code,
+                    None => warn!("Unknown key '{}'", name),
+                }
+            }
+        }
+        if let Some((width, height)) = self.min_size {
+            config.min_size = Vec2::new(width, height);
+        }
+        if let Some((width, height)) = self.max_size {
+            config.max_size = Vec2::new(width, height);
+        }
+        if let Some(show) = self.show_help_button {
+            config.show_help_button = show;
+        }
+        if let Some(secs) = self.open_animation_duration {
+            config.open_animation_duration = Duration::from_secs_f32(secs.max(0.0));
+        }
+        if let Some(render) = self.render_markdown {
+            config.render_markdown = render;
+        }
+        if let Some(max) = self.max_completions {
+            config.max_completions = max;
+        }
+        if let Some(score) = self.autocomplete_min_score {
+            config.autocomplete_min_score = score;
+        }
+        if let Some(suffix_search) = self.autocomplete_suffix_search {
+            config.autocomplete_suffix_search = suffix_search;
+        }
+        if let Some(columns) = self.cvarlist_columns {
+            config.cvarlist_columns = columns;
+        }
+        if let Some(placeholder) = &self.input_placeholder {
+            config.input_placeholder.clone_from(placeholder);
+        }
+    }
+}
+
+/// Loads [`ConsoleConfigAsset`]s from RON. Found by asset type, so any extension works.
+#[derive(TypePath, Default)]
+struct ConsoleConfigAssetLoader;
+
+impl AssetLoader for ConsoleConfigAssetLoader {
+    type Asset = ConsoleConfigAsset;
+    type Settings = ();
+    type Error = ConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let path = load_context.path().to_string();
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|e| ConfigError::Io(path.clone(), e.to_string()))?;
+        ron::de::from_bytes(&bytes).map_err(|e| ConfigError::Parse(path, e.to_string()))
+    }
+}
+
+/// Asset path passed to [`ConsolePlugin::config_asset`](crate::ConsolePlugin::config_asset).
+#[derive(Resource)]
+struct ConsoleConfigAssetPath(String);
+
+/// Handle keeping the config asset loaded.
+#[derive(Resource)]
+struct ConsoleConfigAssetHandle(Handle<ConsoleConfigAsset>);
+
+/// Startup system that starts loading the config asset.
+fn load_config_asset(mut commands: Commands, asset_server: Res<AssetServer>, path: Res<ConsoleConfigAssetPath>) {
+    commands.insert_resource(ConsoleConfigAssetHandle(asset_server.load(&path.0)));
+}
+
+/// System that applies the config asset when it loads or changes.
+fn apply_config_asset(
+    mut events: MessageReader<AssetEvent<ConsoleConfigAsset>>,
+    handle: Option<Res<ConsoleConfigAssetHandle>>,
+    assets: Res<Assets<ConsoleConfigAsset>>,
+    mut config: ResMut<ConsoleConfig>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    let changed = events.read().any(|event| match event {
+        AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id == handle.0.id(),
+        _ => false,
+    });
+    if changed && let Some(asset) = assets.get(&handle.0) {
+        asset.apply_to(&mut config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_asset_overrides_set_fields() {
+        let asset: ConsoleConfigAsset = ron::from_str(
+            r#"(theme: Some("light"), open_key: Some("F1"), min_size: Some((500.0, 250.0)), input_placeholder: Some("Try 'spawn'"))"#,
+        )
+        .unwrap();
+        let mut config = ConsoleConfig::default();
+        asset.apply_to(&mut config);
+
+        assert_eq!(config.theme_name.as_deref(), Some("light"));
+        assert_eq!(config.open_key, KeyCode::F1);
+        assert_eq!(config.min_size, Vec2::new(500.0, 250.0));
+        assert_eq!(config.input_placeholder, "Try 'spawn'");
+        // Fields left out keep their defaults
+        assert_eq!(config.submit_key, KeyCode::Enter);
+        assert_eq!(config.max_completions, ConsoleConfig::default().max_completions);
+    }
+
+    #[test]
+    fn test_config_asset_loads_through_asset_server() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("console_config.ron"), "(cvarlist_columns: Some(2))").unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin {
+            file_path: dir.path().display().to_string(),
+            ..default()
+        }));
+        app.init_resource::<ConsoleConfig>();
+        app.add_plugins(ConsoleConfigAssetPlugin { path: "console_config.ron".into() });
+
+        for _ in 0..100 {
+            app.update();
+            if app.world().resource::<ConsoleConfig>().cvarlist_columns == 2 {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("config asset was not applied");
+    }
+}
@@ -0,0 +1,236 @@
+//! Arithmetic expressions for the `set` command.
+//!
+//! A small recursive descent evaluator over `+`, `-`, `*`, `/`, parentheses,
+//! numbers, and ConVar names:
+//!
+//! ```text
+//! expr   := term (('+' | '-') term)*
+//! term   := factor (('*' | '/') factor)*
+//! factor := ('+' | '-') factor | number | name | '(' expr ')'
+//! ```
+
+/// Deepest run of nested parentheses and signs an expression may contain.
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Arithmetic evaluation error types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArithmeticError {
+    /// A character that can't appear at this point.
+    UnexpectedChar { position: usize, found: char },
+    /// The expression ended early.
+    UnexpectedEnd,
+    /// A name that doesn't resolve to a numeric value.
+    UnknownVariable { name: String },
+    /// Division by zero.
+    DivisionByZero,
+    /// Parentheses or signs nested deeper than [`MAX_NESTING_DEPTH`].
+    NestingTooDeep,
+}
+
+impl std::fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::UnexpectedChar { position, found } => {
+                write!(f, "unexpected '{}' at position {}", found, position)
+            }
+            ArithmeticError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ArithmeticError::UnknownVariable { name } => {
+                write!(f, "'{}' is not a numeric variable", name)
+            }
+            ArithmeticError::DivisionByZero => write!(f, "division by zero"),
+            ArithmeticError::NestingTooDeep => {
+                write!(f, "expression nested deeper than {} levels", MAX_NESTING_DEPTH)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+/// Evaluate an arithmetic expression, resolving names with `lookup`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_console_two::core::evaluate_arithmetic;
+///
+/// let value = evaluate_arithmetic("(sv_gravity + 100) / 2", |name| {
+///     (name == "sv_gravity").then_some(800.0)
+/// });
+/// assert_eq!(value, Ok(450.0));
+/// ```
+pub fn evaluate_arithmetic(
+    expr: &str,
+    lookup: impl FnMut(&str) -> Option<f64>,
+) -> Result<f64, ArithmeticError> {
+    let mut parser = Parser { input: expr, pos: 0, depth: 0, lookup };
+    let value = parser.expr()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        Some(found) => Err(ArithmeticError::UnexpectedChar { position: parser.pos, found }),
+        None => Ok(value),
+    }
+}
+
+struct Parser<'a, F> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+    lookup: F,
+}
+
+impl<F: FnMut(&str) -> Option<f64>> Parser<'_, F> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Consume `op` if it is the next non-whitespace character.
+    fn eat(&mut self, op: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume characters while `pred` holds, returning them.
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|&c| pred(c)) {
+            self.pos += c.len_utf8();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn expr(&mut self) -> Result<f64, ArithmeticError> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, ArithmeticError> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                let divisor = self.factor()?;
+                if divisor == 0.0 {
+                    return Err(ArithmeticError::DivisionByZero);
+                }
+                value /= divisor;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Parse a factor, counting nesting so hostile input can't overflow the stack.
+    fn factor(&mut self) -> Result<f64, ArithmeticError> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(ArithmeticError::NestingTooDeep);
+        }
+        self.depth += 1;
+        let value = self.nested_factor();
+        self.depth -= 1;
+        value
+    }
+
+    fn nested_factor(&mut self) -> Result<f64, ArithmeticError> {
+        if self.eat('-') {
+            return Ok(-self.factor()?);
+        }
+        if self.eat('+') {
+            return self.factor();
+        }
+        if self.eat('(') {
+            let value = self.expr()?;
+            if !self.eat(')') {
+                return Err(self.unexpected());
+            }
+            return Ok(value);
+        }
+
+        self.skip_whitespace();
+        let position = self.pos;
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                number.parse().map_err(|_| ArithmeticError::UnexpectedChar { position, found: c })
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '.').to_string();
+                (self.lookup)(&name).ok_or(ArithmeticError::UnknownVariable { name })
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn unexpected(&mut self) -> ArithmeticError {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(found) => ArithmeticError::UnexpectedChar { position: self.pos, found },
+            None => ArithmeticError::UnexpectedEnd,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: &str) -> Result<f64, ArithmeticError> {
+        evaluate_arithmetic(expr, |name| match name {
+            "sv_gravity" => Some(800.0),
+            "host_timescale" => Some(0.5),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        assert_eq!(eval("800"), Ok(800.0));
+        assert_eq!(eval("sv_gravity+100"), Ok(900.0));
+        assert_eq!(eval("2 + 3 * 4"), Ok(14.0));
+        assert_eq!(eval("(2 + 3) * 4"), Ok(20.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("-sv_gravity / -(4)"), Ok(200.0));
+        assert_eq!(eval("sv_gravity * host_timescale"), Ok(400.0));
+        assert_eq!(eval("1.5 * 2"), Ok(3.0));
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic_errors() {
+        assert_eq!(eval("1 / 0"), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(eval("1 / (sv_gravity - 800)"), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(eval("sv_missing + 1"), Err(ArithmeticError::UnknownVariable { name: "sv_missing".into() }));
+        assert_eq!(eval("(1 + 2"), Err(ArithmeticError::UnexpectedEnd));
+        assert_eq!(eval("1 + "), Err(ArithmeticError::UnexpectedEnd));
+        assert_eq!(eval("1 2"), Err(ArithmeticError::UnexpectedChar { position: 2, found: '2' }));
+        assert_eq!(eval("1 % 2"), Err(ArithmeticError::UnexpectedChar { position: 2, found: '%' }));
+        assert!(eval("1.2.3").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic_nesting() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(eval(&nested(MAX_NESTING_DEPTH - 1)), Ok(1.0));
+        assert_eq!(eval(&nested(100_000)), Err(ArithmeticError::NestingTooDeep));
+        assert_eq!(eval(&format!("{}1", "-".repeat(100_000))), Err(ArithmeticError::NestingTooDeep));
+    }
+}
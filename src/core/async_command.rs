@@ -0,0 +1,52 @@
+//! Commands that finish on a background task.
+//!
+//! Handlers created with [`ConCommand::new_async`](super::ConCommand::new_async)
+//! return a future that is spawned on the [`AsyncComputeTaskPool`]. Its output
+//! is added to the console once the task completes, usually a frame or more
+//! after the command ran.
+
+use bevy::prelude::*;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+
+use super::ConsoleOutputEvent;
+
+/// Resource holding the tasks of async commands that haven't finished yet.
+#[derive(Resource, Default)]
+pub struct PendingAsyncCommands {
+    tasks: Vec<Task<Vec<ConsoleOutputEvent>>>,
+}
+
+impl PendingAsyncCommands {
+    /// Spawn `future` on the [`AsyncComputeTaskPool`] and track it.
+    pub fn spawn(&mut self, future: impl Future<Output = Vec<ConsoleOutputEvent>> + Send + 'static) {
+        self.tasks.push(AsyncComputeTaskPool::get().spawn(future));
+    }
+
+    /// Number of commands still running.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Whether no command is running.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+/// System that moves the output of finished async commands into the pending output.
+pub(crate) fn poll_async_commands(
+    mut async_commands: ResMut<PendingAsyncCommands>,
+    pending: Res<crate::PendingCommands>,
+) {
+    if async_commands.is_empty() {
+        return;
+    }
+
+    async_commands.tasks.retain_mut(|task| match check_ready(task) {
+        Some(outputs) => {
+            pending.outputs.extend(outputs);
+            false
+        }
+        None => true,
+    });
+}
@@ -0,0 +1,208 @@
+//! Record of command access decisions, for reviewing who ran what.
+//!
+//! Every command invocation and variable write the pipeline resolves is
+//! recorded in [`ConsoleAuditLog`], including ones rejected by permissions,
+//! `sv_cheats` or the whitelist. The `audit_export`, `audit_stats` and
+//! `audit_clear` commands read and reset it.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+
+use super::PermissionLevel;
+
+/// Default number of records kept by [`ConsoleAuditLog`].
+pub const DEFAULT_AUDIT_LOG_SIZE: usize = 1000;
+
+/// One command access decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize))]
+pub struct AuditRecord {
+    /// When the command ran, in milliseconds since the Unix epoch.
+    pub timestamp_unix_ms: u64,
+    /// The command name as registered.
+    pub command_name: Box<str>,
+    /// The level the command requires.
+    pub required_permission: PermissionLevel,
+    /// The level the console had.
+    pub actual_permission: PermissionLevel,
+    /// Whether the command was allowed to run.
+    pub allowed: bool,
+    /// Number of arguments passed. Values are left out, since they may contain player data.
+    pub args_count: usize,
+}
+
+impl AuditRecord {
+    /// Create a record timestamped now.
+    pub fn new(
+        command_name: impl Into<Box<str>>,
+        required_permission: PermissionLevel,
+        actual_permission: PermissionLevel,
+        allowed: bool,
+        args_count: usize,
+    ) -> Self {
+        let timestamp_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Self {
+            timestamp_unix_ms,
+            command_name: command_name.into(),
+            required_permission,
+            actual_permission,
+            allowed,
+            args_count,
+        }
+    }
+}
+
+/// Resource holding the most recent command access decisions, oldest first.
+#[derive(Resource, Debug, Clone)]
+pub struct ConsoleAuditLog {
+    records: VecDeque<AuditRecord>,
+    capacity: usize,
+}
+
+impl Default for ConsoleAuditLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_AUDIT_LOG_SIZE)
+    }
+}
+
+impl ConsoleAuditLog {
+    /// Create an empty log keeping at most `capacity` records.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Add a record, dropping the oldest one if the log is full.
+    pub fn push(&mut self, record: AuditRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// All stored records, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &AuditRecord> {
+        self.records.iter()
+    }
+
+    /// Number of stored records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether no records are stored.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Number of stored records that were denied.
+    pub fn denied_count(&self) -> usize {
+        self.records.iter().filter(|record| !record.allowed).count()
+    }
+
+    /// Records per command, most accessed first.
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for record in &self.records {
+            *counts.entry(&record.command_name).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
+    /// Remove all stored records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// The records as CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("timestamp_unix_ms,command_name,required_permission,actual_permission,allowed,args_count\n");
+        for record in &self.records {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                record.timestamp_unix_ms,
+                csv_field(&record.command_name),
+                record.required_permission.name(),
+                record.actual_permission.name(),
+                record.allowed,
+                record.args_count,
+            );
+        }
+        csv
+    }
+
+    /// The records as a pretty-printed JSON array of objects.
+    #[cfg(feature = "persist")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.records)
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, allowed: bool) -> AuditRecord {
+        AuditRecord {
+            timestamp_unix_ms: 1_700_000_000_000,
+            command_name: name.into(),
+            required_permission: PermissionLevel::Admin,
+            actual_permission: PermissionLevel::User,
+            allowed,
+            args_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_audit_log_export() {
+        let mut log = ConsoleAuditLog::with_capacity(2);
+        log.push(record("echo", true));
+        log.push(record("kick", false));
+        log.push(record("say,\"hi\"", false));
+
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.denied_count(), 2);
+        assert_eq!(
+            log.to_csv(),
+            "timestamp_unix_ms,command_name,required_permission,actual_permission,allowed,args_count\n\
+             1700000000000,kick,Admin,User,false,1\n\
+             1700000000000,\"say,\"\"hi\"\"\",Admin,User,false,1\n"
+        );
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_audit_log_json() {
+        let mut log = ConsoleAuditLog::default();
+        log.push(record("say,\"hi\"", false));
+
+        let json: serde_json::Value = serde_json::from_str(&log.to_json().unwrap()).unwrap();
+        assert_eq!(json[0]["command_name"], "say,\"hi\"");
+        assert_eq!(json[0]["required_permission"], "Admin");
+        assert_eq!(json[0]["actual_permission"], "User");
+        assert_eq!(json[0]["allowed"], false);
+        assert_eq!(ConsoleAuditLog::default().to_json().unwrap(), "[]");
+    }
+}
@@ -0,0 +1,111 @@
+//! Recent ConVar changes, kept for systems that don't run every frame.
+//!
+//! [`ConVarChangedEvent`]s are dropped after two updates, so a system on a
+//! timer can miss them. [`ConVarChangeLog`] keeps the most recent changes
+//! regardless of when they happened.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::ConVarChangedEvent;
+
+/// Default number of changes kept by [`ConVarChangeLog`].
+pub const DEFAULT_CHANGE_LOG_SIZE: usize = 100;
+
+/// Resource holding the most recent ConVar changes, oldest first.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn apply_video_settings(log: Res<ConVarChangeLog>) {
+///     if let Some(change) = log.changes_for("r_vsync").last() {
+///         set_vsync(change.new_value == "1");
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ConVarChangeLog {
+    changes: VecDeque<ConVarChangedEvent>,
+    capacity: usize,
+}
+
+impl Default for ConVarChangeLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CHANGE_LOG_SIZE)
+    }
+}
+
+impl ConVarChangeLog {
+    /// Create an empty log keeping at most `capacity` changes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            changes: VecDeque::with_capacity(capacity.min(DEFAULT_CHANGE_LOG_SIZE)),
+            capacity,
+        }
+    }
+
+    /// Record a change, dropping the oldest one if the log is full.
+    pub fn push(&mut self, change: ConVarChangedEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.changes.len() == self.capacity {
+            self.changes.pop_front();
+        }
+        self.changes.push_back(change);
+    }
+
+    /// All stored changes, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &ConVarChangedEvent> {
+        self.changes.iter()
+    }
+
+    /// Stored changes to the ConVar `name`, oldest first.
+    pub fn changes_for<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ConVarChangedEvent> {
+        self.changes.iter().filter(move |change| &*change.name == name)
+    }
+
+    /// Number of stored changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Whether no changes are stored.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Remove all stored changes.
+    pub fn clear(&mut self) {
+        self.changes.clear();
+    }
+}
+
+/// System that records this frame's changes in the [`ConVarChangeLog`].
+pub(crate) fn record_convar_changes(
+    mut changes: MessageReader<ConVarChangedEvent>,
+    mut log: ResMut<ConVarChangeLog>,
+) {
+    for change in changes.read() {
+        log.push(change.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_log_capacity() {
+        let mut log = ConVarChangeLog::with_capacity(2);
+        log.push(ConVarChangedEvent::new("sv_gravity", "800", "400"));
+        log.push(ConVarChangedEvent::new("cl_fov", "90", "100"));
+        log.push(ConVarChangedEvent::new("sv_gravity", "400", "200"));
+
+        assert_eq!(log.len(), 2);
+        let values: Vec<_> = log.changes_for("sv_gravity").map(|c| c.new_value.as_str()).collect();
+        assert_eq!(values, ["200"]);
+        assert_eq!(log.iter().next().unwrap().name.as_ref(), "cl_fov");
+    }
+}
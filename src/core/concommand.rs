@@ -4,7 +4,7 @@
 
 use bevy::prelude::*;
 
-use super::{ConVarFlags, PermissionLevel};
+use super::{CommandError, ConVarFlags, ConsoleOutputEvent, PermissionLevel};
 
 /// Arguments passed to a command handler.
 #[derive(Debug, Clone)]
@@ -73,6 +73,17 @@ impl<'a> CommandArgs<'a> {
         self.args.iter().copied()
     }
 
+    /// Check whether a flag such as `--verbose` was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.args.contains(&name)
+    }
+
+    /// Get the argument following a flag, e.g. the path in `--file <path>`.
+    pub fn flag_value(&self, name: &str) -> Option<&str> {
+        let index = self.args.iter().position(|arg| *arg == name)?;
+        self.args.get(index + 1).copied()
+    }
+
     /// Join all arguments with a space (useful for string arguments).
     pub fn join(&self, separator: &str) -> String {
         self.args.join(separator)
@@ -92,6 +103,78 @@ impl<'a> std::ops::Index<usize> for CommandArgs<'a> {
     }
 }
 
+/// Expected type of a command argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    /// Any text.
+    String,
+    /// A whole number.
+    Int,
+    /// A decimal number.
+    Float,
+    /// `1`/`0`, `true`/`false`, `yes`/`no` or `on`/`off`.
+    Bool,
+    /// One of the listed values, ignoring case.
+    Enum(&'static [&'static str]),
+}
+
+impl ArgType {
+    /// Check whether `value` is valid for this type.
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            ArgType::String => true,
+            ArgType::Int => value.parse::<i64>().is_ok(),
+            ArgType::Float => value.parse::<f64>().is_ok(),
+            ArgType::Bool => <bool as super::ConVarValue>::parse(value).is_some(),
+            ArgType::Enum(choices) => choices.iter().any(|choice| choice.eq_ignore_ascii_case(value)),
+        }
+    }
+}
+
+impl std::fmt::Display for ArgType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgType::String => write!(f, "String"),
+            ArgType::Int => write!(f, "Int"),
+            ArgType::Float => write!(f, "Float"),
+            ArgType::Bool => write!(f, "Bool"),
+            ArgType::Enum(choices) => write!(f, "{}", choices.join("|")),
+        }
+    }
+}
+
+/// Definition of one positional command argument.
+///
+/// # Examples
+///
+/// ```ignore
+/// ConCommand::new("bind", bind_handler).schema(&[
+///     ArgDef { name: "key", ty: ArgType::String, required: true, description: "Key to bind" },
+///     ArgDef { name: "command", ty: ArgType::String, required: true, description: "" },
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgDef {
+    /// Argument name shown in usage.
+    pub name: &'static str,
+    /// Expected type.
+    pub ty: ArgType,
+    /// Whether the command fails without it.
+    pub required: bool,
+    /// Description shown by `help <cmd>`.
+    pub description: &'static str,
+}
+
+impl std::fmt::Display for ArgDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.required {
+            write!(f, "<{}:{}>", self.name, self.ty)
+        } else {
+            write!(f, "[{}:{}]", self.name, self.ty)
+        }
+    }
+}
+
 /// Type alias for command handler functions.
 ///
 /// Handlers receive:
@@ -99,6 +182,32 @@ impl<'a> std::ops::Index<usize> for CommandArgs<'a> {
 /// - `world`: Mutable access to the Bevy world
 pub type CommandHandler = Box<dyn Fn(&CommandArgs, &mut World) + Send + Sync>;
 
+/// Output sink for commands created with [`ConCommand::new_streaming`].
+///
+/// Emitted events go straight into the console's pending output, so they keep
+/// their order relative to the console's own output for the command and are
+/// kept even if the handler panics part way through.
+#[derive(Debug)]
+pub struct StreamingOutput {
+    outputs: crate::OutputQueue,
+}
+
+impl StreamingOutput {
+    /// Create a sink that appends to the pending output of `world`'s console.
+    fn new(world: &World) -> Self {
+        let outputs = world
+            .get_resource::<crate::PendingCommands>()
+            .map(|pending| pending.outputs.clone())
+            .unwrap_or_default();
+        Self { outputs }
+    }
+
+    /// Emit an output event.
+    pub fn emit(&mut self, event: ConsoleOutputEvent) {
+        self.outputs.push(event);
+    }
+}
+
 /// Type alias for autocomplete provider functions.
 ///
 /// Receives the partial input and returns a list of suggestions.
@@ -113,10 +222,16 @@ pub struct ConCommandMeta {
     pub name: Box<str>,
     /// Description.
     pub description: &'static str,
+    /// Multi-line usage text shown by `help <cmd>`.
+    pub help_text: Option<&'static str>,
+    /// Command to use instead, if this one is deprecated.
+    pub deprecated_replacement: Option<&'static str>,
     /// Flags.
     pub flags: ConVarFlags,
     /// Required permission level.
     pub required_permission: PermissionLevel,
+    /// Positional arguments, empty if the command has no schema.
+    pub schema: Vec<ArgDef>,
 }
 
 impl ConCommandMeta {
@@ -132,6 +247,12 @@ impl ConCommandMeta {
         self.description
     }
 
+    /// Get the usage text.
+    #[inline]
+    pub fn get_help_text(&self) -> Option<&'static str> {
+        self.help_text
+    }
+
     /// Get the flags.
     #[inline]
     pub fn get_flags(&self) -> ConVarFlags {
@@ -143,6 +264,36 @@ impl ConCommandMeta {
     pub fn get_required_permission(&self) -> PermissionLevel {
         self.required_permission
     }
+
+    /// Usage line built from the schema, e.g. `bind <key:String> [count:Int]`.
+    ///
+    /// Returns `None` if the command has no schema.
+    pub fn usage(&self) -> Option<String> {
+        if self.schema.is_empty() {
+            return None;
+        }
+        let mut usage = self.name.to_string();
+        for arg in &self.schema {
+            usage.push_str(&format!(" {}", arg));
+        }
+        Some(usage)
+    }
+
+    /// Check `args` against the schema.
+    ///
+    /// Extra arguments are allowed, so the last argument can be joined from the rest.
+    pub fn validate_args(&self, args: &[String]) -> Result<(), CommandError> {
+        for (i, def) in self.schema.iter().enumerate() {
+            match args.get(i) {
+                Some(value) if !def.ty.accepts(value) => {
+                    return Err(CommandError::InvalidArgument(def.name, def.ty));
+                }
+                None if def.required => return Err(CommandError::MissingArgument(def.name)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A console command with a handler function.
@@ -161,8 +312,11 @@ impl ConCommandMeta {
 pub struct ConCommand {
     name: Box<str>,
     description: &'static str,
+    help_text: Option<&'static str>,
+    deprecated_replacement: Option<&'static str>,
     flags: ConVarFlags,
     required_permission: PermissionLevel,
+    schema: Vec<ArgDef>,
     handler: CommandHandler,
     autocomplete: Option<AutocompleteProvider>,
 }
@@ -176,19 +330,97 @@ impl ConCommand {
         Self {
             name: name.into(),
             description: "",
+            help_text: None,
+            deprecated_replacement: None,
             flags: ConVarFlags::NONE,
             required_permission: PermissionLevel::User,
+            schema: Vec::new(),
             handler: Box::new(handler),
             autocomplete: None,
         }
     }
 
+    /// Create a command whose handler writes output through a [`StreamingOutput`].
+    ///
+    /// Each [`emit`](StreamingOutput::emit) appends to the console's pending
+    /// output immediately, while the handler is still running. Handlers still
+    /// run to completion within the frame, and output emitted before a panic is
+    /// not lost.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let scan = ConCommand::new_streaming("scan_assets", |_args, world, output| {
+    ///     for dir in asset_dirs(world) {
+    ///         output.emit(ConsoleOutputEvent::info(format!("Scanning {}", dir)));
+    ///         scan_dir(world, &dir);
+    ///     }
+    ///     output.emit(ConsoleOutputEvent::result("Done"));
+    /// });
+    /// ```
+    pub fn new_streaming<F>(name: impl Into<Box<str>>, generator: F) -> Self
+    where
+        F: Fn(&CommandArgs, &mut World, &mut StreamingOutput) + Send + Sync + 'static,
+    {
+        Self::new(name, move |args, world| {
+            let mut output = StreamingOutput::new(world);
+            generator(args, world, &mut output);
+        })
+    }
+
+    /// Create a command that finishes on a background task.
+    ///
+    /// The handler runs on the main thread with access to the world and
+    /// returns a future, which is spawned on the
+    /// [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool). The output
+    /// events it resolves to are shown when it completes. The command is
+    /// reported as successful as soon as the future is spawned.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let ping = ConCommand::new_async("ping", |args, _world| {
+    ///     let host = args.get(0).unwrap_or("localhost").to_string();
+    ///     async move {
+    ///         match ping_host(&host).await {
+    ///             Ok(ms) => vec![ConsoleOutputEvent::result(format!("{}: {} ms", host, ms))],
+    ///             Err(e) => vec![ConsoleOutputEvent::error(format!("{}: {}", host, e))],
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn new_async<F, Fut>(name: impl Into<Box<str>>, handler: F) -> Self
+    where
+        F: Fn(&CommandArgs, &mut World) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<ConsoleOutputEvent>> + Send + 'static,
+    {
+        Self::new(name, move |args, world| {
+            let future = handler(args, world);
+            world.get_resource_or_init::<super::PendingAsyncCommands>().spawn(future);
+        })
+    }
+
     /// Set the description.
     pub fn description(mut self, desc: &'static str) -> Self {
         self.description = desc;
         self
     }
 
+    /// Set multi-line usage text shown below the description by `help <cmd>`.
+    pub fn help_text(mut self, text: &'static str) -> Self {
+        self.help_text = Some(text);
+        self
+    }
+
+    /// Mark the command as deprecated in favor of `replacement`.
+    ///
+    /// Adds [`ConVarFlags::DEPRECATED`], so call this after [`flags`](Self::flags).
+    pub fn deprecated(mut self, replacement: &'static str) -> Self {
+        self.flags |= ConVarFlags::DEPRECATED;
+        self.deprecated_replacement = Some(replacement);
+        self
+    }
+
     /// Set the flags.
     pub fn flags(mut self, flags: ConVarFlags) -> Self {
         self.flags = flags;
@@ -210,6 +442,15 @@ impl ConCommand {
         self
     }
 
+    /// Describe the positional arguments.
+    ///
+    /// `help <cmd>` prints the resulting usage line, and invocations with
+    /// missing required or mistyped arguments fail without running the handler.
+    pub fn schema(mut self, args: &[ArgDef]) -> Self {
+        self.schema = args.to_vec();
+        self
+    }
+
     /// Get the command name.
     #[inline]
     pub fn name(&self) -> &str {
@@ -222,6 +463,12 @@ impl ConCommand {
         self.description
     }
 
+    /// Get the usage text.
+    #[inline]
+    pub fn get_help_text(&self) -> Option<&'static str> {
+        self.help_text
+    }
+
     /// Get the flags.
     #[inline]
     pub fn get_flags(&self) -> ConVarFlags {
@@ -261,8 +508,11 @@ impl ConCommand {
             ConCommandMeta {
                 name: self.name,
                 description: self.description,
+                help_text: self.help_text,
+                deprecated_replacement: self.deprecated_replacement,
                 flags: self.flags,
                 required_permission: self.required_permission,
+                schema: self.schema,
             },
             self.handler,
             self.autocomplete,
@@ -301,6 +551,17 @@ mod tests {
         assert_eq!(args.parse_or::<i32>(1, 0), 0);
     }
 
+    #[test]
+    fn test_command_args_flag() {
+        let args = CommandArgs::new("help --markdown sv_gravity", vec!["--markdown", "sv_gravity"]);
+        assert!(args.flag("--markdown"));
+        assert!(!args.flag("--deprecated"));
+
+        let args = CommandArgs::new("cvar_dump --file out.json", vec!["--file", "out.json"]);
+        assert_eq!(args.flag_value("--file"), Some("out.json"));
+        assert_eq!(CommandArgs::new("cvar_dump --file", vec!["--file"]).flag_value("--file"), None);
+    }
+
     #[test]
     fn test_command_args_join() {
         let args = CommandArgs::new("echo hello world", vec!["hello", "world"]);
@@ -317,5 +578,56 @@ mod tests {
         assert_eq!(cmd.name(), "test");
         assert_eq!(cmd.get_description(), "A test command");
         assert!(cmd.get_flags().contains(ConVarFlags::CHEAT));
+        assert_eq!(cmd.get_help_text(), None);
+    }
+
+    #[test]
+    fn test_concommand_deprecated() {
+        let cmd = ConCommand::new("old", |_args, _world| {})
+            .flags(ConVarFlags::CHEAT)
+            .deprecated("new");
+        assert!(cmd.get_flags().contains(ConVarFlags::DEPRECATED));
+        assert!(cmd.get_flags().contains(ConVarFlags::CHEAT));
+
+        let (meta, _, _) = cmd.split();
+        assert_eq!(meta.deprecated_replacement, Some("new"));
+    }
+
+    #[test]
+    fn test_concommand_help_text() {
+        let cmd = ConCommand::new("test", |_args, _world| {})
+            .help_text("test a\ntest a b");
+        assert_eq!(cmd.get_help_text(), Some("test a\ntest a b"));
+
+        let (meta, _, _) = cmd.split();
+        assert_eq!(meta.get_help_text(), Some("test a\ntest a b"));
+    }
+
+    #[test]
+    fn test_concommand_schema() {
+        const MODES: &[&str] = &["fast", "slow"];
+        let cmd = ConCommand::new("bind", |_args, _world| {}).schema(&[
+            ArgDef { name: "key", ty: ArgType::String, required: true, description: "" },
+            ArgDef { name: "count", ty: ArgType::Int, required: false, description: "" },
+            ArgDef { name: "mode", ty: ArgType::Enum(MODES), required: false, description: "" },
+        ]);
+        let (meta, _, _) = cmd.split();
+        assert_eq!(meta.usage().as_deref(), Some("bind <key:String> [count:Int] [mode:fast|slow]"));
+
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(meta.validate_args(&args(&["w"])), Ok(()));
+        assert_eq!(meta.validate_args(&args(&["w", "3", "FAST", "extra"])), Ok(()));
+        assert_eq!(meta.validate_args(&args(&[])), Err(CommandError::MissingArgument("key")));
+        assert_eq!(
+            meta.validate_args(&args(&["w", "three"])),
+            Err(CommandError::InvalidArgument("count", ArgType::Int))
+        );
+        assert_eq!(
+            meta.validate_args(&args(&["w", "3", "medium"])),
+            Err(CommandError::InvalidArgument("mode", ArgType::Enum(MODES)))
+        );
+
+        let (meta, _, _) = ConCommand::new("plain", |_args, _world| {}).split();
+        assert_eq!(meta.usage(), None);
     }
 }
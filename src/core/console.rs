@@ -4,12 +4,12 @@
 //! with console variables and commands, combining [`ConsoleRegistry`] and
 //! [`CommandHandlers`] into a single ergonomic API.
 
-use bevy::ecs::system::SystemParam;
+use bevy::ecs::system::{SystemParam, SystemState};
 use bevy::prelude::*;
 
 use super::{
     ConCommand, ConVar, ConVarValue, ConsoleRegistry, CommandHandlers,
-    ConEntry, ConVarMeta, ConCommandMeta,
+    ConEntry, ConVarMeta, ConCommandMeta, CommandSource,
 };
 
 /// Unified console system parameter for convenient access.
@@ -42,6 +42,22 @@ pub struct Console<'w> {
 }
 
 impl Console<'_> {
+    /// Run `f` with a [`Console`] borrowed from `world`, for use in exclusive systems.
+    ///
+    /// ```ignore
+    /// fn apply_preset(world: &mut World) {
+    ///     Console::scope(world, |console| {
+    ///         let gravity: f32 = console.get("sv_gravity").unwrap_or(800.0);
+    ///         console.set("sv_gravity", gravity * 0.5);
+    ///     });
+    /// }
+    /// ```
+    pub fn scope<R>(world: &mut World, f: impl FnOnce(&mut Console) -> R) -> R {
+        let mut state = SystemState::<Console>::new(world);
+        let mut console = state.get_mut(world);
+        f(&mut console)
+    }
+
     /// Register a console variable.
     ///
     /// Returns `true` if newly registered, `false` if it replaced an existing entry.
@@ -52,9 +68,11 @@ impl Console<'_> {
     /// Register a console command.
     ///
     /// This handles both the metadata (in registry) and handler (in handlers) registration.
-    /// Returns `true` if newly registered, `false` if it replaced an existing entry.
+    /// Returns `true` if newly registered, `false` if it replaced an existing entry or was rejected.
     pub fn register_cmd(&mut self, cmd: ConCommand) -> bool {
-        let (name, handler, autocomplete, is_new) = self.registry.register_cmd(cmd);
+        let Some((name, handler, autocomplete, is_new)) = self.registry.try_register_cmd(cmd) else {
+            return false;
+        };
         self.handlers.register(name, handler, autocomplete);
         is_new
     }
@@ -64,6 +82,11 @@ impl Console<'_> {
         self.registry.get(name)
     }
 
+    /// Get a typed reference to a ConVar by name.
+    pub fn get_var<T: ConVarValue + PartialEq + 'static>(&self, name: &str) -> Option<&ConVar<T>> {
+        self.registry.get_var(name)
+    }
+
     /// Get a ConVar's value as a string.
     pub fn get_string(&self, name: &str) -> Option<String> {
         self.registry.get_string(name)
@@ -157,18 +180,55 @@ impl Console<'_> {
 ///
 /// Use this when you only need to read console values, not modify them.
 /// This allows for better parallelism in Bevy's scheduler.
+///
+/// # Writing from a read-only system
+///
+/// A `ConsoleRef` can't become a [`Console`] in place, since Bevy has already
+/// granted the system shared access. There are two ways to write anyway:
+///
+/// ```ignore
+/// // Regular system: queue the change, applied when the command pipeline runs
+/// fn low_gravity_zone(console: ConsoleRef, player: Single<&Transform, With<Player>>) {
+///     if player.translation.y > 100.0 && console.get::<f32>("sv_gravity") != Some(200.0) {
+///         console.set_deferred("sv_gravity", 200.0f32);
+///     }
+/// }
+///
+/// // Exclusive system: borrow a mutable Console for the rest of the system
+/// fn low_gravity_zone(world: &mut World) {
+///     Console::scope(world, |console| console.set("sv_gravity", 200.0f32));
+/// }
+/// ```
 #[derive(SystemParam)]
 pub struct ConsoleRef<'w> {
     registry: Res<'w, ConsoleRegistry>,
     handlers: Res<'w, CommandHandlers>,
+    deferred: Res<'w, crate::DeferredAssignments>,
 }
 
 impl ConsoleRef<'_> {
+    /// Queue an assignment of `value` to the ConVar `name`.
+    ///
+    /// The change goes through the normal command pipeline (access checks,
+    /// change events) the next time it runs, so [`get`](Self::get) still
+    /// returns the old value for now. The value is queued as-is rather than
+    /// as console text, so strings containing `;` or quotes can't inject
+    /// other commands.
+    pub fn set_deferred<T: ConVarValue>(&self, name: &str, value: T) {
+        let command = crate::QueuedCommand::assignment(name, value.format(), CommandSource::Programmatic);
+        self.deferred.0.lock().unwrap().push(command);
+    }
+
     /// Get a ConVar's typed value by name.
     pub fn get<T: ConVarValue + PartialEq + 'static>(&self, name: &str) -> Option<T> {
         self.registry.get(name)
     }
 
+    /// Get a typed reference to a ConVar by name.
+    pub fn get_var<T: ConVarValue + PartialEq + 'static>(&self, name: &str) -> Option<&ConVar<T>> {
+        self.registry.get_var(name)
+    }
+
     /// Get a ConVar's value as a string.
     pub fn get_string(&self, name: &str) -> Option<String> {
         self.registry.get_string(name)
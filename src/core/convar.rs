@@ -3,12 +3,12 @@
 //! ConVars are typed variables that can be modified via the console.
 //! Inspired by the Source Engine ConVar system.
 
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::fmt::{self, Display};
 
 use bevy::prelude::*;
 
-use super::PermissionLevel;
+use super::{tokenize_string, PermissionLevel};
 
 /// Flags controlling ConVar behavior.
 ///
@@ -41,6 +41,15 @@ impl ConVarFlags {
     /// Development only, stripped in release builds.
     pub const DEV_ONLY: Self = Self(1 << 5);
 
+    /// Kept for compatibility, hidden from listings unless requested.
+    pub const DEPRECATED: Self = Self(1 << 6);
+
+    /// Server-authoritative, sent to clients on change (`replicate` feature).
+    pub const REPLICATE_S2C: Self = Self(1 << 7);
+
+    /// Client preference, sent to the server on change for it to validate (`replicate` feature).
+    pub const REPLICATE_C2S: Self = Self(1 << 8);
+
     /// Check if a flag is set.
     #[inline]
     pub const fn contains(self, other: Self) -> bool {
@@ -53,6 +62,12 @@ impl ConVarFlags {
         Self(self.0 | other.0)
     }
 
+    /// Flags set in both.
+    #[inline]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
     /// Remove flags.
     #[inline]
     pub const fn difference(self, other: Self) -> Self {
@@ -64,8 +79,38 @@ impl ConVarFlags {
     pub const fn is_empty(self) -> bool {
         self.0 == 0
     }
+
+    /// Names of the set flags, e.g. `["ARCHIVE", "CHEAT"]`.
+    pub fn names(self) -> Vec<&'static str> {
+        FLAG_NAMES
+            .iter()
+            .filter(|(_, flag)| self.contains(*flag))
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Look up a single flag by name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        FLAG_NAMES
+            .iter()
+            .find(|(flag_name, _)| flag_name.eq_ignore_ascii_case(name))
+            .map(|(_, flag)| *flag)
+    }
 }
 
+/// Display names of each flag, in bit order.
+const FLAG_NAMES: &[(&str, ConVarFlags)] = &[
+    ("ARCHIVE", ConVarFlags::ARCHIVE),
+    ("CHEAT", ConVarFlags::CHEAT),
+    ("READ_ONLY", ConVarFlags::READ_ONLY),
+    ("HIDDEN", ConVarFlags::HIDDEN),
+    ("NOTIFY", ConVarFlags::NOTIFY),
+    ("DEV_ONLY", ConVarFlags::DEV_ONLY),
+    ("DEPRECATED", ConVarFlags::DEPRECATED),
+    ("REPLICATE_S2C", ConVarFlags::REPLICATE_S2C),
+    ("REPLICATE_C2S", ConVarFlags::REPLICATE_C2S),
+];
+
 impl std::ops::BitOr for ConVarFlags {
     type Output = Self;
 
@@ -82,9 +127,37 @@ impl std::ops::BitOrAssign for ConVarFlags {
     }
 }
 
+impl std::ops::BitAnd for ConVarFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl std::ops::BitAndAssign for ConVarFlags {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(rhs);
+    }
+}
+
+impl std::ops::Not for ConVarFlags {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        Self(!self.0)
+    }
+}
+
 /// Trait for types that can be stored in a ConVar.
 ///
-/// Implemented for common types: `bool`, `i32`, `i64`, `f32`, `f64`, `String`.
+/// Implemented for common types: `bool`, `i32`, `i64`, `f32`, `f64`, `String`,
+/// [`Color`], the tuples `(f32, f32)` and `(f32, f32, f32)`, and the list types
+/// `Vec<String>` and [`CsvList`]. With the
+/// `derive` feature, `#[derive(ConVarValue)]` implements it for field-less enums.
 pub trait ConVarValue: Clone + Send + Sync + 'static {
     /// Parse a value from a string.
     fn parse(s: &str) -> Option<Self>;
@@ -99,6 +172,11 @@ pub trait ConVarValue: Clone + Send + Sync + 'static {
     fn supports_bounds() -> bool {
         false
     }
+
+    /// The complete set of accepted values, if the type has one (e.g. enums).
+    fn allowed_values() -> &'static [&'static str] {
+        &[]
+    }
 }
 
 impl ConVarValue for bool {
@@ -228,6 +306,63 @@ impl ConVarValue for f64 {
     }
 }
 
+/// Parse exactly `N` whitespace-separated floats.
+fn parse_components<const N: usize>(s: &str) -> Option<[f32; N]> {
+    let mut parts = s.split_whitespace();
+    let mut components = [0.0; N];
+    for component in &mut components {
+        *component = parts.next()?.parse().ok()?;
+    }
+    parts.next().is_none().then_some(components)
+}
+
+/// 2D value as `"x y"`. Bounds apply per component.
+impl ConVarValue for (f32, f32) {
+    fn parse(s: &str) -> Option<Self> {
+        let [x, y] = parse_components(s)?;
+        Some((x, y))
+    }
+
+    fn format(&self) -> String {
+        format!("{} {}", self.0.format(), self.1.format())
+    }
+
+    fn clamp(self, min: Option<&Self>, max: Option<&Self>) -> Self {
+        (
+            ConVarValue::clamp(self.0, min.map(|m| &m.0), max.map(|m| &m.0)),
+            ConVarValue::clamp(self.1, min.map(|m| &m.1), max.map(|m| &m.1)),
+        )
+    }
+
+    fn supports_bounds() -> bool {
+        true
+    }
+}
+
+/// 3D value as `"x y z"`. Bounds apply per component.
+impl ConVarValue for (f32, f32, f32) {
+    fn parse(s: &str) -> Option<Self> {
+        let [x, y, z] = parse_components(s)?;
+        Some((x, y, z))
+    }
+
+    fn format(&self) -> String {
+        format!("{} {} {}", self.0.format(), self.1.format(), self.2.format())
+    }
+
+    fn clamp(self, min: Option<&Self>, max: Option<&Self>) -> Self {
+        (
+            ConVarValue::clamp(self.0, min.map(|m| &m.0), max.map(|m| &m.0)),
+            ConVarValue::clamp(self.1, min.map(|m| &m.1), max.map(|m| &m.1)),
+            ConVarValue::clamp(self.2, min.map(|m| &m.2), max.map(|m| &m.2)),
+        )
+    }
+
+    fn supports_bounds() -> bool {
+        true
+    }
+}
+
 impl ConVarValue for String {
     fn parse(s: &str) -> Option<Self> {
         Some(s.to_string())
@@ -242,6 +377,156 @@ impl ConVarValue for String {
     }
 }
 
+/// Space-separated list. Elements containing whitespace or quotes are quoted.
+impl ConVarValue for Vec<String> {
+    fn parse(s: &str) -> Option<Self> {
+        let tokens = tokenize_string(s).ok()?;
+        Some(tokens.into_iter().map(unescape_list_item).collect())
+    }
+
+    fn format(&self) -> String {
+        self.iter()
+            .map(|item| format_list_item(item))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn clamp(self, _min: Option<&Self>, _max: Option<&Self>) -> Self {
+        self
+    }
+}
+
+/// Quote a list element if it would not survive whitespace splitting.
+fn format_list_item(item: &str) -> String {
+    let needs_quotes = item.is_empty()
+        || item.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\');
+    if needs_quotes {
+        format!("\"{}\"", item.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        item.to_string()
+    }
+}
+
+/// Undo the escaping applied by [`format_list_item`].
+///
+/// Only `\\` and `\"` are escapes, so other backslashes, such as those in
+/// typed Windows paths, are kept.
+fn unescape_list_item(token: &str) -> String {
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&next @ ('\\' | '"'))) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Comma-separated list, for elements that commonly contain spaces.
+///
+/// Whitespace around each element is trimmed and empty elements are dropped.
+/// Commas and backslashes inside an element are escaped as `\,` and `\\`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CsvList(pub Vec<String>);
+
+impl ConVarValue for CsvList {
+    fn parse(s: &str) -> Option<Self> {
+        let mut items = Vec::new();
+        let mut item = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some(&next @ ('\\' | ','))) => {
+                    item.push(next);
+                    chars.next();
+                }
+                (',', _) => items.push(std::mem::take(&mut item)),
+                _ => item.push(c),
+            }
+        }
+        items.push(item);
+        Some(Self(
+            items
+                .iter()
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+
+    fn format(&self) -> String {
+        self.0
+            .iter()
+            .map(|item| item.replace('\\', "\\\\").replace(',', "\\,"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn clamp(self, _min: Option<&Self>, _max: Option<&Self>) -> Self {
+        self
+    }
+}
+
+/// Colors accept several input forms and always format as `#RRGGBBAA`:
+///
+/// - hex: `#f80`, `#ff8800`, `#ff8800cc` (the `#` is optional)
+/// - components: `255 128 0`, `255 128 0 204`, `1.0 0.5 0.0`
+/// - names: `red`, `green`, `blue`, `white`, `black`
+///
+/// Components are read as 0–255 if any exceeds 1.0, otherwise as 0.0–1.0.
+impl ConVarValue for Color {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let named = match s.to_lowercase().as_str() {
+            "red" => Some(Color::srgb(1.0, 0.0, 0.0)),
+            "green" => Some(Color::srgb(0.0, 1.0, 0.0)),
+            "blue" => Some(Color::srgb(0.0, 0.0, 1.0)),
+            "white" => Some(Color::WHITE),
+            "black" => Some(Color::BLACK),
+            _ => None,
+        };
+        if named.is_some() {
+            return named;
+        }
+
+        // A single token is only valid as hex
+        if !s.contains(char::is_whitespace) {
+            return Srgba::hex(s).ok().map(Color::from);
+        }
+
+        let components: Vec<f32> = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        match components.len() {
+            3 | 4 => {
+                let scale = if components.iter().any(|&c| c > 1.0) { 255.0 } else { 1.0 };
+                if components.iter().any(|&c| !(0.0..=scale).contains(&c)) {
+                    return None;
+                }
+                let alpha = components.get(3).map_or(1.0, |a| a / scale);
+                Some(Color::srgba(components[0] / scale, components[1] / scale, components[2] / scale, alpha))
+            }
+            _ => None,
+        }
+    }
+
+    fn format(&self) -> String {
+        let [r, g, b, a] = self.to_srgba().to_u8_array();
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+
+    fn clamp(self, _min: Option<&Self>, _max: Option<&Self>) -> Self {
+        self
+    }
+}
+
 /// Type-erased trait for ConVar storage.
 ///
 /// This allows storing ConVars of different types in the same registry.
@@ -269,6 +554,17 @@ pub trait ConVarDyn: Send + Sync {
 
     /// Clone the value into a new box.
     fn clone_boxed(&self) -> Box<dyn ConVarDyn>;
+
+    /// Describe the constraints on accepted values, e.g. `"Range: [0, 100]"`.
+    ///
+    /// Returns an empty string if there are none.
+    fn constraint_description(&self) -> String;
+
+    /// Values to offer when autocompleting an assignment to this variable.
+    ///
+    /// Enums list their allowed values and bools `0` and `1`. Bounded
+    /// numbers return a single `<min..max>` range hint instead.
+    fn value_completions(&self) -> Vec<String>;
 }
 
 /// A console variable with typed value and constraints.
@@ -276,7 +572,7 @@ pub trait ConVarDyn: Send + Sync {
 /// # Examples
 ///
 /// ```
-/// use bevy_console::core::{ConVar, ConVarFlags};
+/// use bevy_console_two::core::{ConVar, ConVarFlags};
 ///
 /// // Create a simple convar
 /// let mut gravity = ConVar::new("sv_gravity", 800.0f32)
@@ -302,9 +598,12 @@ pub struct ConVar<T: ConVarValue> {
     default: T,
     flags: ConVarFlags,
     description: &'static str,
+    description_key: Option<&'static str>,
     min: Option<T>,
     max: Option<T>,
     required_permission: PermissionLevel,
+    /// Byte length limit for `String` values, and whether to truncate rather than reject.
+    max_len: Option<(usize, bool)>,
 }
 
 impl<T: ConVarValue> ConVar<T> {
@@ -316,18 +615,37 @@ impl<T: ConVarValue> ConVar<T> {
             default,
             flags: ConVarFlags::NONE,
             description: "",
+            description_key: None,
             min: None,
             max: None,
             required_permission: PermissionLevel::User,
+            max_len: None,
         }
     }
 
+    /// Create a new ConVar whose default is computed once by `factory`.
+    ///
+    /// The factory is called immediately and not stored.
+    pub fn new_with_factory(name: impl Into<Box<str>>, factory: impl FnOnce() -> T) -> Self {
+        Self::new(name, factory())
+    }
+
     /// Set the description.
     pub fn description(mut self, desc: &'static str) -> Self {
         self.description = desc;
         self
     }
 
+    /// Set the key used to look up a translated description in
+    /// [`ConsoleLocalization`](super::ConsoleLocalization).
+    ///
+    /// The static [`description`](Self::description) is used when the key has
+    /// no localized string.
+    pub fn description_key(mut self, key: &'static str) -> Self {
+        self.description_key = Some(key);
+        self
+    }
+
     /// Set the flags.
     pub fn flags(mut self, flags: ConVarFlags) -> Self {
         self.flags = flags;
@@ -381,10 +699,33 @@ impl<T: ConVarValue> ConVar<T> {
         if self.flags.contains(ConVarFlags::READ_ONLY) {
             return false;
         }
+        let Some(value) = self.limit_length(value) else {
+            return false;
+        };
         self.value = value.clamp(self.min.as_ref(), self.max.as_ref());
         true
     }
 
+    /// Apply the `max_length` limit, returning `None` if the value is rejected.
+    fn limit_length(&self, mut value: T) -> Option<T> {
+        let Some((max_len, truncate)) = self.max_len else {
+            return Some(value);
+        };
+        if let Some(s) = (&mut value as &mut dyn Any).downcast_mut::<String>()
+            && s.len() > max_len
+        {
+            if !truncate {
+                return None;
+            }
+            let mut end = max_len;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            s.truncate(end);
+        }
+        Some(value)
+    }
+
     /// Get the default value.
     #[inline]
     pub fn default_value(&self) -> &T {
@@ -419,6 +760,24 @@ impl<T: ConVarValue> ConVar<T> {
         self.description
     }
 
+    /// Get the localization key for the description, if set.
+    #[inline]
+    pub fn get_description_key(&self) -> Option<&'static str> {
+        self.description_key
+    }
+
+    /// Get the minimum value, if set.
+    #[inline]
+    pub fn get_min(&self) -> Option<&T> {
+        self.min.as_ref()
+    }
+
+    /// Get the maximum value, if set.
+    #[inline]
+    pub fn get_max(&self) -> Option<&T> {
+        self.max.as_ref()
+    }
+
     /// Check if this ConVar has min/max constraints.
     #[inline]
     pub fn has_bounds(&self) -> bool {
@@ -441,11 +800,12 @@ impl<T: ConVarValue + PartialEq> ConVarDyn for ConVar<T> {
         if self.flags.contains(ConVarFlags::READ_ONLY) {
             return false;
         }
-        if let Some(value) = T::parse(s) {
-            self.value = value.clamp(self.min.as_ref(), self.max.as_ref());
-            true
-        } else {
-            false
+        match T::parse(s).and_then(|value| self.limit_length(value)) {
+            Some(value) => {
+                self.value = value.clamp(self.min.as_ref(), self.max.as_ref());
+                true
+            }
+            None => false,
         }
     }
 
@@ -472,6 +832,79 @@ impl<T: ConVarValue + PartialEq> ConVarDyn for ConVar<T> {
     fn clone_boxed(&self) -> Box<dyn ConVarDyn> {
         Box::new(self.clone())
     }
+
+    fn constraint_description(&self) -> String {
+        let mut parts = Vec::new();
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => parts.push(format!("Range: [{}, {}]", min.format(), max.format())),
+            (Some(min), None) => parts.push(format!("Min: {}", min.format())),
+            (None, Some(max)) => parts.push(format!("Max: {}", max.format())),
+            (None, None) => {}
+        }
+        if !T::allowed_values().is_empty() {
+            parts.push(format!("Allowed: [{}]", T::allowed_values().join(", ")));
+        }
+        if let Some((max_len, truncate)) = self.max_len {
+            let mode = if truncate { "truncated" } else { "rejected" };
+            parts.push(format!("Max length: {} bytes ({} beyond)", max_len, mode));
+        }
+        parts.join(", ")
+    }
+
+    fn value_completions(&self) -> Vec<String> {
+        if !T::allowed_values().is_empty() {
+            return T::allowed_values().iter().map(|value| value.to_string()).collect();
+        }
+        if TypeId::of::<T>() == TypeId::of::<bool>() {
+            return vec!["0".to_string(), "1".to_string()];
+        }
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => vec![format!("<{}..{}>", min.format(), max.format())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ConVar<String> {
+    /// Reject values longer than `max_len` bytes.
+    pub fn max_length(mut self, max_len: usize) -> Self {
+        self.max_len = Some((max_len, false));
+        self
+    }
+
+    /// Truncate values longer than `max_len` bytes, at a character boundary.
+    pub fn max_length_truncate(mut self, max_len: usize) -> Self {
+        self.max_len = Some((max_len, true));
+        self
+    }
+
+    /// Get the length limit in bytes, if set.
+    #[inline]
+    pub fn get_max_length(&self) -> Option<usize> {
+        self.max_len.map(|(max_len, _)| max_len)
+    }
+}
+
+impl ConVar<Vec<String>> {
+    /// Append an element to the list. Returns false if the ConVar is read-only.
+    pub fn push_string(&mut self, s: &str) -> bool {
+        let mut list = self.value.clone();
+        list.push(s.to_string());
+        self.set(list)
+    }
+}
+
+impl ConVar<Color> {
+    /// The current value as `hsl(h, s%, l%)`, or `hsla(h, s%, l%, a)` if translucent.
+    pub fn format_hsl(&self) -> String {
+        let hsla = Hsla::from(self.value);
+        let (h, s, l) = (hsla.hue.round(), (hsla.saturation * 100.0).round(), (hsla.lightness * 100.0).round());
+        if hsla.alpha < 1.0 {
+            format!("hsla({}, {}%, {}%, {:.2})", h, s, l, hsla.alpha)
+        } else {
+            format!("hsl({}, {}%, {}%)", h, s, l)
+        }
+    }
 }
 
 impl<T: ConVarValue> Display for ConVar<T> {
@@ -500,6 +933,21 @@ mod tests {
         assert_eq!(cvar.name(), "test");
     }
 
+    #[test]
+    fn test_convar_new_with_factory() {
+        let mut calls = 0;
+        let mut cvar = ConVar::new_with_factory("screen_width", || {
+            calls += 1;
+            1280i32
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(cvar.get(), 1280);
+
+        cvar.set(640);
+        cvar.reset();
+        assert_eq!(cvar.get(), 1280);
+    }
+
     #[test]
     fn test_convar_set() {
         let mut cvar = ConVar::new("test", 42i32);
@@ -575,5 +1023,260 @@ mod tests {
         assert!(flags.contains(ConVarFlags::ARCHIVE));
         assert!(flags.contains(ConVarFlags::NOTIFY));
         assert!(!flags.contains(ConVarFlags::CHEAT));
+        assert_eq!(flags.names(), vec!["ARCHIVE", "NOTIFY"]);
+        assert_eq!(ConVarFlags::from_name("read_only"), Some(ConVarFlags::READ_ONLY));
+        assert_eq!(ConVarFlags::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_convar_flags_operators() {
+        use ConVarFlags as F;
+        assert!(!(!(F::ARCHIVE | F::CHEAT)).contains(F::ARCHIVE));
+        assert!((!F::ARCHIVE).contains(F::CHEAT));
+        assert_eq!((F::ARCHIVE | F::CHEAT).intersection(F::CHEAT | F::HIDDEN), F::CHEAT);
+        assert_eq!((F::ARCHIVE | F::CHEAT) & F::HIDDEN, F::NONE);
+
+        let mut flags = F::ARCHIVE | F::CHEAT | F::NOTIFY;
+        flags &= !F::CHEAT;
+        assert_eq!(flags, F::ARCHIVE | F::NOTIFY);
+    }
+
+    #[test]
+    fn test_convar_string_list() {
+        let list: Vec<String> = vec!["alice smith".into(), "bob".into(), "say \"hi\"".into()];
+        let formatted = list.format();
+        assert_eq!(formatted, r#""alice smith" bob "say \"hi\"""#);
+        assert_eq!(Vec::<String>::parse(&formatted), Some(list));
+
+        assert_eq!(
+            Vec::<String>::parse("alice  bob charlie"),
+            Some(vec!["alice".into(), "bob".into(), "charlie".into()])
+        );
+        assert_eq!(Vec::<String>::parse(""), Some(vec![]));
+    }
+
+    #[test]
+    fn test_convar_string_list_backslashes() {
+        let list: Vec<String> = vec![r"C:\foo".into(), r"C:\Program Files\bar".into(), r"\\server\share".into()];
+        let formatted = list.format();
+        assert_eq!(formatted, r#""C:\\foo" "C:\\Program Files\\bar" "\\\\server\\share""#);
+        assert_eq!(Vec::<String>::parse(&formatted), Some(list));
+
+        // Typed paths keep backslashes that aren't escapes
+        assert_eq!(Vec::<String>::parse(r"C:\foo D:\bar"), Some(vec![r"C:\foo".into(), r"D:\bar".into()]));
+        assert_eq!(Vec::<String>::parse(r"a\,b"), Some(vec![r"a\,b".into()]));
+    }
+
+    #[test]
+    fn test_convar_push_string() {
+        let mut cvar = ConVar::new("sv_admins", vec!["alice".to_string()]);
+        assert!(cvar.push_string("bob jones"));
+        assert_eq!(cvar.get_string(), r#"alice "bob jones""#);
+
+        let mut cvar = ConVar::new("sv_admins", Vec::<String>::new()).flags(ConVarFlags::READ_ONLY);
+        assert!(!cvar.push_string("alice"));
+    }
+
+    #[test]
+    fn test_convar_csv_list() {
+        let list = CsvList::parse("alice smith, bob,,").unwrap();
+        assert_eq!(list, CsvList(vec!["alice smith".into(), "bob".into()]));
+        assert_eq!(list.format(), "alice smith,bob");
+
+        let list = CsvList(vec!["Smith, Alice".into(), r"C:\games".into()]);
+        assert_eq!(list.format(), r"Smith\, Alice,C:\\games");
+        assert_eq!(CsvList::parse(&list.format()), Some(list));
+    }
+
+    #[test]
+    fn test_convar_color_parse() {
+        let orange = Color::srgb_u8(255, 128, 0);
+        assert_eq!(Color::parse("#FF8000"), Some(orange));
+        assert_eq!(Color::parse("ff8000ff"), Some(orange));
+        assert_eq!(Color::parse("255 128 0"), Some(orange));
+        assert_eq!(Color::parse("255 128 0 255"), Some(orange));
+        assert_eq!(Color::parse("Red"), Some(Color::srgb(1.0, 0.0, 0.0)));
+        assert_eq!(Color::parse("0.5 0.5 0.5"), Some(Color::srgb(0.5, 0.5, 0.5)));
+        assert_eq!(Color::parse("1 1 1 0.5"), Some(Color::srgba(1.0, 1.0, 1.0, 0.5)));
+
+        assert_eq!(Color::parse("300 0 0"), None);
+        assert_eq!(Color::parse("1 2"), None);
+        assert_eq!(Color::parse("purple"), None);
+    }
+
+    #[test]
+    fn test_convar_color_format() {
+        let mut cvar = ConVar::new("cl_crosshair_color", Color::WHITE);
+        assert_eq!(cvar.get_string(), "#FFFFFFFF");
+        assert_eq!(cvar.format_hsl(), "hsl(0, 0%, 100%)");
+
+        assert!(cvar.set_string("128 64 32"));
+        assert_eq!(cvar.get_string(), "#804020FF");
+        assert!(cvar.set_string("0 0 255 127.5"));
+        assert_eq!(cvar.get_string(), "#0000FF80");
+        assert_eq!(cvar.format_hsl(), "hsla(240, 100%, 50%, 0.50)");
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_convar_derive_enum() {
+        #[derive(Debug, Clone, PartialEq, crate::ConVarValue)]
+        enum Quality {
+            Low,
+            Medium,
+            High,
+        }
+
+        assert_eq!(Quality::parse("medium"), Some(Quality::Medium));
+        assert_eq!(Quality::parse(" HIGH "), Some(Quality::High));
+        assert_eq!(Quality::parse("ultra"), None);
+        assert_eq!(Quality::Low.format(), "Low");
+        assert_eq!(Quality::ALLOWED_VALUES, &["Low", "Medium", "High"]);
+        assert_eq!(Quality::allowed_values(), Quality::ALLOWED_VALUES);
+
+        let mut cvar = ConVar::new("r_quality", Quality::Low);
+        assert!(cvar.set_string("high"));
+        assert_eq!(cvar.get(), Quality::High);
+        assert!(!cvar.set_string("ultra"));
+        assert_eq!(cvar.constraint_description(), "Allowed: [Low, Medium, High]");
+        assert_eq!(cvar.value_completions(), ["Low", "Medium", "High"]);
+    }
+
+    #[test]
+    fn test_convar_constraint_description() {
+        assert_eq!(ConVar::new("sv_cheats", false).constraint_description(), "");
+        assert_eq!(ConVar::new("cl_fov", 90).min(60).max(120).constraint_description(), "Range: [60, 120]");
+        assert_eq!(ConVar::new("sv_gravity", 800.0f32).min(0.0).constraint_description(), "Min: 0");
+        assert_eq!(
+            ConVar::new("sv_motd", String::new()).max_length_truncate(64).constraint_description(),
+            "Max length: 64 bytes (truncated beyond)"
+        );
+    }
+
+    #[test]
+    fn test_convar_value_completions() {
+        assert_eq!(ConVar::new("cl_showfps", false).value_completions(), ["0", "1"]);
+        assert_eq!(ConVar::new("cl_fov", 90).min(60).max(120).value_completions(), ["<60..120>"]);
+        assert!(ConVar::new("sv_gravity", 800.0f32).min(0.0).value_completions().is_empty());
+        assert!(ConVar::new("sv_hostname", String::new()).value_completions().is_empty());
+    }
+
+    #[test]
+    fn test_convar_tuple_values() {
+        assert_eq!(<(f32, f32)>::parse("1.5 2.5"), Some((1.5, 2.5)));
+        assert_eq!(<(f32, f32, f32)>::parse(" 1  -2 0.25 "), Some((1.0, -2.0, 0.25)));
+        assert_eq!(<(f32, f32)>::parse("1"), None);
+        assert_eq!(<(f32, f32)>::parse("1 2 3"), None);
+        assert_eq!(<(f32, f32, f32)>::parse("1 x 3"), None);
+
+        for value in [(0.0, 0.0, 0.0), (1.0, -2.5, 1e-3), (800.0, 0.1, -123.456)] {
+            assert_eq!(<(f32, f32, f32)>::parse(&value.format()), Some(value));
+        }
+        assert_eq!((1.0f32, 2.5f32).format(), "1 2.5");
+
+        let mut cvar = ConVar::new("cl_spawn_offset", (0.0f32, 0.0f32)).min((-1.0, 0.0)).max((1.0, 10.0));
+        assert!(cvar.set_string("-5 5"));
+        assert_eq!(cvar.get(), (-1.0, 5.0));
+        assert!(cvar.set_string("0.5 20"));
+        assert_eq!(cvar.get(), (0.5, 10.0));
+    }
+
+    #[test]
+    fn test_convar_string_max_length() {
+        let mut cvar = ConVar::new("sv_hostname", String::from("server")).max_length(8);
+        assert_eq!(cvar.get_max_length(), Some(8));
+        assert!(cvar.set("my game".into()));
+        assert!(!cvar.set("a much longer name".into()));
+        assert!(!cvar.set_string("123456789"));
+        assert_eq!(cvar.get_string(), "my game");
+
+        let mut cvar = ConVar::new("sv_motd", String::new()).max_length_truncate(5);
+        assert!(cvar.set_string("hello world"));
+        assert_eq!(cvar.get_string(), "hello");
+        // Never splits a multi-byte character
+        assert!(cvar.set("héllo".into()));
+        assert_eq!(cvar.get(), "héll");
+    }
+}
+
+#[cfg(test)]
+mod roundtrip_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    use crate::core::{CommandHandlers, ConsoleRegistry};
+
+    fn roundtrips<T: ConVarValue + PartialEq>(value: T) -> bool {
+        T::parse(&value.format()) == Some(value)
+    }
+
+    proptest! {
+        #[test]
+        fn test_bool_roundtrip(value: bool) {
+            prop_assert!(roundtrips(value));
+        }
+
+        #[test]
+        fn test_i32_roundtrip(value: i32) {
+            prop_assert!(roundtrips(value));
+        }
+
+        #[test]
+        fn test_i64_roundtrip(value: i64) {
+            prop_assert!(roundtrips(value));
+        }
+
+        #[test]
+        fn test_f32_roundtrip(value in any::<f32>().prop_filter("finite", |v| v.is_finite())) {
+            prop_assert_eq!(f32::parse(&value.format()).map(f32::to_bits), Some(value.to_bits()));
+        }
+
+        #[test]
+        fn test_f64_roundtrip(value in any::<f64>().prop_filter("finite", |v| v.is_finite())) {
+            prop_assert_eq!(f64::parse(&value.format()).map(f64::to_bits), Some(value.to_bits()));
+        }
+
+        // Whole-number formatting drops the decimal point; fractions must survive
+        #[test]
+        fn test_f32_fraction_roundtrip(whole in -1_000_000i32..1_000_000, frac in 1u32..1000) {
+            let value = whole as f32 + frac as f32 / 1000.0;
+            prop_assert!(roundtrips(value));
+        }
+
+        #[test]
+        fn test_f64_fraction_roundtrip(whole in -1_000_000_000i64..1_000_000_000, frac in 1u32..1_000_000) {
+            let value = whole as f64 + frac as f64 / 1_000_000.0;
+            prop_assert!(roundtrips(value));
+        }
+    }
+
+    #[test]
+    fn test_registered_convars_set_own_string() {
+        let mut app = App::new();
+        app.init_resource::<ConsoleRegistry>()
+            .init_resource::<CommandHandlers>()
+            .add_systems(Startup, (crate::builtins::register_builtin_commands, |mut registry: ResMut<ConsoleRegistry>| {
+                registry.register_var(ConVar::new("t_bool", true));
+                registry.register_var(ConVar::new("t_i32", -42i32));
+                registry.register_var(ConVar::new("t_i64", i64::MAX));
+                registry.register_var(ConVar::new("t_f32", 0.1f32));
+                registry.register_var(ConVar::new("t_f64", -1234.5678f64));
+                registry.register_var(ConVar::new("t_whole", 800.0f32));
+                registry.register_var(ConVar::new("t_string", String::from("my \"quoted\" server")));
+                registry.register_var(ConVar::new("t_list", vec!["de_dust".to_string(), "two words".to_string()]));
+                registry.register_var(ConVar::new("t_csv", CsvList(vec!["a b".into(), "c".into()])));
+                registry.register_var(ConVar::new("t_color", Color::srgba_u8(255, 128, 0, 204)));
+                registry.register_var(ConVar::new("t_vec3", (1.0f32, -2.5f32, 0.1f32)));
+            }));
+        app.update();
+
+        let mut registry = app.world_mut().resource_mut::<ConsoleRegistry>();
+        let names: Vec<String> = registry.vars().map(|(name, _)| name.to_string()).collect();
+        assert!(names.len() > 10);
+        for name in names {
+            let before = registry.get_string(&name).unwrap();
+            assert!(registry.set_string(&name, &before), "{name} rejected its own value {before:?}");
+            assert_eq!(registry.get_string(&name).unwrap(), before, "{name} changed on round-trip");
+        }
     }
 }
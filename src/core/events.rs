@@ -7,6 +7,32 @@
 
 use bevy::prelude::*;
 
+use super::{ArgType, ConVarValue};
+
+/// Where a console command originated.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum CommandSource {
+    /// Typed into the egui console.
+    Ui,
+    /// Read from the terminal's stdin.
+    Terminal,
+    /// Received from a remote console client over TCP.
+    Remote,
+    /// Read from a script file by `exec`.
+    Script {
+        /// Path of the script file.
+        file: Box<str>,
+    },
+    /// Replayed from a recorded macro by `playback`.
+    Macro {
+        /// Name of the macro.
+        name: Box<str>,
+    },
+    /// Sent by game code.
+    #[default]
+    Programmatic,
+}
+
 /// Event sent when a command is submitted to the console.
 ///
 /// The console system will parse and execute this command.
@@ -14,25 +40,44 @@ use bevy::prelude::*;
 /// # Examples
 ///
 /// ```ignore
-/// fn submit_command(mut events: EventWriter<ConsoleInputEvent>) {
-///     events.send(ConsoleInputEvent {
-///         command: "sv_cheats 1".to_string(),
-///     });
+/// fn submit_command(mut events: MessageWriter<ConsoleInputEvent>) {
+///     events.write(ConsoleInputEvent::new("sv_cheats 1"));
 /// }
 /// ```
 #[derive(Message, Debug, Clone)]
 pub struct ConsoleInputEvent {
     /// The raw command string to execute.
     pub command: String,
+    /// Where the command came from.
+    pub source: CommandSource,
+    /// Execution priority, from 0 (normal) to 255 (highest).
+    ///
+    /// Queued commands with a higher priority run first; equal priorities run
+    /// in submission order.
+    pub priority: u8,
 }
 
 impl ConsoleInputEvent {
-    /// Create a new input event.
+    /// Create a new input event with a [`CommandSource::Programmatic`] source.
     pub fn new(command: impl Into<String>) -> Self {
         Self {
             command: command.into(),
+            source: CommandSource::default(),
+            priority: 0,
         }
     }
+
+    /// Set where the command came from.
+    pub fn with_source(mut self, source: CommandSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set the execution priority, e.g. so a network `disconnect` jumps the queue.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
 }
 
 /// Event sent when output should be displayed in the console.
@@ -54,7 +99,7 @@ pub struct ConsoleOutputEvent {
 }
 
 /// Log level for console output.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ConsoleOutputLevel {
     /// Debug information (gray).
     Debug,
@@ -65,6 +110,8 @@ pub enum ConsoleOutputLevel {
     Warn,
     /// Error (red).
     Error,
+    /// Positive confirmation that an operation succeeded (green).
+    Success,
     /// Command echo (shows the command that was executed).
     Command,
     /// Command result/response.
@@ -100,6 +147,11 @@ impl ConsoleOutputEvent {
         Self::new(ConsoleOutputLevel::Error, message)
     }
 
+    /// Create a success message.
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ConsoleOutputLevel::Success, message)
+    }
+
     /// Create a command echo message.
     pub fn command(message: impl Into<String>) -> Self {
         Self::new(ConsoleOutputLevel::Command, message)
@@ -151,6 +203,181 @@ impl ConVarChangedEvent {
     }
 }
 
+/// How a console command invocation ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// The handler ran to completion.
+    Success,
+    /// The caller lacked the permission level or `sv_cheats` was disabled.
+    PermissionDenied,
+    /// No command, variable, or alias with that name exists.
+    NotFound,
+    /// The arguments did not match the command's schema.
+    InvalidArguments,
+    /// An alias kept expanding into aliases past `MAX_ALIAS_DEPTH`.
+    AliasDepthExceeded,
+    /// The handler panicked with the given message.
+    Panicked(String),
+}
+
+/// Event sent after each queued command has been processed.
+///
+/// Every queued command produces exactly one. Variable reads and writes are
+/// named after the variable, and an alias invocation produces one of its own
+/// followed by one for each command it expands to.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn track_commands(mut events: MessageReader<CommandExecutionEvent>) {
+///     for event in events.read() {
+///         info!("{} -> {:?}", event.name, event.outcome);
+///     }
+/// }
+/// ```
+#[derive(Message, Debug, Clone)]
+pub struct CommandExecutionEvent {
+    /// The command name as registered.
+    pub name: Box<str>,
+    /// The arguments passed to the command.
+    pub args: Vec<String>,
+    /// How the invocation ended.
+    pub outcome: CommandOutcome,
+    /// Where the command came from.
+    pub source: CommandSource,
+}
+
+impl CommandExecutionEvent {
+    /// Create a new execution event.
+    pub fn new(
+        name: impl Into<Box<str>>,
+        args: Vec<String>,
+        outcome: CommandOutcome,
+        source: CommandSource,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            outcome,
+            source,
+        }
+    }
+}
+
+/// Typed counterpart of [`ConVarChangedEvent`] for ConVars flagged `NOTIFY`.
+///
+/// Only written for value types whose event has been registered.
+/// [`ConsoleEventsPlugin`] registers `bool`, `i32`, `f32` and `String`; other
+/// types need `app.add_message::<TypedConVarChangedEvent<T>>()`.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn on_gravity_change(mut events: MessageReader<TypedConVarChangedEvent<f32>>) {
+///     for event in events.read().filter(|e| &*e.name == "sv_gravity") {
+///         info!("Gravity {} -> {}", event.old, event.new);
+///     }
+/// }
+/// ```
+#[derive(Message, Debug, Clone)]
+pub struct TypedConVarChangedEvent<T: ConVarValue> {
+    /// The name of the ConVar that changed.
+    pub name: Box<str>,
+    /// The previous value.
+    pub old: T,
+    /// The new value.
+    pub new: T,
+}
+
+impl<T: ConVarValue> TypedConVarChangedEvent<T> {
+    /// Create a new typed change event.
+    pub fn new(name: impl Into<Box<str>>, old: T, new: T) -> Self {
+        Self {
+            name: name.into(),
+            old,
+            new,
+        }
+    }
+}
+
+/// Write a [`TypedConVarChangedEvent<T>`] if that message type is registered,
+/// and trigger a [`ConVarChange<T>`](super::ConVarChange) on the ConVar's entity.
+pub(crate) fn write_typed_change<T: ConVarValue>(
+    world: &mut World,
+    name: &str,
+    old: &str,
+    new: &str,
+) {
+    let (Some(old), Some(new)) = (T::parse(old), T::parse(new)) else {
+        return;
+    };
+    if let Some(mut messages) = world.get_resource_mut::<Messages<TypedConVarChangedEvent<T>>>() {
+        messages.write(TypedConVarChangedEvent::new(name, old.clone(), new.clone()));
+    }
+    super::observe::trigger_change(world, name, old, new);
+}
+
+/// Why a console command failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// No command, variable, or alias with that name exists.
+    Unknown,
+    /// The current permission level is too low.
+    PermissionDenied,
+    /// The entry is flagged `CHEAT` and `sv_cheats` is disabled.
+    CheatRequired,
+    /// The command is not in the enabled [`ConsoleWhitelist`](super::ConsoleWhitelist).
+    NotWhitelisted,
+    /// The input could not be tokenized.
+    ParseFailed(String),
+    /// A required argument from the command's schema was not given.
+    MissingArgument(&'static str),
+    /// An argument did not match the type in the command's schema.
+    InvalidArgument(&'static str, ArgType),
+    /// The value could not be assigned to the variable: it didn't parse as the
+    /// variable's type, or the variable is read-only.
+    InvalidValue,
+    /// An alias kept expanding into aliases past `MAX_ALIAS_DEPTH`, usually
+    /// because it refers to itself.
+    AliasDepthExceeded,
+    /// The command queue was full and its overflow policy is
+    /// [`OverflowPolicy::Drop`](crate::OverflowPolicy::Drop).
+    QueueFull,
+    /// The input's source exceeded its [`ConsoleInputRateLimit`](super::ConsoleInputRateLimit).
+    RateLimited,
+    /// The handler panicked with the given message.
+    HandlerPanicked(String),
+}
+
+/// Event sent when a command fails, alongside the error output.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn on_command_error(mut events: MessageReader<CommandErrorEvent>) {
+///     for event in events.read() {
+///         warn!("{} failed: {:?}", event.command_name, event.error);
+///     }
+/// }
+/// ```
+#[derive(Message, Debug, Clone)]
+pub struct CommandErrorEvent {
+    /// The command or variable name the error relates to.
+    pub command_name: Box<str>,
+    /// What went wrong.
+    pub error: CommandError,
+}
+
+impl CommandErrorEvent {
+    /// Create a new error event.
+    pub fn new(command_name: impl Into<Box<str>>, error: CommandError) -> Self {
+        Self {
+            command_name: command_name.into(),
+            error,
+        }
+    }
+}
+
 /// Event sent when the console is opened or closed.
 #[derive(Message, Debug, Clone, Copy)]
 pub struct ConsoleToggleEvent {
@@ -171,8 +398,53 @@ impl ConsoleToggleEvent {
 }
 
 /// Event requesting the console to clear its output buffer.
-#[derive(Message, Debug, Clone, Copy, Default)]
-pub struct ConsoleClearEvent;
+///
+/// With a `filter`, only entries of that level are removed from the UI log.
+/// The [`ConsoleOutputBuffer`](super::ConsoleOutputBuffer) doesn't record
+/// levels, so it is only cleared when `filter` is `None`.
+#[derive(Message, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConsoleClearEvent {
+    /// The level to clear, or `None` for everything.
+    pub filter: Option<ConsoleOutputLevel>,
+}
+
+impl ConsoleClearEvent {
+    /// Clear all output.
+    pub fn all() -> Self {
+        Self { filter: None }
+    }
+
+    /// Clear only output of `level`.
+    pub fn level(level: ConsoleOutputLevel) -> Self {
+        Self { filter: Some(level) }
+    }
+}
+
+/// Event that pauses or resumes console command processing.
+///
+/// While paused, submitted input is held back and executed in order once
+/// processing resumes.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ConsolePauseEvent {
+    /// Whether command processing should be paused.
+    pub paused: bool,
+}
+
+impl ConsolePauseEvent {
+    /// Create an event that pauses command processing.
+    pub fn pause() -> Self {
+        Self { paused: true }
+    }
+
+    /// Create an event that resumes command processing.
+    pub fn resume() -> Self {
+        Self { paused: false }
+    }
+}
+
+/// Resource tracking whether console command processing is paused.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConsolePaused(pub bool);
 
 /// Plugin that registers all console events.
 pub struct ConsoleEventsPlugin;
@@ -182,8 +454,16 @@ impl Plugin for ConsoleEventsPlugin {
         app.add_message::<ConsoleInputEvent>()
             .add_message::<ConsoleOutputEvent>()
             .add_message::<ConVarChangedEvent>()
+            .add_message::<TypedConVarChangedEvent<bool>>()
+            .add_message::<TypedConVarChangedEvent<i32>>()
+            .add_message::<TypedConVarChangedEvent<f32>>()
+            .add_message::<TypedConVarChangedEvent<String>>()
+            .add_message::<CommandExecutionEvent>()
+            .add_message::<CommandErrorEvent>()
             .add_message::<ConsoleToggleEvent>()
-            .add_message::<ConsoleClearEvent>();
+            .add_message::<ConsoleClearEvent>()
+            .add_message::<ConsolePauseEvent>()
+            .init_resource::<ConsolePaused>();
     }
 }
 
@@ -195,6 +475,10 @@ mod tests {
     fn test_console_input_event() {
         let event = ConsoleInputEvent::new("sv_cheats 1");
         assert_eq!(event.command, "sv_cheats 1");
+        assert_eq!(event.source, CommandSource::Programmatic);
+
+        let event = event.with_source(CommandSource::Terminal);
+        assert_eq!(event.source, CommandSource::Terminal);
     }
 
     #[test]
@@ -202,6 +486,9 @@ mod tests {
         let event = ConsoleOutputEvent::error("Something went wrong");
         assert_eq!(event.level, ConsoleOutputLevel::Error);
         assert_eq!(event.message, "Something went wrong");
+
+        let event = ConsoleOutputEvent::success("Saved config");
+        assert_eq!(event.level, ConsoleOutputLevel::Success);
     }
 
     #[test]
@@ -211,4 +498,45 @@ mod tests {
         assert_eq!(event.old_value, "800");
         assert_eq!(event.new_value, "1000");
     }
+
+    #[test]
+    fn test_write_typed_change() {
+        let mut world = World::new();
+        // Unregistered type: silently ignored
+        write_typed_change::<f32>(&mut world, "sv_gravity", "800", "1000");
+
+        world.init_resource::<Messages<TypedConVarChangedEvent<f32>>>();
+        write_typed_change::<f32>(&mut world, "sv_gravity", "800", "1000");
+        let messages = world.resource::<Messages<TypedConVarChangedEvent<f32>>>();
+        let event = messages.iter_current_update_messages().next().unwrap();
+        assert_eq!(&*event.name, "sv_gravity");
+        assert_eq!((event.old, event.new), (800.0, 1000.0));
+    }
+
+    #[test]
+    fn test_command_execution_event() {
+        let event = CommandExecutionEvent::new(
+            "echo",
+            vec!["hi".into()],
+            CommandOutcome::Success,
+            CommandSource::Ui,
+        );
+        assert_eq!(&*event.name, "echo");
+        assert_eq!(event.args, vec!["hi"]);
+        assert_eq!(event.outcome, CommandOutcome::Success);
+        assert_eq!(event.source, CommandSource::Ui);
+    }
+
+    #[test]
+    fn test_command_error_event() {
+        let event = CommandErrorEvent::new("noclip", CommandError::CheatRequired);
+        assert_eq!(&*event.command_name, "noclip");
+        assert_eq!(event.error, CommandError::CheatRequired);
+    }
+
+    #[test]
+    fn test_console_pause_event() {
+        assert!(ConsolePauseEvent::pause().paused);
+        assert!(!ConsolePauseEvent::resume().paused);
+    }
 }
@@ -0,0 +1,127 @@
+//! Recent console input, kept for consumers that don't read every frame.
+//!
+//! Every [`MessageReader`] has its own cursor, so any number of systems can
+//! read each [`ConsoleInputEvent`] independently. Messages are only kept for
+//! two updates though, so a system that runs on a timer or is added later can
+//! miss them. [`ConsoleInputEventLog`] keeps them for a configurable number of
+//! frames instead.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::ConsoleInputEvent;
+
+/// Default number of frames kept by [`ConsoleInputEventLog`].
+pub const DEFAULT_INPUT_LOG_FRAMES: u64 = 60;
+
+/// Resource holding the console input of the last few frames.
+///
+/// Readers track their own position with [`frame`](Self::frame) and
+/// [`since`](Self::since), so they don't compete with the command pipeline or
+/// each other.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn replay_recorder(log: Res<ConsoleInputEventLog>, mut last_seen: Local<u64>, mut file: ResMut<ReplayFile>) {
+///     for event in log.since(*last_seen) {
+///         file.write(&event.command);
+///     }
+///     *last_seen = log.frame();
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ConsoleInputEventLog {
+    entries: VecDeque<(u64, ConsoleInputEvent)>,
+    frame: u64,
+    max_frames: u64,
+}
+
+impl Default for ConsoleInputEventLog {
+    fn default() -> Self {
+        Self::with_frames(DEFAULT_INPUT_LOG_FRAMES)
+    }
+}
+
+impl ConsoleInputEventLog {
+    /// Create an empty log keeping input for `max_frames` frames.
+    pub fn with_frames(max_frames: u64) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            frame: 0,
+            max_frames,
+        }
+    }
+
+    /// The number of the most recently recorded frame.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// All stored input, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleInputEvent> {
+        self.entries.iter().map(|(_, event)| event)
+    }
+
+    /// Input recorded after `frame`, oldest first.
+    pub fn since(&self, frame: u64) -> impl Iterator<Item = &ConsoleInputEvent> {
+        self.entries
+            .iter()
+            .filter(move |(recorded, _)| *recorded > frame)
+            .map(|(_, event)| event)
+    }
+
+    /// Number of stored events.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no input is stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Start a new frame, recording `events` and dropping expired ones.
+    pub fn record_frame(&mut self, events: impl IntoIterator<Item = ConsoleInputEvent>) {
+        self.frame += 1;
+        let frame = self.frame;
+        self.entries.extend(events.into_iter().map(|event| (frame, event)));
+
+        let oldest = frame.saturating_sub(self.max_frames);
+        while self.entries.front().is_some_and(|(recorded, _)| *recorded <= oldest) {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// System that records this frame's input in the [`ConsoleInputEventLog`].
+pub(crate) fn record_console_input(
+    mut input_events: MessageReader<ConsoleInputEvent>,
+    mut log: ResMut<ConsoleInputEventLog>,
+) {
+    log.record_frame(input_events.read().cloned());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_log_expiry() {
+        let mut log = ConsoleInputEventLog::with_frames(2);
+        log.record_frame([ConsoleInputEvent::new("echo one")]);
+        let after_first = log.frame();
+        log.record_frame([ConsoleInputEvent::new("echo two")]);
+
+        let commands: Vec<_> = log.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, ["echo one", "echo two"]);
+        let commands: Vec<_> = log.since(after_first).map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, ["echo two"]);
+
+        log.record_frame([]);
+        assert_eq!(log.len(), 1);
+        log.record_frame([]);
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+//! Translated ConVar descriptions.
+//!
+//! ConVars can carry a [`description_key`](super::ConVar::description_key)
+//! alongside their static description. [`ConsoleLocalization`] maps those keys
+//! to translated strings and is filled in by the game's localization system.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+/// Resource holding localized strings by key.
+///
+/// Entries without a key, or whose key has no string here, fall back to the
+/// static description.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_console_two::core::ConsoleLocalization;
+///
+/// let mut localization = ConsoleLocalization::default();
+/// localization.insert("cvar.sv_gravity", "Schwerkraft der Welt");
+/// assert_eq!(localization.get("cvar.sv_gravity"), Some("Schwerkraft der Welt"));
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConsoleLocalization {
+    /// Localized strings by key.
+    pub strings: HashMap<&'static str, String>,
+}
+
+impl ConsoleLocalization {
+    /// Set the localized string for `key`.
+    pub fn insert(&mut self, key: &'static str, text: impl Into<String>) {
+        self.strings.insert(key, text.into());
+    }
+
+    /// Get the localized string for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// Remove all localized strings, e.g. before loading another language.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
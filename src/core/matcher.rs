@@ -7,7 +7,7 @@
 pub struct MatchResult {
     /// The match score (higher is better).
     pub score: i32,
-    /// Indices of matched characters in the text.
+    /// Indices of matched characters in the text, counted in chars rather than bytes.
     pub indices: Vec<usize>,
 }
 
@@ -33,7 +33,7 @@ impl MatchResult {
 /// # Examples
 ///
 /// ```
-/// use bevy_console::core::subsequence_match;
+/// use bevy_console_two::core::subsequence_match;
 ///
 /// // Exact match
 /// let result = subsequence_match("help", "help").unwrap();
@@ -51,11 +51,11 @@ pub fn subsequence_match(pattern: &str, text: &str) -> Option<MatchResult> {
         return Some(MatchResult::new(0, Vec::new()));
     }
 
-    let pattern_bytes = pattern.as_bytes();
-    let text_bytes = text.as_bytes();
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    let text_chars: Vec<char> = text.chars().collect();
 
     let mut score = 0i32;
-    let mut indices = Vec::with_capacity(pattern_bytes.len());
+    let mut indices = Vec::with_capacity(pattern_chars.len());
     let mut pattern_idx = 0;
     let mut prev_match_idx: Option<usize> = None;
 
@@ -64,12 +64,12 @@ pub fn subsequence_match(pattern: &str, text: &str) -> Option<MatchResult> {
         score += 20;
     }
 
-    for (i, &text_char) in text_bytes.iter().enumerate() {
-        if pattern_idx >= pattern_bytes.len() {
+    for (i, &text_char) in text_chars.iter().enumerate() {
+        if pattern_idx >= pattern_chars.len() {
             break;
         }
 
-        let pattern_char = pattern_bytes[pattern_idx];
+        let pattern_char = pattern_chars[pattern_idx];
 
         if text_char.eq_ignore_ascii_case(&pattern_char) {
             indices.push(i);
@@ -78,18 +78,16 @@ pub fn subsequence_match(pattern: &str, text: &str) -> Option<MatchResult> {
             score += 1;
 
             // Consecutive match bonus
-            if let Some(prev) = prev_match_idx {
-                if prev == i - 1 {
-                    score += 10;
-                }
+            if prev_match_idx.is_some_and(|prev| prev + 1 == i) {
+                score += 10;
             }
 
             // Word start bonus (after _, space, or at start)
             if i == 0 {
                 score += 5;
             } else {
-                let prev_char = text_bytes[i - 1];
-                if prev_char == b'_' || prev_char == b' ' || prev_char == b'.' {
+                let prev_char = text_chars[i - 1];
+                if prev_char == '_' || prev_char == ' ' || prev_char == '.' {
                     score += 5;
                 }
             }
@@ -100,13 +98,53 @@ pub fn subsequence_match(pattern: &str, text: &str) -> Option<MatchResult> {
     }
 
     // All pattern characters must be matched
-    if pattern_idx == pattern_bytes.len() {
+    if pattern_idx == pattern_chars.len() {
         Some(MatchResult::new(score, indices))
     } else {
         None
     }
 }
 
+/// Bonus for [`suffix_match`], kept below the exact prefix bonus.
+const SUFFIX_BONUS: i32 = 10;
+
+/// Match `pattern` against the end of `text`, ignoring ASCII case.
+///
+/// This lets `gravity` find `sv_gravity` with the whole word highlighted even
+/// when an earlier, scattered subsequence match exists. Only proper suffixes
+/// match; a pattern equal to the whole text is a prefix match instead.
+///
+/// # Scoring
+///
+/// Scored like a consecutive [`subsequence_match`] run starting at the suffix,
+/// plus +10 instead of the +20 exact prefix bonus.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_console_two::suffix_match;
+///
+/// let result = suffix_match("gravity", "sv_gravity").unwrap();
+/// assert_eq!(result.indices, (3..10).collect::<Vec<_>>());
+/// assert!(suffix_match("sv", "sv_gravity").is_none());
+/// ```
+pub fn suffix_match(pattern: &str, text: &str) -> Option<MatchResult> {
+    if pattern.is_empty() || pattern.len() >= text.len() {
+        return None;
+    }
+
+    let start = text.len() - pattern.len();
+    if !text.is_char_boundary(start) || !text[start..].eq_ignore_ascii_case(pattern) {
+        return None;
+    }
+
+    let len = pattern.chars().count();
+    let word_start = matches!(text.as_bytes()[start - 1], b'_' | b' ' | b'.');
+    let score = SUFFIX_BONUS + len as i32 + 10 * (len as i32 - 1) + if word_start { 5 } else { 0 };
+    let start = text[..start].chars().count();
+    Some(MatchResult::new(score, (start..start + len).collect()))
+}
+
 /// Match and sort multiple candidates by score.
 ///
 /// Returns candidates sorted by score (highest first), with their match results.
@@ -129,8 +167,71 @@ pub fn match_and_sort<'a>(
     matches
 }
 
+/// Match `text` against a glob `pattern`.
+///
+/// `*` matches any sequence (including none) and `?` matches exactly one
+/// character. All other characters match literally.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_console_two::core::glob_match;
+///
+/// assert!(glob_match("sv_*", "sv_gravity"));
+/// assert!(glob_match("*grav*", "sv_gravity"));
+/// assert!(glob_match("sv_?heats", "sv_cheats"));
+/// assert!(!glob_match("cl_*", "sv_gravity"));
+/// ```
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is currently matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character and retry
+                Some((star, star_t)) => {
+                    p = star + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("sv_*", "sv_gravity"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("sv_*max*", "sv_maxspeed"));
+        assert!(glob_match("sv_*max*", "sv_player_maxhealth"));
+        assert!(!glob_match("sv_*max*", "sv_gravity"));
+        assert!(glob_match("*gravity", "sv_gravity"));
+        assert!(!glob_match("*gravity", "sv_gravity_scale"));
+        assert!(glob_match("?v_*", "sv_cheats"));
+        assert!(!glob_match("sv_cheat?", "sv_cheat"));
+        assert!(glob_match("sv_cheats", "sv_cheats"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+    }
     use super::*;
 
     #[test]
@@ -141,6 +242,24 @@ mod tests {
         assert!(result.score > 4);
     }
 
+    #[test]
+    fn test_suffix_match() {
+        let result = suffix_match("GRAVITY", "sv_gravity").unwrap();
+        assert_eq!(result.indices, vec![3, 4, 5, 6, 7, 8, 9]);
+        // Beats the subsequence match, but not an exact prefix
+        assert!(result.score >= subsequence_match("gravity", "sv_gravity").unwrap().score);
+        assert!(result.score < subsequence_match("gravity", "gravity_scale").unwrap().score);
+
+        assert!(suffix_match("fps", "cl_showfps").is_some());
+        assert!(suffix_match("cl_fov", "cl_fov").is_none());
+        assert!(suffix_match("", "cl_fov").is_none());
+        assert!(suffix_match("gra", "sv_gravity").is_none());
+
+        // Indices count chars, not bytes
+        assert_eq!(suffix_match("speed", "ü_speed").unwrap().indices, vec![2, 3, 4, 5, 6]);
+        assert_eq!(subsequence_match("sd", "ü_speed").unwrap().indices, vec![2, 6]);
+    }
+
     #[test]
     fn test_subsequence_match() {
         let result = subsequence_match("sgr", "sv_gravity").unwrap();
@@ -7,6 +7,18 @@
 //! - [`ConsoleRegistry`] - Central registry for all console entries
 //! - [`Trie`] - Fast prefix lookup for autocomplete
 //! - [`tokenize`] - Simple command tokenizer
+//! - [`evaluate_arithmetic`] - Expression evaluator for the `set` command
+//! - [`CommandStats`] - Execution counters for profiling
+//! - [`ConsoleOutputBuffer`] - Recent output lines for searching
+//! - [`ConsoleInputRateLimit`] - Per-source input rate limiting
+//! - [`ConsoleTelemetry`] - Opt-in command usage callback
+//! - [`ConVarSnapshot`] - ConVar values readable from other threads
+//! - [`ConsoleLocalization`] - Translated ConVar descriptions
+//! - [`ConsoleInputEventLog`] - Recent console input for secondary readers
+//! - [`PendingAsyncCommands`] - Background tasks of async commands
+//! - [`ConVarChangeLog`] - Recent ConVar changes for infrequent systems
+//! - [`ConsoleAuditLog`] - Command access decisions for review and export
+//! - [`ObserveConVarExt`] - Bevy observers for individual ConVars
 //! - Events for communication between layers
 
 mod convar;
@@ -15,20 +27,51 @@ mod registry;
 mod trie;
 mod matcher;
 mod tokenizer;
+mod arithmetic;
 mod events;
 mod permissions;
 mod console;
+mod stats;
+mod output;
+mod rate_limit;
+mod telemetry;
+mod snapshot;
+mod localization;
+mod input_log;
+mod async_command;
+mod change_log;
+mod audit;
+mod observe;
 
-pub use convar::{ConVar, ConVarFlags, ConVarValue, ConVarDyn};
-pub use concommand::{ConCommand, ConCommandMeta, CommandHandler, CommandArgs};
-pub use registry::{ConsoleRegistry, ConEntry, ConVarMeta, CommandHandlers};
+pub use convar::{ConVar, ConVarFlags, ConVarValue, ConVarDyn, CsvList};
+pub use concommand::{ConCommand, ConCommandMeta, CommandHandler, CommandArgs, ArgDef, ArgType, StreamingOutput};
+pub use registry::{ConsoleRegistry, ConEntry, ConVarMeta, CommandHandlers, NamespacePolicy};
 pub use trie::Trie;
-pub use matcher::{subsequence_match, match_and_sort, MatchResult};
-pub use tokenizer::{tokenize, tokenize_string, split_commands, TokenizedCommand, TokenizeError};
+pub use matcher::{subsequence_match, suffix_match, match_and_sort, glob_match, MatchResult};
+pub use tokenizer::{tokenize, tokenize_string, split_commands, expand_vars, TokenizedCommand, TokenizeError};
+pub use arithmetic::{evaluate_arithmetic, ArithmeticError, MAX_NESTING_DEPTH};
 pub use events::{
-    ConsoleInputEvent, ConsoleOutputEvent, ConsoleOutputLevel,
-    ConVarChangedEvent, ConsoleToggleEvent, ConsoleClearEvent,
+    ConsoleInputEvent, CommandSource, ConsoleOutputEvent, ConsoleOutputLevel,
+    ConVarChangedEvent, TypedConVarChangedEvent, ConsoleToggleEvent, ConsoleClearEvent,
+    ConsolePauseEvent, ConsolePaused,
+    CommandExecutionEvent, CommandOutcome,
+    CommandErrorEvent, CommandError,
     ConsoleEventsPlugin,
 };
-pub use permissions::{PermissionLevel, ConsolePermissions};
+pub use permissions::{PermissionLevel, ConsolePermissions, ConsoleWhitelist, SANDBOX_MODE_COMMAND, SANDBOX_COMMANDS, DEFAULT_WHITELIST};
 pub use console::{Console, ConsoleRef};
+pub use stats::{CommandStats, CommandBudget};
+pub use output::{ConsoleOutputBuffer, GrepLine, DEFAULT_OUTPUT_BUFFER_SIZE};
+pub use rate_limit::{ConsoleInputRateLimit, DEFAULT_REMOTE_INPUTS_PER_SECOND};
+pub use telemetry::{ConsoleTelemetry, CommandTelemetryEvent};
+pub use snapshot::ConVarSnapshot;
+pub use localization::ConsoleLocalization;
+pub use input_log::{ConsoleInputEventLog, DEFAULT_INPUT_LOG_FRAMES};
+pub(crate) use input_log::record_console_input;
+pub use async_command::PendingAsyncCommands;
+pub(crate) use async_command::poll_async_commands;
+pub use change_log::{ConVarChangeLog, DEFAULT_CHANGE_LOG_SIZE};
+pub(crate) use change_log::record_convar_changes;
+pub use audit::{ConsoleAuditLog, AuditRecord, DEFAULT_AUDIT_LOG_SIZE};
+pub use observe::{ConVarChange, ObserveConVarExt};
+pub(crate) use snapshot::update_convar_snapshot;
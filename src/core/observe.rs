@@ -0,0 +1,115 @@
+//! Bevy observers for individual ConVars.
+//!
+//! Each [`ConVarFlags::NOTIFY`] variable can get an entity of its own, stored
+//! in [`ConVarMeta::entity`](super::ConVarMeta::entity). When the console
+//! changes the variable, a [`ConVarChange`] is triggered on that entity, so
+//! reacting to one variable doesn't need a system polling every change event.
+
+use bevy::ecs::system::IntoObserverSystem;
+use bevy::prelude::*;
+
+use super::{ConEntry, ConVarFlags, ConVarValue, ConsoleRegistry};
+
+/// Event triggered on a NOTIFY ConVar's entity when the console changes its value.
+///
+/// Like [`TypedConVarChangedEvent`](super::TypedConVarChangedEvent), it is
+/// sent for changes made through console input and commands, and for changes
+/// made through [`ConsoleRegistry`] setters such as `Console::set` the next
+/// time the console pipeline runs.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct ConVarChange<T: ConVarValue> {
+    /// The ConVar's entity.
+    pub entity: Entity,
+    /// The name of the ConVar that changed.
+    pub name: Box<str>,
+    /// The previous value.
+    pub old: T,
+    /// The new value.
+    pub new: T,
+}
+
+/// Extension for attaching observers to a ConVar's entity.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn setup(mut commands: Commands) {
+///     commands.observe_convar("sv_gravity", |change: On<ConVarChange<f32>>| {
+///         info!("Gravity {} -> {}", change.old, change.new);
+///     });
+/// }
+///
+/// app.add_systems(Startup, setup.after(ConsoleSet::Register));
+/// ```
+pub trait ObserveConVarExt {
+    /// Observe changes to the NOTIFY ConVar `name`, spawning its entity if needed.
+    ///
+    /// The ConVar must be registered by the time the command is applied, and
+    /// `T` must be its value type.
+    fn observe_convar<T: ConVarValue, B: Bundle, M>(
+        &mut self,
+        name: impl Into<Box<str>>,
+        observer: impl IntoObserverSystem<ConVarChange<T>, B, M>,
+    ) -> &mut Self;
+}
+
+impl ObserveConVarExt for Commands<'_, '_> {
+    fn observe_convar<T: ConVarValue, B: Bundle, M>(
+        &mut self,
+        name: impl Into<Box<str>>,
+        observer: impl IntoObserverSystem<ConVarChange<T>, B, M>,
+    ) -> &mut Self {
+        let name = name.into();
+        self.queue(move |world: &mut World| {
+            if let Some(entity) = convar_entity::<T>(world, &name) {
+                world.entity_mut(entity).observe(observer);
+            }
+        });
+        self
+    }
+}
+
+/// The entity of the ConVar `name`, spawned on first use.
+///
+/// Returns `None` with a warning if the ConVar doesn't exist, isn't flagged
+/// NOTIFY or doesn't hold a `T`.
+fn convar_entity<T: ConVarValue>(world: &mut World, name: &str) -> Option<Entity> {
+    let registry = world.resource::<ConsoleRegistry>();
+    let Some(ConEntry::Var(meta)) = registry.get_entry(name) else {
+        warn!("Cannot observe unknown ConVar '{}'", name);
+        return None;
+    };
+    if !meta.flags.contains(ConVarFlags::NOTIFY) {
+        warn!("Cannot observe ConVar '{}': it is not flagged NOTIFY", name);
+        return None;
+    }
+    if meta.type_name != std::any::type_name::<T>() {
+        warn!("Cannot observe ConVar '{}' as {}: it holds {}", name, std::any::type_name::<T>(), meta.type_name);
+        return None;
+    }
+    if let Some(entity) = meta.entity {
+        return Some(entity);
+    }
+
+    let entity = world.spawn(Name::new(format!("ConVar {}", name))).id();
+    if let Some(ConEntry::Var(meta)) = world.resource_mut::<ConsoleRegistry>().get_entry_mut(name) {
+        meta.entity = Some(entity);
+    }
+    Some(entity)
+}
+
+/// Trigger a [`ConVarChange`] on the entity of the ConVar `name`, if it has one.
+pub(crate) fn trigger_change<T: ConVarValue>(world: &mut World, name: &str, old: T, new: T) {
+    let entity = match world.get_resource::<ConsoleRegistry>().and_then(|registry| registry.get_entry(name)) {
+        Some(ConEntry::Var(meta)) => meta.entity,
+        _ => None,
+    };
+    if let Some(entity) = entity {
+        world.trigger(ConVarChange {
+            entity,
+            name: name.into(),
+            old,
+            new,
+        });
+    }
+}
@@ -0,0 +1,168 @@
+//! In-memory buffer of recent console output.
+//!
+//! Unlike the egui log, this buffer lives in the core layer so output can be
+//! searched (e.g. by the `grep` command) with any frontend.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+/// Default number of lines kept by [`ConsoleOutputBuffer`].
+pub const DEFAULT_OUTPUT_BUFFER_SIZE: usize = 1000;
+
+/// Resource holding the most recent console output lines.
+///
+/// Oldest lines are dropped once `capacity` is reached.
+#[derive(Resource, Debug, Clone)]
+pub struct ConsoleOutputBuffer {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl Default for ConsoleOutputBuffer {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_OUTPUT_BUFFER_SIZE)
+    }
+}
+
+/// A line produced by [`ConsoleOutputBuffer::grep`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepLine {
+    /// Line text, with matches wrapped in `[` `]` if `is_match`.
+    pub text: String,
+    /// `false` for context lines printed after a match.
+    pub is_match: bool,
+}
+
+impl ConsoleOutputBuffer {
+    /// Create an empty buffer keeping at most `capacity` lines.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity.min(DEFAULT_OUTPUT_BUFFER_SIZE)),
+            capacity,
+        }
+    }
+
+    /// Append a message, splitting it into lines.
+    pub fn push(&mut self, message: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        for line in message.lines() {
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line.to_string());
+        }
+    }
+
+    /// Iterate over stored lines, oldest first.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(String::as_str)
+    }
+
+    /// Number of stored lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Remove all lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Find lines containing `pattern`, plus `after` lines of context per match.
+    ///
+    /// Matching is case-insensitive (ASCII) unless `case_sensitive` is set.
+    pub fn grep(&self, pattern: &str, case_sensitive: bool, after: usize) -> Vec<GrepLine> {
+        let mut result = Vec::new();
+        let mut context_left = 0;
+        for line in &self.lines {
+            if let Some(text) = highlight_matches(line, pattern, case_sensitive) {
+                result.push(GrepLine { text, is_match: true });
+                context_left = after;
+            } else if context_left > 0 {
+                result.push(GrepLine { text: line.clone(), is_match: false });
+                context_left -= 1;
+            }
+        }
+        result
+    }
+}
+
+/// Wrap every occurrence of `pattern` in brackets, or `None` if there is none.
+fn highlight_matches(line: &str, pattern: &str, case_sensitive: bool) -> Option<String> {
+    if pattern.is_empty() {
+        return None;
+    }
+    // ASCII lowercasing keeps byte offsets valid for slicing the original line
+    let (haystack, needle) = if case_sensitive {
+        (line.to_string(), pattern.to_string())
+    } else {
+        (line.to_ascii_lowercase(), pattern.to_ascii_lowercase())
+    };
+
+    let mut out = String::with_capacity(line.len() + 2);
+    let mut last = 0;
+    let mut found = false;
+    for (start, _) in haystack.match_indices(&needle) {
+        let end = start + needle.len();
+        out.push_str(&line[last..start]);
+        out.push('[');
+        out.push_str(&line[start..end]);
+        out.push(']');
+        last = end;
+        found = true;
+    }
+    if !found {
+        return None;
+    }
+    out.push_str(&line[last..]);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_buffer_capacity() {
+        let mut buffer = ConsoleOutputBuffer::with_capacity(3);
+        buffer.push("a\nb");
+        buffer.push("c");
+        buffer.push("d");
+        assert_eq!(buffer.lines().collect::<Vec<_>>(), vec!["b", "c", "d"]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_output_buffer_grep() {
+        let mut buffer = ConsoleOutputBuffer::default();
+        buffer.push("Loading map");
+        buffer.push("ERROR: missing texture");
+        buffer.push("retrying");
+        buffer.push("done");
+        buffer.push("error again, error");
+
+        let matches = buffer.grep("error", false, 0);
+        assert_eq!(matches, vec![
+            GrepLine { text: "[ERROR]: missing texture".into(), is_match: true },
+            GrepLine { text: "[error] again, [error]".into(), is_match: true },
+        ]);
+
+        assert_eq!(buffer.grep("error", true, 0).len(), 1);
+
+        let with_context = buffer.grep("ERROR", true, 1);
+        assert_eq!(with_context.len(), 2);
+        assert_eq!(with_context[1], GrepLine { text: "retrying".into(), is_match: false });
+
+        assert!(buffer.grep("", false, 0).is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 //! Permission levels for console access control.
 //!
-//! Provides hierarchical permissions for commands and variables.
+//! Provides hierarchical permissions for commands and variables, plus an
+//! optional command whitelist for sandboxed consoles.
+
+use std::collections::HashSet;
 
 use bevy::prelude::*;
 
@@ -21,7 +24,8 @@ use bevy::prelude::*;
 ///
 /// Games needing finer-grained roles (e.g., moderators) should implement
 /// their own authorization layer on top of these base levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Reflect)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PermissionLevel {
     /// Basic user - can access general commands and variables.
@@ -66,7 +70,8 @@ impl std::fmt::Display for PermissionLevel {
 ///     };
 /// }
 /// ```
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
 pub struct ConsolePermissions {
     /// The current permission level.
     pub current_level: PermissionLevel,
@@ -98,6 +103,64 @@ impl ConsolePermissions {
     }
 }
 
+/// Command that switches the [`ConsoleWhitelist`] on and off.
+pub const SANDBOX_MODE_COMMAND: &str = "sandbox_mode";
+
+/// Commands that manage the [`ConsoleWhitelist`]. They are never blocked by
+/// it, so sandbox mode can always be adjusted or switched off again.
+pub const SANDBOX_COMMANDS: &[&str] = &[SANDBOX_MODE_COMMAND, "sandbox_allow", "sandbox_deny", "sandbox_list"];
+
+/// Commands a new [`ConsoleWhitelist`] allows.
+pub const DEFAULT_WHITELIST: &[&str] = &["echo", "help", "cvarlist", "find"];
+
+/// Resource restricting which commands may run, for limited in-game consoles.
+///
+/// While `enabled`, commands not in `allowed` are rejected. Variables are
+/// unaffected. Disabled by default, allowing [`DEFAULT_WHITELIST`] once enabled.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut whitelist = ConsoleWhitelist::default();
+/// whitelist.allow("echo").allow("help");
+/// whitelist.enabled = true;
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ConsoleWhitelist {
+    /// Whether the whitelist is enforced.
+    pub enabled: bool,
+    /// Commands that may run while enabled.
+    pub allowed: HashSet<Box<str>>,
+}
+
+impl Default for ConsoleWhitelist {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed: DEFAULT_WHITELIST.iter().map(|&name| name.into()).collect(),
+        }
+    }
+}
+
+impl ConsoleWhitelist {
+    /// Add a command to the whitelist.
+    pub fn allow(&mut self, name: impl Into<Box<str>>) -> &mut Self {
+        self.allowed.insert(name.into());
+        self
+    }
+
+    /// Remove a command from the whitelist.
+    pub fn deny(&mut self, name: &str) -> &mut Self {
+        self.allowed.remove(name);
+        self
+    }
+
+    /// Whether the command `name` may run.
+    pub fn is_allowed(&self, name: &str) -> bool {
+        !self.enabled || SANDBOX_COMMANDS.contains(&name) || self.allowed.contains(name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +192,18 @@ mod tests {
         assert_eq!(PermissionLevel::Admin.name(), "Admin");
         assert_eq!(PermissionLevel::Server.name(), "Server");
     }
+
+    #[test]
+    fn test_whitelist() {
+        let mut whitelist = ConsoleWhitelist::default();
+        assert!(whitelist.is_allowed("quit"));
+        assert!(whitelist.allowed.contains("help"));
+
+        whitelist.allow("echo").allow("quit").deny("quit");
+        whitelist.enabled = true;
+        assert!(whitelist.is_allowed("echo"));
+        assert!(!whitelist.is_allowed("quit"));
+        assert!(whitelist.is_allowed(SANDBOX_MODE_COMMAND));
+        assert!(whitelist.is_allowed("sandbox_allow"));
+    }
 }
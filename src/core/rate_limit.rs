@@ -0,0 +1,133 @@
+//! Per-source rate limiting of console input.
+//!
+//! Applied to [`ConsoleInputEvent`](super::ConsoleInputEvent)s before they are
+//! tokenized, so a flood of remote input cannot stall the command pipeline.
+
+use std::collections::{HashMap, VecDeque};
+use std::mem::{self, Discriminant};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+
+use super::CommandSource;
+
+/// Default limit for [`CommandSource::Remote`] input.
+pub const DEFAULT_REMOTE_INPUTS_PER_SECOND: usize = 10;
+
+/// Length of the sliding window inputs are counted over.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Inputs accepted in the last second from one kind of source.
+#[derive(Debug, Clone)]
+struct SourceWindow {
+    max_per_second: usize,
+    accepted: VecDeque<Instant>,
+}
+
+/// Resource limiting how many input events each [`CommandSource`] may submit per second.
+///
+/// Limits apply to a source variant as a whole, regardless of its data (e.g.
+/// all script files share one limit). Input over the limit is dropped with an
+/// error. Only [`CommandSource::Remote`] is limited by default.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut limit = ConsoleInputRateLimit::default();
+/// limit.set_limit(&CommandSource::Terminal, Some(20));
+/// limit.set_limit(&CommandSource::Remote, None);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ConsoleInputRateLimit {
+    windows: HashMap<Discriminant<CommandSource>, SourceWindow>,
+}
+
+impl Default for ConsoleInputRateLimit {
+    fn default() -> Self {
+        let mut limit = Self { windows: HashMap::new() };
+        limit.set_limit(&CommandSource::Remote, Some(DEFAULT_REMOTE_INPUTS_PER_SECOND));
+        limit
+    }
+}
+
+impl ConsoleInputRateLimit {
+    /// Create a rate limit that lets all input through.
+    pub fn unlimited() -> Self {
+        Self { windows: HashMap::new() }
+    }
+
+    /// Set the inputs allowed per second for sources like `source`, or `None` for no limit.
+    pub fn set_limit(&mut self, source: &CommandSource, max_per_second: Option<usize>) {
+        let key = mem::discriminant(source);
+        match max_per_second {
+            Some(max_per_second) => {
+                self.windows.insert(key, SourceWindow { max_per_second, accepted: VecDeque::new() });
+            }
+            None => {
+                self.windows.remove(&key);
+            }
+        }
+    }
+
+    /// The inputs allowed per second for sources like `source`.
+    pub fn limit(&self, source: &CommandSource) -> Option<usize> {
+        self.windows.get(&mem::discriminant(source)).map(|window| window.max_per_second)
+    }
+
+    /// Record an input from `source` at `now`, returning `false` if it exceeds the limit.
+    pub fn check(&mut self, source: &CommandSource, now: Instant) -> bool {
+        let Some(window) = self.windows.get_mut(&mem::discriminant(source)) else {
+            return true;
+        };
+        while window
+            .accepted
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= RATE_LIMIT_WINDOW)
+        {
+            window.accepted.pop_front();
+        }
+        if window.accepted.len() >= window.max_per_second {
+            return false;
+        }
+        window.accepted.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_sliding_window() {
+        let mut limit = ConsoleInputRateLimit::default();
+        let remote = CommandSource::Remote;
+        let start = Instant::now();
+
+        for i in 0..DEFAULT_REMOTE_INPUTS_PER_SECOND {
+            assert!(limit.check(&remote, start + Duration::from_millis(i as u64)));
+        }
+        assert!(!limit.check(&remote, start + Duration::from_millis(500)));
+        assert!(limit.check(&remote, start + Duration::from_millis(1000)));
+
+        // Unlimited sources always pass
+        for _ in 0..100 {
+            assert!(limit.check(&CommandSource::Ui, start));
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_per_variant() {
+        let mut limit = ConsoleInputRateLimit::unlimited();
+        assert_eq!(limit.limit(&CommandSource::Remote), None);
+
+        limit.set_limit(&CommandSource::Script { file: "".into() }, Some(1));
+        let now = Instant::now();
+        assert!(limit.check(&CommandSource::Script { file: "a.cfg".into() }, now));
+        assert!(!limit.check(&CommandSource::Script { file: "b.cfg".into() }, now));
+        assert_eq!(limit.limit(&CommandSource::Script { file: "b.cfg".into() }), Some(1));
+
+        limit.set_limit(&CommandSource::Script { file: "".into() }, None);
+        assert!(limit.check(&CommandSource::Script { file: "a.cfg".into() }, now));
+    }
+}
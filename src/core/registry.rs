@@ -2,17 +2,23 @@
 //!
 //! Central storage with trie-based lookup for fast autocomplete.
 
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::time::Instant;
 
 use bevy::prelude::*;
 
 use super::{
     ConCommand, ConCommandMeta, ConVar, ConVarDyn, ConVarFlags, ConVarValue,
-    Trie, subsequence_match, matcher::MatchResult,
+    Trie, subsequence_match, suffix_match, glob_match, matcher::MatchResult,
     CommandHandler, concommand::AutocompleteProvider,
-    PermissionLevel,
+    PermissionLevel, ConsoleLocalization, ConVarChangedEvent, events,
 };
 
+/// Score of an entry found only through its description, equal to the default
+/// autocomplete threshold so such matches rank below any name match.
+const DESCRIPTION_MATCH_SCORE: i32 = 5;
+
 /// Entry type in the console registry.
 pub enum ConEntry {
     /// A console variable.
@@ -38,6 +44,25 @@ impl ConEntry {
         }
     }
 
+    /// Get the description translated through `localization`.
+    ///
+    /// Falls back to [`description`](Self::description) when the entry has no
+    /// localization key or the key has no string.
+    pub fn description_localized<'a>(&'a self, localization: &'a ConsoleLocalization) -> &'a str {
+        match self {
+            ConEntry::Var(meta) => meta.description_localized(localization),
+            ConEntry::Cmd(meta) => meta.get_description(),
+        }
+    }
+
+    /// Get the usage text of this entry (commands only).
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            ConEntry::Var(_) => None,
+            ConEntry::Cmd(meta) => meta.help_text,
+        }
+    }
+
     /// Get the flags of this entry.
     pub fn flags(&self) -> ConVarFlags {
         match self {
@@ -118,6 +143,31 @@ impl CommandHandlers {
     pub fn has_autocomplete(&self, name: &str) -> bool {
         self.autocomplete.contains_key(name)
     }
+
+    /// Iterate over the names of all commands with a registered handler.
+    pub fn iter_names(&self) -> impl Iterator<Item = &str> {
+        self.handlers.keys().map(|name| name.as_ref())
+    }
+
+    /// Move all handlers from `other` into this storage.
+    ///
+    /// Handlers whose name is already taken are skipped with a warning.
+    /// Returns the number of handlers merged.
+    pub fn merge_from(&mut self, mut other: CommandHandlers) -> usize {
+        let mut merged = 0;
+        for (name, handler) in other.handlers {
+            if self.handlers.contains_key(&name) {
+                bevy::log::warn!("Console: Not merging handler '{}', one is already registered", name);
+                continue;
+            }
+            if let Some(ac) = other.autocomplete.remove(&name) {
+                self.autocomplete.insert(name.clone(), ac);
+            }
+            self.handlers.insert(name, handler);
+            merged += 1;
+        }
+        merged
+    }
 }
 
 /// Metadata for a type-erased ConVar.
@@ -126,12 +176,24 @@ pub struct ConVarMeta {
     pub name: Box<str>,
     /// Description.
     pub description: &'static str,
+    /// Localization key for the description.
+    pub description_key: Option<&'static str>,
     /// Flags.
     pub flags: ConVarFlags,
     /// Required permission level.
     pub required_permission: PermissionLevel,
+    /// Name of the backing Rust type (from `std::any::type_name`).
+    pub type_name: &'static str,
+    /// When the value was last changed through [`set_string`](Self::set_string)
+    /// or [`ConsoleRegistry::set`]. Not persisted.
+    pub last_modified_at: Option<Instant>,
+    /// Entity that [`ConVarChange`](super::ConVarChange) is triggered on, spawned by
+    /// the first [`observe_convar`](super::ObserveConVarExt::observe_convar) call.
+    pub entity: Option<Entity>,
     /// Type-erased value storage.
     value: Box<dyn ConVarDyn>,
+    /// Writes the typed change event for the backing type.
+    typed_change: fn(&mut World, &str, &str, &str),
 }
 
 impl ConVarMeta {
@@ -140,12 +202,33 @@ impl ConVarMeta {
         Self {
             name: cvar.name().into(),
             description: cvar.get_description(),
+            description_key: cvar.get_description_key(),
             flags: cvar.get_flags(),
             required_permission: cvar.get_required_permission(),
+            type_name: std::any::type_name::<T>(),
+            last_modified_at: None,
+            entity: None,
             value: Box::new(cvar),
+            typed_change: events::write_typed_change::<T>,
         }
     }
 
+    /// Get the description translated through `localization`, falling back
+    /// to the static description.
+    pub fn description_localized<'a>(&'a self, localization: &'a ConsoleLocalization) -> &'a str {
+        self.description_key
+            .and_then(|key| localization.get(key))
+            .unwrap_or(self.description)
+    }
+
+    /// Get the writer for this variable's [`TypedConVarChangedEvent`](super::TypedConVarChangedEvent),
+    /// which also triggers its [`ConVarChange`](super::ConVarChange).
+    ///
+    /// Called as `writer(world, name, old_value, new_value)`.
+    pub fn typed_change_writer(&self) -> fn(&mut World, &str, &str, &str) {
+        self.typed_change
+    }
+
     /// Get the current value as a string.
     pub fn get_string(&self) -> String {
         self.value.get_string()
@@ -153,7 +236,19 @@ impl ConVarMeta {
 
     /// Set the value from a string.
     pub fn set_string(&mut self, s: &str) -> bool {
-        self.value.set_string(s)
+        let changed = self.value.set_string(s);
+        if changed {
+            self.last_modified_at = Some(Instant::now());
+        }
+        changed
+    }
+
+    /// The value `s` would be stored as, formatted, without changing this variable.
+    ///
+    /// Returns `None` if [`set_string`](Self::set_string) would reject it.
+    pub fn validate_string(&self, s: &str) -> Option<String> {
+        let mut value = self.value.clone_boxed();
+        value.set_string(s).then(|| value.get_string())
     }
 
     /// Get the default value as a string.
@@ -171,6 +266,18 @@ impl ConVarMeta {
         self.value.is_modified()
     }
 
+    /// Describe the bounds and allowed values, or an empty string if there are none.
+    pub fn constraint_description(&self) -> String {
+        self.value.constraint_description()
+    }
+
+    /// Values to offer when autocompleting an assignment to this variable.
+    ///
+    /// See [`ConVarDyn::value_completions`].
+    pub fn value_completions(&self) -> Vec<String> {
+        self.value.value_completions()
+    }
+
     /// Try to downcast to a specific ConVar type.
     pub fn downcast_ref<T: ConVarValue + PartialEq + 'static>(&self) -> Option<&ConVar<T>> {
         self.value.as_any().downcast_ref()
@@ -182,6 +289,23 @@ impl ConVarMeta {
     }
 }
 
+/// Callback that can reject a registration by returning `false`.
+type BeforeRegisterHook = Box<dyn Fn(&str, &ConEntry) -> bool + Send + Sync>;
+/// Callback run after a successful registration.
+type AfterRegisterHook = Box<dyn Fn(&str, &ConEntry) + Send + Sync>;
+
+/// How names missing a required namespace prefix are handled.
+///
+/// See [`ConsoleRegistry::require_namespace_prefix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NamespacePolicy {
+    /// Log a warning and register the entry anyway.
+    #[default]
+    Warn,
+    /// Log an error and reject the registration.
+    Reject,
+}
+
 /// Central registry for console variables and commands.
 ///
 /// Uses a trie for O(k) lookup and fast prefix iteration for autocomplete.
@@ -210,6 +334,16 @@ pub struct ConsoleRegistry {
     trie: Trie<()>,
     /// Actual storage (trie stores () to save memory, we lookup here).
     entries: HashMap<Box<str>, ConEntry>,
+    /// Callbacks that can veto a registration, run in the order added.
+    before_register: Vec<BeforeRegisterHook>,
+    /// Callbacks run after an entry is stored, in the order added.
+    after_register: Vec<AfterRegisterHook>,
+    /// Prefixes new names must start with (any one of them). Empty means no check.
+    namespace_prefixes: Vec<&'static str>,
+    /// What to do with names missing a required prefix.
+    namespace_policy: NamespacePolicy,
+    /// Changes made through the registry's setters, waiting to notify listeners.
+    changes: Vec<ConVarChangedEvent>,
 }
 
 impl ConsoleRegistry {
@@ -218,24 +352,108 @@ impl ConsoleRegistry {
         Self::default()
     }
 
-    /// Register a console variable.
+    /// Add a callback that runs before each variable or command is registered.
     ///
-    /// Returns `true` if the variable was newly registered, `false` if it replaced an existing entry.
-    /// A warning is logged if a duplicate is detected.
-    pub fn register_var<T: ConVarValue + PartialEq>(&mut self, cvar: ConVar<T>) -> bool {
-        let name: Box<str> = cvar.name().into();
-        let is_duplicate = self.entries.contains_key(&name);
+    /// Returning `false` rejects the registration and the entry is not stored.
+    /// Callbacks run in the order they were added; the first rejection stops the chain.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// registry.on_before_register(|name, entry| {
+    ///     !(entry.flags().contains(ConVarFlags::CHEAT) && name.starts_with("mod_"))
+    /// });
+    /// ```
+    pub fn on_before_register(&mut self, callback: impl Fn(&str, &ConEntry) -> bool + Send + Sync + 'static) {
+        self.before_register.push(Box::new(callback));
+    }
 
+    /// Add a callback that runs after each variable or command is stored.
+    pub fn on_after_register(&mut self, callback: impl Fn(&str, &ConEntry) + Send + Sync + 'static) {
+        self.after_register.push(Box::new(callback));
+    }
+
+    /// Require newly registered names to start with `prefix`, e.g. `"physics_"`.
+    ///
+    /// With several prefixes, a name must match any one of them. This is a
+    /// development-time guard: entries registered earlier are not checked, and
+    /// by default a mismatch only logs a warning (see [`set_namespace_policy`](Self::set_namespace_policy)).
+    pub fn require_namespace_prefix(&mut self, prefix: &'static str) {
+        if !self.namespace_prefixes.contains(&prefix) {
+            self.namespace_prefixes.push(prefix);
+        }
+    }
+
+    /// Set whether names missing a required prefix are warned about or rejected.
+    pub fn set_namespace_policy(&mut self, policy: NamespacePolicy) {
+        self.namespace_policy = policy;
+    }
+
+    /// Remove all required namespace prefixes.
+    pub fn clear_namespace_requirements(&mut self) {
+        self.namespace_prefixes.clear();
+    }
+
+    /// Check `name` against the required prefixes. Returns `false` if it should be rejected.
+    fn check_namespace(&self, name: &str) -> bool {
+        if self.namespace_prefixes.is_empty()
+            || self.namespace_prefixes.iter().any(|prefix| name.starts_with(prefix))
+        {
+            return true;
+        }
+        let prefixes = self.namespace_prefixes.join("', '");
+        match self.namespace_policy {
+            NamespacePolicy::Warn => {
+                bevy::log::warn!("Console: '{}' does not start with a required prefix ('{}')", name, prefixes);
+                true
+            }
+            NamespacePolicy::Reject => {
+                bevy::log::error!("Console: Rejected '{}', it does not start with a required prefix ('{}')", name, prefixes);
+                false
+            }
+        }
+    }
+
+    /// Store an entry unless the namespace check or a `before_register` callback rejects it.
+    ///
+    /// Returns `None` if rejected, otherwise whether the name was new.
+    fn insert_entry(&mut self, name: Box<str>, entry: ConEntry) -> Option<bool> {
+        if !self.check_namespace(&name) {
+            return None;
+        }
+        if !self.before_register.iter().all(|callback| callback(&name, &entry)) {
+            bevy::log::warn!("Console: Registration of '{}' was rejected", name);
+            return None;
+        }
+
+        let is_duplicate = self.entries.contains_key(&name);
         if is_duplicate {
             bevy::log::warn!(
-                "Console: Overwriting existing entry '{}' with new variable",
-                name
+                "Console: Overwriting existing entry '{}' with new {}",
+                name,
+                if entry.is_var() { "variable" } else { "command" }
             );
         }
 
         self.trie.insert(&name, ());
-        self.entries.insert(name, ConEntry::Var(ConVarMeta::from_convar(cvar)));
-        !is_duplicate
+        self.entries.insert(name.clone(), entry);
+        if let Some(entry) = self.entries.get(&name) {
+            for callback in &self.after_register {
+                callback(&name, entry);
+            }
+        }
+        Some(!is_duplicate)
+    }
+
+    /// Register a console variable.
+    ///
+    /// Returns `true` if the variable was newly registered, `false` if it replaced an existing entry
+    /// or was rejected by an [`on_before_register`](Self::on_before_register) callback or
+    /// [`NamespacePolicy::Reject`].
+    /// A warning is logged if a duplicate is detected.
+    pub fn register_var<T: ConVarValue + PartialEq>(&mut self, cvar: ConVar<T>) -> bool {
+        let name: Box<str> = cvar.name().into();
+        self.insert_entry(name, ConEntry::Var(ConVarMeta::from_convar(cvar))).unwrap_or(false)
     }
 
     /// Register a console command's metadata.
@@ -243,90 +461,160 @@ impl ConsoleRegistry {
     /// Note: The handler must be registered separately in `CommandHandlers`.
     /// Use `register_cmd_full` for a complete registration when you have access to `CommandHandlers`.
     ///
-    /// Returns `true` if newly registered, `false` if it replaced an existing entry.
+    /// Returns `true` if newly registered, `false` if it replaced an existing entry
+    /// or was rejected by an [`on_before_register`](Self::on_before_register) callback.
     pub fn register_cmd_meta(&mut self, meta: ConCommandMeta) -> bool {
         let name: Box<str> = meta.name.clone();
-        let is_duplicate = self.entries.contains_key(&name);
-
-        if is_duplicate {
-            bevy::log::warn!(
-                "Console: Overwriting existing entry '{}' with new command",
-                name
-            );
-        }
-
-        self.trie.insert(&name, ());
-        self.entries.insert(name, ConEntry::Cmd(meta));
-        !is_duplicate
+        self.insert_entry(name, ConEntry::Cmd(meta)).unwrap_or(false)
     }
 
     /// Register a console command, returning the handler for separate storage.
     ///
     /// The returned tuple contains (name, handler, autocomplete, is_new) which should be
-    /// stored in `CommandHandlers`. `is_new` is `false` if an existing entry was overwritten.
+    /// stored in `CommandHandlers`. `is_new` is `false` if an existing entry was overwritten
+    /// or the command was rejected; use [`try_register_cmd`](Self::try_register_cmd)
+    /// to tell the two apart.
     pub fn register_cmd(&mut self, cmd: ConCommand) -> (Box<str>, CommandHandler, Option<AutocompleteProvider>, bool) {
         let (meta, handler, autocomplete) = cmd.split();
         let name = meta.name.clone();
-        let is_duplicate = self.entries.contains_key(&name);
-
-        if is_duplicate {
-            bevy::log::warn!(
-                "Console: Overwriting existing entry '{}' with new command",
-                name
-            );
-        }
+        let is_new = self.insert_entry(name.clone(), ConEntry::Cmd(meta)).unwrap_or(false);
+        (name, handler, autocomplete, is_new)
+    }
 
-        self.trie.insert(&name, ());
-        self.entries.insert(name.clone(), ConEntry::Cmd(meta));
-        (name, handler, autocomplete, !is_duplicate)
+    /// Register a console command like [`register_cmd`](Self::register_cmd),
+    /// returning `None` if an [`on_before_register`](Self::on_before_register)
+    /// callback or [`NamespacePolicy::Reject`] rejected it.
+    ///
+    /// The handler of a rejected command is dropped.
+    pub fn try_register_cmd(&mut self, cmd: ConCommand) -> Option<(Box<str>, CommandHandler, Option<AutocompleteProvider>, bool)> {
+        let (meta, handler, autocomplete) = cmd.split();
+        let name = meta.name.clone();
+        let is_new = self.insert_entry(name.clone(), ConEntry::Cmd(meta))?;
+        Some((name, handler, autocomplete, is_new))
     }
 
     /// Get an entry by name.
+    ///
+    /// Falls back to a case-insensitive lookup when no exact match exists.
     pub fn get_entry(&self, name: &str) -> Option<&ConEntry> {
-        self.entries.get(name)
+        if let Some(entry) = self.entries.get(name) {
+            return Some(entry);
+        }
+
+        let canonical = self.resolve_ci(name)?;
+        self.entries.get(canonical)
     }
 
     /// Get a mutable entry by name.
+    ///
+    /// Falls back to a case-insensitive lookup like [`get_entry`](Self::get_entry).
     pub fn get_entry_mut(&mut self, name: &str) -> Option<&mut ConEntry> {
-        self.entries.get_mut(name)
+        if self.entries.contains_key(name) {
+            return self.entries.get_mut(name);
+        }
+
+        let canonical = self.resolve_ci(name)?.to_string();
+        self.entries.get_mut(canonical.as_str())
+    }
+
+    /// Resolve a name to its registered spelling, ignoring ASCII case.
+    fn resolve_ci(&self, name: &str) -> Option<&str> {
+        self.trie.get_ci(name).map(|(canonical, _)| canonical)
     }
 
     /// Get a ConVar's value by name.
     pub fn get<T: ConVarValue + PartialEq + 'static>(&self, name: &str) -> Option<T> {
-        match self.entries.get(name)? {
+        match self.get_entry(name)? {
             ConEntry::Var(meta) => meta.downcast_ref::<T>().map(|cvar| cvar.get()),
             ConEntry::Cmd(_) => None,
         }
     }
 
+    /// Get a typed reference to a ConVar by name.
+    ///
+    /// Unlike [`get`](Self::get), this exposes the ConVar itself so callers
+    /// can inspect bounds, flags, and the default value.
+    pub fn get_var<T: ConVarValue + PartialEq + 'static>(&self, name: &str) -> Option<&ConVar<T>> {
+        match self.get_entry(name)? {
+            ConEntry::Var(meta) => meta.downcast_ref::<T>(),
+            ConEntry::Cmd(_) => None,
+        }
+    }
+
+    /// Get a typed mutable reference to a ConVar by name.
+    pub fn get_var_mut<T: ConVarValue + PartialEq + 'static>(&mut self, name: &str) -> Option<&mut ConVar<T>> {
+        match self.get_entry_mut(name)? {
+            ConEntry::Var(meta) => meta.downcast_mut::<T>(),
+            ConEntry::Cmd(_) => None,
+        }
+    }
+
+    /// Get the backing type name of an entry.
+    ///
+    /// Returns the ConVar's Rust type name for variables and `"command"` for commands.
+    pub fn entry_type_name(&self, name: &str) -> Option<&str> {
+        match self.get_entry(name)? {
+            ConEntry::Var(meta) => Some(meta.type_name),
+            ConEntry::Cmd(_) => Some("command"),
+        }
+    }
+
     /// Get a ConVar's value as a string.
     pub fn get_string(&self, name: &str) -> Option<String> {
-        match self.entries.get(name)? {
+        match self.get_entry(name)? {
             ConEntry::Var(meta) => Some(meta.get_string()),
             ConEntry::Cmd(_) => None,
         }
     }
 
     /// Set a ConVar's value.
+    ///
+    /// Falls back to a case-insensitive lookup like [`get_entry`](Self::get_entry).
+    /// Like [`set_string`](Self::set_string) and [`reset`](Self::reset), the
+    /// change is reported to [`ConVarChangedEvent`] readers and, for NOTIFY
+    /// variables, typed events and observers when the console pipeline next runs.
     pub fn set<T: ConVarValue + PartialEq + 'static>(&mut self, name: &str, value: T) -> bool {
-        match self.entries.get_mut(name) {
-            Some(ConEntry::Var(meta)) => {
-                if let Some(cvar) = meta.downcast_mut::<T>() {
-                    cvar.set(value)
-                } else {
-                    false
-                }
+        self.update_var(name, |meta| {
+            let changed = meta.downcast_mut::<T>().is_some_and(|cvar| cvar.set(value));
+            if changed {
+                meta.last_modified_at = Some(Instant::now());
             }
-            _ => false,
-        }
+            changed
+        })
     }
 
     /// Set a ConVar's value from a string.
     pub fn set_string(&mut self, name: &str, value: &str) -> bool {
-        match self.entries.get_mut(name) {
-            Some(ConEntry::Var(meta)) => meta.set_string(value),
-            _ => false,
+        self.update_var(name, |meta| meta.set_string(value))
+    }
+
+    /// Reset a ConVar to its default value.
+    ///
+    /// Returns `false` if no variable has that name.
+    pub fn reset(&mut self, name: &str) -> bool {
+        self.update_var(name, |meta| {
+            meta.reset();
+            true
+        })
+    }
+
+    /// Apply `update` to the variable `name`, recording the change if it succeeds.
+    fn update_var(&mut self, name: &str, update: impl FnOnce(&mut ConVarMeta) -> bool) -> bool {
+        let Some(ConEntry::Var(meta)) = self.get_entry_mut(name) else {
+            return false;
+        };
+        let old_value = meta.get_string();
+        if !update(meta) {
+            return false;
         }
+        let change = ConVarChangedEvent::new(meta.name.clone(), old_value, meta.get_string());
+        self.changes.push(change);
+        true
+    }
+
+    /// Take the changes recorded by the setters since the last call.
+    pub(crate) fn take_changes(&mut self) -> Vec<ConVarChangedEvent> {
+        std::mem::take(&mut self.changes)
     }
 
     /// Check if an entry exists.
@@ -351,6 +639,15 @@ impl ConsoleRegistry {
             .filter_map(|(name, _)| self.entries.get(name).map(|e| (name, e)))
     }
 
+    /// Iterate over entries whose names match a glob pattern (`*` and `?`).
+    ///
+    /// The literal text before the first wildcard is looked up in the trie.
+    pub fn glob_iter<'a>(&'a self, pattern: &'a str) -> impl Iterator<Item = (&'a str, &'a ConEntry)> {
+        let prefix_len = pattern.find(['*', '?']).unwrap_or(pattern.len());
+        self.prefix_iter(&pattern[..prefix_len])
+            .filter(move |(name, _)| glob_match(pattern, name))
+    }
+
     /// Iterate over all entries.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &ConEntry)> {
         self.entries.iter().map(|(k, v)| (k.as_ref(), v))
@@ -377,28 +674,109 @@ impl ConsoleRegistry {
         self.vars().filter(|(_, meta)| meta.is_modified())
     }
 
+    /// Iterate over all variables with non-default values, most recently modified first.
+    ///
+    /// Variables changed without going through the registry sort last, by name.
+    pub fn modified_vars_by_recency(&self) -> impl Iterator<Item = (&str, &ConVarMeta)> {
+        let mut vars: Vec<_> = self.modified_vars().collect();
+        vars.sort_by_key(|(name, meta)| (Reverse(meta.last_modified_at), *name));
+        vars.into_iter()
+    }
+
     /// Iterate over all variables with ARCHIVE flag.
     pub fn archive_vars(&self) -> impl Iterator<Item = (&str, &ConVarMeta)> {
         self.vars().filter(|(_, meta)| meta.flags.contains(ConVarFlags::ARCHIVE))
     }
 
-    /// Find entries matching a fuzzy pattern.
+    /// Find entries whose name contains the characters of `pattern` in order.
     ///
-    /// Returns entries sorted by match score (best first).
+    /// Returns entries sorted by match score (best first). See
+    /// [`fuzzy_find_with_options`](Self::fuzzy_find_with_options) to also match
+    /// name suffixes and descriptions.
     pub fn fuzzy_find(&self, pattern: &str) -> Vec<(&str, &ConEntry, MatchResult)> {
+        self.fuzzy_find_with_threshold(pattern, None)
+    }
+
+    /// Like [`fuzzy_find`](Self::fuzzy_find), but drops matches scoring below `min_score`.
+    pub fn fuzzy_find_with_threshold(
+        &self,
+        pattern: &str,
+        min_score: Option<i32>,
+    ) -> Vec<(&str, &ConEntry, MatchResult)> {
+        self.fuzzy_find_with_options(pattern, min_score, false)
+    }
+
+    /// Find entries matching a fuzzy pattern, dropping matches scoring below `min_score`.
+    ///
+    /// With `suffix_search`, names ending in `pattern` get a [`suffix_match`]
+    /// bonus and entries whose description contains `pattern` (at least three
+    /// characters, ignoring case) match with a low score and no highlighted
+    /// characters. Returns entries sorted by match score (best first).
+    pub fn fuzzy_find_with_options(
+        &self,
+        pattern: &str,
+        min_score: Option<i32>,
+        suffix_search: bool,
+    ) -> Vec<(&str, &ConEntry, MatchResult)> {
+        let description_pattern = (suffix_search && pattern.len() >= 3).then(|| pattern.to_lowercase());
         let mut matches: Vec<_> = self
             .entries
             .iter()
             .filter(|(_, entry)| !entry.flags().contains(ConVarFlags::HIDDEN))
             .filter_map(|(name, entry)| {
-                subsequence_match(pattern, name).map(|result| (name.as_ref(), entry, result))
+                let subsequence = subsequence_match(pattern, name);
+                let suffix = suffix_search.then(|| suffix_match(pattern, name)).flatten();
+                let result = match (subsequence, suffix) {
+                    (Some(a), Some(b)) => Some(if b.score > a.score { b } else { a }),
+                    (a, b) => a.or(b),
+                };
+                let result = result.or_else(|| {
+                    let pattern = description_pattern.as_ref()?;
+                    entry
+                        .description()
+                        .to_lowercase()
+                        .contains(pattern.as_str())
+                        .then(|| MatchResult::new(DESCRIPTION_MATCH_SCORE, Vec::new()))
+                })?;
+                Some((name.as_ref(), entry, result))
             })
+            .filter(|(_, _, result)| min_score.is_none_or(|min| result.score >= min))
             .collect();
 
         matches.sort_by(|a, b| b.2.score.cmp(&a.2.score).then_with(|| a.0.cmp(b.0)));
         matches
     }
 
+    /// Move all entries from `other` into this registry.
+    ///
+    /// Entries whose name is already registered are skipped with a warning,
+    /// keeping the existing entry. Returns the number of entries merged.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut plugin_registry = ConsoleRegistry::new();
+    /// plugin_registry.register_var(ConVar::new("ai_debug", false));
+    /// registry.merge_from(plugin_registry);
+    /// ```
+    pub fn merge_from(&mut self, mut other: ConsoleRegistry) -> usize {
+        let names: Vec<Box<str>> = other.trie.keys().map(Into::into).collect();
+        let mut merged = 0;
+        for name in names {
+            let Some(entry) = other.entries.remove(&name) else {
+                continue;
+            };
+            if self.entries.contains_key(&name) {
+                bevy::log::warn!("Console: Not merging '{}', an entry with that name already exists", name);
+                continue;
+            }
+            if self.insert_entry(name, entry).is_some() {
+                merged += 1;
+            }
+        }
+        merged
+    }
+
     /// Find entries by searching both name and description.
     pub fn search(&self, query: &str) -> Vec<(&str, &ConEntry)> {
         let query_lower = query.to_lowercase();
@@ -448,6 +826,37 @@ mod tests {
         assert_eq!(registry.get::<f32>("sv_gravity"), Some(500.0));
     }
 
+    #[test]
+    fn test_registry_get_var() {
+        let mut registry = ConsoleRegistry::new();
+
+        registry.register_var(ConVar::new("cl_fov", 90i32).min(60).max(120));
+        let _ = registry.register_cmd(ConCommand::new("test", |_, _| {}));
+
+        assert_eq!(registry.get_var::<i32>("cl_fov").map(|v| v.has_bounds()), Some(true));
+        assert!(registry.get_var::<f32>("cl_fov").is_none());
+        assert!(registry.get_var::<i32>("test").is_none());
+
+        if let Some(cvar) = registry.get_var_mut::<i32>("cl_fov") {
+            cvar.set(150);
+        }
+        assert_eq!(registry.get::<i32>("cl_fov"), Some(120));
+    }
+
+    #[test]
+    fn test_registry_entry_type_name() {
+        let mut registry = ConsoleRegistry::new();
+
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        registry.register_var(ConVar::new("sv_hostname", String::from("server")));
+        let _ = registry.register_cmd(ConCommand::new("test", |_, _| {}));
+
+        assert_eq!(registry.entry_type_name("sv_gravity"), Some("f32"));
+        assert_eq!(registry.entry_type_name("sv_hostname"), Some(std::any::type_name::<String>()));
+        assert_eq!(registry.entry_type_name("test"), Some("command"));
+        assert_eq!(registry.entry_type_name("unknown"), None);
+    }
+
     #[test]
     fn test_registry_cmd() {
         let mut registry = ConsoleRegistry::new();
@@ -476,6 +885,29 @@ mod tests {
         assert_eq!(cl_entries.len(), 1);
     }
 
+    #[test]
+    fn test_registry_glob_iter() {
+        let mut registry = ConsoleRegistry::new();
+
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        registry.register_var(ConVar::new("sv_maxspeed", 320.0f32));
+        registry.register_var(ConVar::new("cl_gravity_scale", 1.0f32));
+        registry.register_var(ConVar::new("cl_fov", 90i32));
+
+        let names = |pattern| {
+            let mut names: Vec<_> = registry.glob_iter(pattern).map(|(name, _)| name).collect();
+            names.sort_unstable();
+            names
+        };
+
+        assert_eq!(names("sv_*"), vec!["sv_gravity", "sv_maxspeed"]);
+        assert_eq!(names("*gravity*"), vec!["cl_gravity_scale", "sv_gravity"]);
+        assert_eq!(names("*_*a*e*"), vec!["cl_gravity_scale", "sv_maxspeed"]);
+        assert_eq!(names("??_fov"), vec!["cl_fov"]);
+        assert_eq!(names("cl_fov"), vec!["cl_fov"]);
+        assert!(names("*missing*").is_empty());
+    }
+
     #[test]
     fn test_registry_fuzzy_find() {
         let mut registry = ConsoleRegistry::new();
@@ -490,6 +922,40 @@ mod tests {
         assert!(matches.iter().any(|(name, _, _)| *name == "sv_gravity"));
     }
 
+    #[test]
+    fn test_registry_fuzzy_find_threshold() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("xaxbxcxd", 0i32));
+
+        // Four scattered matches without bonuses score 4
+        assert_eq!(registry.fuzzy_find("abcd")[0].2.score, 4);
+        assert_eq!(registry.fuzzy_find_with_threshold("abcd", Some(4)).len(), 1);
+        assert!(registry.fuzzy_find_with_threshold("abcd", Some(5)).is_empty());
+        assert_eq!(registry.fuzzy_find_with_threshold("xax", Some(5)).len(), 1);
+    }
+
+    #[test]
+    fn test_registry_fuzzy_find_suffix_and_description() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        registry.register_var(ConVar::new("gravity_scale", 1.0f32));
+        registry.register_var(ConVar::new("sv_maxspeed", 320.0f32).description("Player run speed"));
+
+        let matches = registry.fuzzy_find_with_options("gravity", None, true);
+        let names: Vec<_> = matches.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, ["gravity_scale", "sv_gravity"]);
+        assert_eq!(matches[1].2.indices, (3..10).collect::<Vec<_>>());
+
+        let matches = registry.fuzzy_find_with_options("RUN", None, true);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, "sv_maxspeed");
+        assert!(matches[0].2.indices.is_empty());
+
+        // Plain fuzzy_find only matches subsequences of names
+        assert!(registry.fuzzy_find("run").is_empty());
+        assert_eq!(registry.fuzzy_find("gravity")[1].2.indices, [3, 4, 5, 6, 7, 8, 9]);
+    }
+
     #[test]
     fn test_registry_search() {
         let mut registry = ConsoleRegistry::new();
@@ -523,6 +989,26 @@ mod tests {
         assert_eq!(registry.modified_vars().count(), 1);
     }
 
+    #[test]
+    fn test_registry_modified_vars_by_recency() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("a_first", 0i32));
+        registry.register_var(ConVar::new("b_second", 0i32));
+        registry.register_var(ConVar::new("c_untracked", 0i32));
+
+        registry.get_var_mut::<i32>("c_untracked").unwrap().set(1);
+        registry.set("b_second", 1i32);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        registry.set_string("a_first", "1");
+
+        let names: Vec<_> = registry.modified_vars_by_recency().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["a_first", "b_second", "c_untracked"]);
+        assert!(registry.get_entry("c_untracked").is_some_and(|e| match e {
+            ConEntry::Var(meta) => meta.last_modified_at.is_none(),
+            ConEntry::Cmd(_) => false,
+        }));
+    }
+
     #[test]
     fn test_duplicate_detection() {
         let mut registry = ConsoleRegistry::new();
@@ -546,4 +1032,129 @@ mod tests {
         let (_, _, _, is_new) = registry.register_cmd(ConCommand::new("test_cmd", |_, _| {}));
         assert!(!is_new);
     }
+
+    #[test]
+    fn test_register_hooks() {
+        use std::sync::{Arc, Mutex};
+
+        let mut registry = ConsoleRegistry::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        registry.on_before_register(|name, _| !name.starts_with("mod_"));
+        registry.on_before_register(|_, entry| !entry.flags().contains(ConVarFlags::CHEAT));
+        let after_log = log.clone();
+        registry.on_after_register(move |name, _| after_log.lock().unwrap().push(name.to_string()));
+
+        assert!(registry.register_var(ConVar::new("sv_gravity", 800i32)));
+        assert!(!registry.register_var(ConVar::new("mod_speed", 2i32)));
+        assert!(!registry.register_var(ConVar::new("sv_wallhack", false).flags(ConVarFlags::CHEAT)));
+        assert!(registry.try_register_cmd(ConCommand::new("mod_reload", |_, _| {})).is_none());
+        assert!(registry.try_register_cmd(ConCommand::new("kick", |_, _| {})).is_some());
+        let (_, _, _, is_new) = registry.register_cmd(ConCommand::new("mod_debug", |_, _| {}));
+        assert!(!is_new);
+        assert!(!registry.contains("mod_debug"));
+
+        assert!(!registry.contains("mod_speed"));
+        assert!(!registry.contains("sv_wallhack"));
+        assert!(!registry.contains("mod_reload"));
+        assert_eq!(registry.prefix_iter("mod_").count(), 0);
+        assert_eq!(*log.lock().unwrap(), vec!["sv_gravity", "kick"]);
+
+        // Merged entries go through the same hooks
+        let mut other = ConsoleRegistry::new();
+        other.register_var(ConVar::new("mod_fov", 90i32));
+        other.register_var(ConVar::new("ai_debug", false));
+        assert_eq!(registry.merge_from(other), 1);
+        assert!(registry.contains("ai_debug"));
+    }
+
+    #[test]
+    fn test_namespace_prefix() {
+        let mut registry = ConsoleRegistry::new();
+        registry.require_namespace_prefix("physics_");
+        registry.require_namespace_prefix("net_");
+
+        // Warn mode still registers
+        assert!(registry.register_var(ConVar::new("gravity", 800i32)));
+        assert!(registry.contains("gravity"));
+
+        registry.set_namespace_policy(NamespacePolicy::Reject);
+        assert!(registry.register_var(ConVar::new("physics_substeps", 4i32)));
+        assert!(registry.register_var(ConVar::new("net_rate", 60i32)));
+        assert!(!registry.register_var(ConVar::new("friction", 4i32)));
+        assert!(registry.try_register_cmd(ConCommand::new("reload", |_, _| {})).is_none());
+        assert!(!registry.contains("friction"));
+        assert!(!registry.contains("reload"));
+
+        registry.clear_namespace_requirements();
+        assert!(registry.register_var(ConVar::new("friction", 4i32)));
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let mut registry = ConsoleRegistry::new();
+        let mut handlers = CommandHandlers::new();
+        registry.register_var(ConVar::new("sv_gravity", 800i32));
+
+        let mut other = ConsoleRegistry::new();
+        let mut other_handlers = CommandHandlers::new();
+        other.register_var(ConVar::new("sv_gravity", 1i32));
+        other.register_var(ConVar::new("ai_debug", false));
+        let (name, handler, autocomplete, _) = other.register_cmd(
+            ConCommand::new("ai_reset", |_, _| {}).autocomplete(|_| vec!["all".into()]),
+        );
+        other_handlers.register(name, handler, autocomplete);
+
+        assert_eq!(registry.merge_from(other), 2);
+        assert_eq!(registry.get::<i32>("sv_gravity"), Some(800));
+        assert_eq!(registry.get::<bool>("ai_debug"), Some(false));
+        assert_eq!(registry.prefix_iter("ai_").count(), 2);
+
+        assert_eq!(handlers.merge_from(other_handlers), 1);
+        assert!(handlers.get("ai_reset").is_some());
+        assert_eq!(handlers.get_completions("ai_reset", ""), vec!["all"]);
+    }
+
+    #[test]
+    fn test_case_insensitive_fallback() {
+        let mut registry = ConsoleRegistry::new();
+
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+
+        assert_eq!(registry.get::<f32>("SV_GRAVITY"), Some(800.0));
+        assert_eq!(registry.get_string("Sv_Gravity"), Some("800".to_string()));
+        assert_eq!(registry.get_entry("SV_GRAVITY").map(|e| e.name()), Some("sv_gravity"));
+        assert!(registry.get_entry("SV_GRAV").is_none());
+
+        registry.get_var_mut::<f32>("SV_GRAVITY").unwrap().set(400.0);
+        assert_eq!(registry.get::<f32>("sv_gravity"), Some(400.0));
+        assert!(registry.get_entry_mut("Sv_Gravity").is_some());
+        assert!(registry.get_var_mut::<f32>("SV_GRAV").is_none());
+
+        assert!(registry.set("SV_GRAVITY", 600.0f32));
+        assert_eq!(registry.get::<f32>("sv_gravity"), Some(600.0));
+        assert!(registry.set_string("Sv_Gravity", "500"));
+        assert_eq!(registry.get::<f32>("sv_gravity"), Some(500.0));
+        assert!(!registry.set("SV_GRAV", 1.0f32));
+
+        // Existence checks stay exact
+        assert!(!registry.contains("SV_GRAVITY"));
+    }
+
+    #[test]
+    fn test_description_localized() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32)
+            .description("World gravity")
+            .description_key("cvar.sv_gravity"));
+        registry.register_var(ConVar::new("sv_cheats", false)
+            .description_key("cvar.sv_cheats"));
+
+        let mut localization = ConsoleLocalization::default();
+        let gravity = registry.get_entry("sv_gravity").unwrap();
+        assert_eq!(gravity.description_localized(&localization), "World gravity");
+
+        localization.insert("cvar.sv_gravity", "Schwerkraft");
+        assert_eq!(gravity.description_localized(&localization), "Schwerkraft");
+        assert_eq!(registry.get_entry("sv_cheats").unwrap().description_localized(&localization), "");
+    }
 }
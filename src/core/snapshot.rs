@@ -0,0 +1,99 @@
+//! Shared string snapshot of ConVar values for use off the main thread.
+//!
+//! Physics or audio threads hold a clone of [`ConVarSnapshot`] and read values
+//! without access to the Bevy `World`. Values are stored as strings, so
+//! readers parse them into whatever type they expect.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::*;
+
+use super::ConsoleRegistry;
+
+/// Thread-safe copy of every ConVar's current value.
+///
+/// Clones share the same storage. Insert one as a resource and the console
+/// plugin refreshes it after commands execute each frame.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn setup(mut commands: Commands, registry: Res<ConsoleRegistry>) {
+///     let snapshot = registry.make_snapshot();
+///     commands.insert_resource(snapshot.clone());
+///
+///     std::thread::spawn(move || loop {
+///         let gravity: f32 = snapshot.get("sv_gravity").and_then(|v| v.parse().ok()).unwrap_or(800.0);
+///     });
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConVarSnapshot {
+    values: Arc<RwLock<HashMap<Box<str>, String>>>,
+}
+
+impl ConVarSnapshot {
+    /// Get the value of a ConVar as of the last refresh.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.values.read().unwrap().get(name).cloned()
+    }
+
+    /// Number of ConVars in the snapshot.
+    pub fn len(&self) -> usize {
+        self.values.read().unwrap().len()
+    }
+
+    /// Check if the snapshot is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.read().unwrap().is_empty()
+    }
+
+    /// Replace the stored values with the registry's current values.
+    pub fn refresh(&self, registry: &ConsoleRegistry) {
+        // Format outside the lock so readers are blocked as briefly as possible
+        let values: HashMap<_, _> = registry
+            .vars()
+            .map(|(name, meta)| (name.into(), meta.get_string()))
+            .collect();
+        *self.values.write().unwrap() = values;
+    }
+}
+
+impl ConsoleRegistry {
+    /// Copy all ConVar values into a new [`ConVarSnapshot`].
+    pub fn make_snapshot(&self) -> ConVarSnapshot {
+        let snapshot = ConVarSnapshot::default();
+        snapshot.refresh(self);
+        snapshot
+    }
+}
+
+/// System that refreshes the [`ConVarSnapshot`] resource when the registry changed.
+pub(crate) fn update_convar_snapshot(registry: Res<ConsoleRegistry>, snapshot: Res<ConVarSnapshot>) {
+    if registry.is_changed() || snapshot.is_added() {
+        snapshot.refresh(&registry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConVar;
+
+    #[test]
+    fn test_snapshot_shared_between_clones() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+
+        let snapshot = registry.make_snapshot();
+        let reader = snapshot.clone();
+        assert_eq!(reader.get("sv_gravity").and_then(|v| v.parse::<f32>().ok()), Some(800.0));
+        assert_eq!(reader.get("missing"), None);
+
+        registry.set("sv_gravity", 400.0f32);
+        snapshot.refresh(&registry);
+        let handle = std::thread::spawn(move || reader.get("sv_gravity"));
+        assert_eq!(handle.join().unwrap().as_deref(), Some("400"));
+    }
+}
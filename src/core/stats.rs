@@ -0,0 +1,143 @@
+//! Command execution statistics for profiling.
+//!
+//! Counters are updated by the command pipeline and printed by the `stats` command.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+
+/// Resource tracking how often commands run and how long they take.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CommandStats {
+    /// Total command handler invocations.
+    pub total_executed: u64,
+    /// Total failed commands (unknown, denied, parse errors, panics).
+    pub total_errors: u64,
+    /// Failures caused by permission level or `sv_cheats`.
+    pub total_permission_denials: u64,
+    /// Frames where [`CommandBudget`] cut execution short.
+    pub budget_exceeded_count: u64,
+    /// Invocation count per command.
+    pub counts: HashMap<Box<str>, u64>,
+    /// Cumulative handler time per command.
+    pub durations: HashMap<Box<str>, Duration>,
+}
+
+impl CommandStats {
+    /// Record one handler invocation and how long it took.
+    pub fn record(&mut self, name: &str, elapsed: Duration) {
+        self.total_executed += 1;
+        // Look up first so the name is only allocated for new commands
+        if let Some(count) = self.counts.get_mut(name) {
+            *count += 1;
+        } else {
+            self.counts.insert(name.into(), 1);
+        }
+        if let Some(total) = self.durations.get_mut(name) {
+            *total += elapsed;
+        } else {
+            self.durations.insert(name.into(), elapsed);
+        }
+    }
+
+    /// Commands sorted by invocation count, most called first.
+    pub fn most_called(&self, limit: usize) -> Vec<(&str, u64)> {
+        let mut entries: Vec<_> = self.counts.iter().map(|(name, n)| (&**name, *n)).collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Commands sorted by cumulative handler time, slowest first.
+    pub fn slowest(&self, limit: usize) -> Vec<(&str, Duration)> {
+        let mut entries: Vec<_> = self.durations.iter().map(|(name, d)| (&**name, *d)).collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Clear all counters.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Minimum time between "budget exceeded" warnings.
+const BUDGET_WARNING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Resource limiting how long command execution may take each frame.
+///
+/// Commands left over when the budget runs out execute on the next frame.
+/// At least one command runs every frame, however small the budget.
+#[derive(Resource, Debug, Clone)]
+pub struct CommandBudget {
+    /// Time after which no further commands start this frame.
+    pub max_frame_time: Duration,
+    last_warning: Option<Instant>,
+}
+
+impl Default for CommandBudget {
+    fn default() -> Self {
+        Self {
+            max_frame_time: Duration::from_millis(2),
+            last_warning: None,
+        }
+    }
+}
+
+impl CommandBudget {
+    /// Create a budget with the given per-frame time.
+    pub fn new(max_frame_time: Duration) -> Self {
+        Self {
+            max_frame_time,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a warning may be logged now, rate-limited to one per second.
+    pub(crate) fn should_warn(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_warning
+            .is_none_or(|last| now.duration_since(last) >= BUDGET_WARNING_INTERVAL);
+        if due {
+            self.last_warning = Some(now);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_record_and_rank() {
+        let mut stats = CommandStats::default();
+        stats.record("echo", Duration::from_millis(1));
+        stats.record("echo", Duration::from_millis(1));
+        stats.record("exec", Duration::from_millis(5));
+
+        assert_eq!(stats.total_executed, 3);
+        assert_eq!(stats.most_called(10), vec![("echo", 2), ("exec", 1)]);
+        assert_eq!(stats.most_called(1), vec![("echo", 2)]);
+        assert_eq!(stats.slowest(10), vec![
+            ("exec", Duration::from_millis(5)),
+            ("echo", Duration::from_millis(2)),
+        ]);
+
+        stats.reset();
+        assert_eq!(stats.budget_exceeded_count, 0);
+        assert_eq!(stats.total_executed, 0);
+        assert!(stats.counts.is_empty());
+    }
+
+    #[test]
+    fn test_budget_warning_rate_limit() {
+        let mut budget = CommandBudget::default();
+        let start = Instant::now();
+        assert!(budget.should_warn(start));
+        assert!(!budget.should_warn(start + Duration::from_millis(500)));
+        assert!(budget.should_warn(start + Duration::from_millis(1000)));
+    }
+}
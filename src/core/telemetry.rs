@@ -0,0 +1,49 @@
+//! Opt-in hook for command usage analytics.
+//!
+//! The command pipeline reports each command invocation to
+//! [`ConsoleTelemetry::on_command_executed`] when a callback is set.
+
+use bevy::prelude::*;
+
+use super::{CommandExecutionEvent, CommandOutcome, CommandSource};
+
+/// Summary of one command invocation passed to the telemetry callback.
+///
+/// Argument values are left out, since they may contain player data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandTelemetryEvent {
+    /// The command name as registered.
+    pub name: Box<str>,
+    /// Number of arguments passed.
+    pub arg_count: usize,
+    /// Where the command came from.
+    pub source: CommandSource,
+    /// Whether the handler ran to completion.
+    pub success: bool,
+}
+
+impl From<&CommandExecutionEvent> for CommandTelemetryEvent {
+    fn from(event: &CommandExecutionEvent) -> Self {
+        Self {
+            name: event.name.clone(),
+            arg_count: event.args.len(),
+            source: event.source.clone(),
+            success: event.outcome == CommandOutcome::Success,
+        }
+    }
+}
+
+/// Resource holding the telemetry callback. Telemetry is off by default.
+///
+/// # Examples
+///
+/// ```ignore
+/// app.insert_resource(ConsoleTelemetry {
+///     on_command_executed: Some(Box::new(|event| send_to_server(event))),
+/// });
+/// ```
+#[derive(Resource, Default)]
+pub struct ConsoleTelemetry {
+    /// Called once for every executed command, in execution order.
+    pub on_command_executed: Option<Box<dyn Fn(CommandTelemetryEvent) + Send + Sync>>,
+}
@@ -15,25 +15,66 @@ pub struct TokenizedCommand<'a> {
 }
 
 /// Tokenize error types.
+///
+/// Errors at a position carry a `context` of the input around it, with a `^`
+/// marker below the offending character:
+///
+/// ```text
+/// echo "hello
+///      ^
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenizeError {
     /// Empty input string.
     EmptyInput,
     /// Unterminated quoted string.
-    UnterminatedString { position: usize },
+    UnterminatedString {
+        /// Byte offset of the opening quote.
+        position: usize,
+        /// The input around the opening quote and a marker line below it.
+        context: String,
+    },
+}
+
+impl TokenizeError {
+    fn unterminated_string(input: &str, position: usize) -> Self {
+        TokenizeError::UnterminatedString {
+            position,
+            context: error_context(input, position),
+        }
+    }
 }
 
 impl std::fmt::Display for TokenizeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TokenizeError::EmptyInput => write!(f, "empty input"),
-            TokenizeError::UnterminatedString { position } => {
-                write!(f, "unterminated string at position {}", position)
+            TokenizeError::UnterminatedString { position, context } => {
+                write!(f, "unterminated string at position {}\n{}", position, context)
             }
         }
     }
 }
 
+/// Characters of context shown on each side of an error position.
+const ERROR_CONTEXT_CHARS: usize = 10;
+
+/// Up to [`ERROR_CONTEXT_CHARS`] characters on each side of the byte offset
+/// `position`, with a second line marking the character at `position` with `^`.
+fn error_context(input: &str, position: usize) -> String {
+    let before: Vec<char> = input[..position].chars().rev().take(ERROR_CONTEXT_CHARS).collect();
+    let after = input[position..].chars().take(ERROR_CONTEXT_CHARS + 1);
+    // Whitespace like tabs and newlines would throw off the marker's column
+    let snippet: String = before
+        .iter()
+        .rev()
+        .copied()
+        .chain(after)
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    format!("{}\n{}^", snippet.trim_end(), " ".repeat(before.len()))
+}
+
 impl std::error::Error for TokenizeError {}
 
 /// Tokenize a command string into command name and arguments.
@@ -48,7 +89,7 @@ impl std::error::Error for TokenizeError {}
 /// # Examples
 ///
 /// ```
-/// use bevy_console::core::tokenize;
+/// use bevy_console_two::core::tokenize;
 ///
 /// // Simple command
 /// let result = tokenize("echo hello world").unwrap();
@@ -132,7 +173,7 @@ pub fn tokenize_string(input: &str) -> Result<Vec<&str>, TokenizeError> {
                 }
 
                 if !found_end {
-                    return Err(TokenizeError::UnterminatedString { position: start });
+                    return Err(TokenizeError::unterminated_string(input, start));
                 }
 
                 tokens.push(&input[content_start..end]);
@@ -163,7 +204,7 @@ pub fn tokenize_string(input: &str) -> Result<Vec<&str>, TokenizeError> {
                 }
 
                 if !found_end {
-                    return Err(TokenizeError::UnterminatedString { position: start });
+                    return Err(TokenizeError::unterminated_string(input, start));
                 }
 
                 tokens.push(&input[content_start..end]);
@@ -198,7 +239,7 @@ pub fn tokenize_string(input: &str) -> Result<Vec<&str>, TokenizeError> {
 /// # Examples
 ///
 /// ```
-/// use bevy_console::core::split_commands;
+/// use bevy_console_two::core::split_commands;
 ///
 /// let commands = split_commands("sv_cheats 1; noclip; god");
 /// assert_eq!(commands, vec!["sv_cheats 1", "noclip", "god"]);
@@ -220,16 +261,12 @@ pub fn split_commands(input: &str) -> Vec<&str> {
                 backslash_count += 1;
                 continue;
             }
-            '"' if !in_single_quote => {
-                // Quote is escaped only if preceded by odd number of backslashes
-                if backslash_count % 2 == 0 {
-                    in_double_quote = !in_double_quote;
-                }
+            // Quote is escaped only if preceded by odd number of backslashes
+            '"' if !in_single_quote && backslash_count % 2 == 0 => {
+                in_double_quote = !in_double_quote;
             }
-            '\'' if !in_double_quote => {
-                if backslash_count % 2 == 0 {
-                    in_single_quote = !in_single_quote;
-                }
+            '\'' if !in_double_quote && backslash_count % 2 == 0 => {
+                in_single_quote = !in_single_quote;
             }
             ';' if !in_double_quote && !in_single_quote => {
                 let cmd = input[start..i].trim();
@@ -252,6 +289,40 @@ pub fn split_commands(input: &str) -> Vec<&str> {
     commands
 }
 
+/// Replace `${name}` references in `input` with the result of `lookup(name)`.
+///
+/// References for which `lookup` returns `None` are left as written, as is an
+/// unterminated `${`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let text = expand_vars("Hello ${sv_gravity}!", |name| (name == "sv_gravity").then(|| "800".to_string()));
+/// assert_eq!(text, "Hello 800!");
+/// ```
+pub fn expand_vars(input: &str, mut lookup: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let reference = &rest[start..start + 3 + len];
+
+        out.push_str(&rest[..start]);
+        match lookup(name) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(reference),
+        }
+        rest = &rest[start + reference.len()..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,6 +374,21 @@ mod tests {
         assert!(matches!(result, Err(TokenizeError::UnterminatedString { .. })));
     }
 
+    #[test]
+    fn test_tokenize_error_context() {
+        let error = tokenize(r#"echo "hello"#).unwrap_err();
+        assert_eq!(error.to_string(), "unterminated string at position 5\necho \"hello\n     ^");
+
+        // Long input is cut to 10 characters on each side
+        let Err(TokenizeError::UnterminatedString { position, context }) =
+            tokenize("alias jump_and_duck \"+jump; +duck; wait; -jump")
+        else {
+            panic!("expected an unterminated string");
+        };
+        assert_eq!(position, 20);
+        assert_eq!(context, "_and_duck \"+jump; +du\n          ^");
+    }
+
     #[test]
     fn test_tokenize_comment() {
         let result = tokenize("echo hello // this is a comment").unwrap();
@@ -406,4 +492,20 @@ mod tests {
         let commands = split_commands(r#"echo "test\\\"inside"; quit"#);
         assert_eq!(commands, vec![r#"echo "test\\\"inside""#, "quit"]);
     }
+
+    #[test]
+    fn test_expand_vars() {
+        let lookup = |name: &str| match name {
+            "sv_gravity" => Some("800".to_string()),
+            "name" => Some("${sv_gravity}".to_string()),
+            _ => None,
+        };
+        assert_eq!(expand_vars("Hello ${sv_gravity}!", lookup), "Hello 800!");
+        assert_eq!(expand_vars("${sv_gravity}${sv_gravity}", lookup), "800800");
+        assert_eq!(expand_vars("${unknown} stays", lookup), "${unknown} stays");
+        // Substituted values are not expanded again
+        assert_eq!(expand_vars("${name}", lookup), "${sv_gravity}");
+        assert_eq!(expand_vars("open ${sv_gravity", lookup), "open ${sv_gravity");
+        assert_eq!(expand_vars("$5 and {braces}", lookup), "$5 and {braces}");
+    }
 }
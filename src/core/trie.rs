@@ -15,7 +15,7 @@ use std::collections::HashMap;
 /// # Examples
 ///
 /// ```
-/// use bevy_console::core::Trie;
+/// use bevy_console_two::core::Trie;
 ///
 /// let mut trie = Trie::new();
 /// trie.insert("sv_gravity", 800);
@@ -52,6 +52,16 @@ impl<V> Default for TrieNode<V> {
     }
 }
 
+impl<V> TrieNode<V> {
+    /// Get the stored key and value, if this node holds an entry.
+    fn entry(&self) -> Option<(&str, &V)> {
+        match (&self.key, &self.value) {
+            (Some(key), Some(value)) => Some((key, value)),
+            _ => None,
+        }
+    }
+}
+
 impl<V> Default for Trie<V> {
     fn default() -> Self {
         Self::new()
@@ -111,6 +121,19 @@ impl<V> Trie<V> {
         node.value.as_ref()
     }
 
+    /// Get the entry for the given key, ignoring ASCII case.
+    ///
+    /// Returns the stored key alongside the value so callers can report the
+    /// canonical spelling. If several keys differ only by case, an exact match
+    /// is preferred; otherwise any one of them is returned.
+    pub fn get_ci(&self, key: &str) -> Option<(&str, &V)> {
+        if let Some(entry) = self.node_for(key).and_then(TrieNode::entry) {
+            return Some(entry);
+        }
+
+        self.nodes_for_ci(key).into_iter().find_map(TrieNode::entry)
+    }
+
     /// Get a mutable reference to the value for the given key.
     pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
         let mut node = &mut self.root;
@@ -162,10 +185,14 @@ impl<V> Trie<V> {
             }
         }
 
-        let mut stack = Vec::new();
-        stack.push(node);
+        PrefixIter { stack: vec![node] }
+    }
 
-        PrefixIter { stack }
+    /// Iterate over all key-value pairs with the given prefix, ignoring ASCII case.
+    pub fn prefix_iter_ci(&self, prefix: &str) -> PrefixIter<'_, V> {
+        PrefixIter {
+            stack: self.nodes_for_ci(prefix),
+        }
     }
 
     /// Iterate over all key-value pairs.
@@ -188,6 +215,84 @@ impl<V> Trie<V> {
         self.root = TrieNode::default();
         self.len = 0;
     }
+
+    /// Insert every entry from `other` whose key isn't in `self` yet.
+    ///
+    /// Existing entries are kept. Returns the number of entries inserted.
+    pub fn merge_from(&mut self, other: Trie<V>) -> usize {
+        let mut inserted = 0;
+        for (key, value) in other.into_entries() {
+            if !self.contains(&key) {
+                self.insert(&key, value);
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// Insert every entry from `other`, replacing existing values.
+    ///
+    /// Returns the number of keys that were new to `self`.
+    pub fn merge_override(&mut self, other: Trie<V>) -> usize {
+        let mut inserted = 0;
+        for (key, value) in other.into_entries() {
+            if self.insert(&key, value).is_none() {
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// Take all entries out of the trie, in no particular order.
+    fn into_entries(self) -> Vec<(Box<str>, V)> {
+        let mut entries = Vec::with_capacity(self.len);
+        let mut stack = vec![self.root];
+        while let Some(node) = stack.pop() {
+            stack.extend(node.children.into_values());
+            if let (Some(key), Some(value)) = (node.key, node.value) {
+                entries.push((key, value));
+            }
+        }
+        entries
+    }
+
+    /// Walk to the node for an exact key.
+    fn node_for(&self, key: &str) -> Option<&TrieNode<V>> {
+        let mut node = &self.root;
+
+        for &byte in key.as_bytes() {
+            node = node.children.get(&byte)?;
+        }
+
+        Some(node)
+    }
+
+    /// Collect every node reachable by a case-insensitive walk of `key`.
+    ///
+    /// Each ASCII letter may branch into both its lower and upper case child.
+    fn nodes_for_ci(&self, key: &str) -> Vec<&TrieNode<V>> {
+        let mut nodes = vec![&self.root];
+
+        for &byte in key.as_bytes() {
+            let lower = byte.to_ascii_lowercase();
+            let upper = byte.to_ascii_uppercase();
+
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| {
+                    let first = node.children.get(&lower);
+                    let second = if upper != lower { node.children.get(&upper) } else { None };
+                    first.into_iter().chain(second)
+                })
+                .collect();
+
+            if nodes.is_empty() {
+                break;
+            }
+        }
+
+        nodes
+    }
 }
 
 /// Iterator over entries with a common prefix.
@@ -206,8 +311,8 @@ impl<'a, V> Iterator for PrefixIter<'a, V> {
             }
 
             // If this node has a value, return it
-            if let (Some(key), Some(value)) = (&node.key, &node.value) {
-                return Some((key, value));
+            if let Some(entry) = node.entry() {
+                return Some(entry);
             }
         }
 
@@ -215,6 +320,30 @@ impl<'a, V> Iterator for PrefixIter<'a, V> {
     }
 }
 
+/// Serialized as a flat list of `(key, value)` pairs, sorted by key so the
+/// output is stable.
+#[cfg(feature = "persist")]
+impl<V: serde::Serialize> serde::Serialize for Trie<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        serializer.collect_seq(entries)
+    }
+}
+
+/// Rebuilds the tree from the flat `(key, value)` list written by `Serialize`.
+#[cfg(feature = "persist")]
+impl<'de, V: serde::Deserialize<'de>> serde::Deserialize<'de> for Trie<V> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = Vec::<(String, V)>::deserialize(deserializer)?;
+        let mut trie = Trie::new();
+        for (key, value) in entries {
+            trie.insert(&key, value);
+        }
+        Ok(trie)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +445,81 @@ mod tests {
         let prefix_entries: Vec<_> = trie.prefix_iter("test").collect();
         assert_eq!(prefix_entries.len(), 3);
     }
+
+    #[test]
+    fn test_trie_case_insensitive() {
+        let mut trie = Trie::new();
+        trie.insert("sv_gravity", 800);
+        trie.insert("sv_cheats", 0);
+        trie.insert("cl_fov", 90);
+
+        // Exact lookup stays case-sensitive
+        assert_eq!(trie.get("SV_GRAVITY"), None);
+
+        assert_eq!(trie.get_ci("SV_GRAVITY"), Some(("sv_gravity", &800)));
+        assert_eq!(trie.get_ci("Sv_Cheats"), Some(("sv_cheats", &0)));
+        assert_eq!(trie.get_ci("sv_gravit"), None);
+        assert_eq!(trie.get_ci("unknown"), None);
+
+        let sv_entries: Vec<_> = trie.prefix_iter_ci("SV_").collect();
+        assert_eq!(sv_entries.len(), 2);
+        assert!(trie.prefix_iter_ci("XYZ").next().is_none());
+    }
+
+    #[test]
+    fn test_trie_case_insensitive_prefers_exact() {
+        let mut trie = Trie::new();
+        trie.insert("Name", 1);
+        trie.insert("name", 2);
+
+        assert_eq!(trie.get_ci("name"), Some(("name", &2)));
+        assert_eq!(trie.get_ci("Name"), Some(("Name", &1)));
+        assert_eq!(trie.prefix_iter_ci("NA").count(), 2);
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_trie_serde_roundtrip() {
+        let mut trie = Trie::new();
+        trie.insert("sv_gravity", 800);
+        trie.insert("sv_cheats", 0);
+        trie.insert("cl_fov", 90);
+
+        let json = serde_json::to_string(&trie).unwrap();
+        assert_eq!(json, r#"[["cl_fov",90],["sv_cheats",0],["sv_gravity",800]]"#);
+
+        let restored: Trie<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored.get("sv_cheats"), Some(&0));
+        assert_eq!(restored.prefix_iter("sv_").count(), 2);
+    }
+
+    #[test]
+    fn test_trie_merge() {
+        let base = || {
+            let mut trie = Trie::new();
+            trie.insert("sv_gravity", 800);
+            trie.insert("sv_cheats", 0);
+            trie
+        };
+        let other = || {
+            let mut trie = Trie::new();
+            trie.insert("sv_cheats", 1);
+            trie.insert("ai_debug", 1);
+            trie.insert("ai_disable", 0);
+            trie
+        };
+
+        let mut trie = base();
+        assert_eq!(trie.merge_from(other()), 2);
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.get("sv_cheats"), Some(&0));
+        assert_eq!(trie.get("ai_debug"), Some(&1));
+
+        let mut trie = base();
+        assert_eq!(trie.merge_override(other()), 2);
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.get("sv_cheats"), Some(&1));
+        assert_eq!(trie.prefix_iter("ai_").count(), 2);
+    }
 }
@@ -0,0 +1,256 @@
+//! Live ConVar editing through `bevy_inspector_egui`.
+//!
+//! [`ConsoleInspectorPlugin`] adds an inspector window listing every visible
+//! ConVar with a widget for its type, plus a reflection-based inspector for
+//! [`ConsolePermissions`]. ConVar edits are queued as assignments in the
+//! command pipeline, so they go through the same permission checks and
+//! constraints as typed commands.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiPrimaryContextPass, PrimaryEguiContext};
+use bevy_inspector_egui::{bevy_inspector, DefaultInspectorConfigPlugin};
+
+use crate::core::{
+    CommandSource, ConVarFlags, ConVarMeta, ConsolePermissions, ConsoleRegistry, PermissionLevel,
+};
+use crate::ui::NumberKind;
+use crate::{PendingCommands, QueuedCommand};
+
+/// Plugin that shows ConVars and [`ConsolePermissions`] in inspector windows.
+///
+/// Requires [`ConsolePlugin`](crate::ConsolePlugin) and `EguiPlugin`. The
+/// windows are separate from the console window and always shown.
+///
+/// ```ignore
+/// app.add_plugins((EguiPlugin::default(), ConsolePlugin::new(), ConsoleInspectorPlugin));
+/// ```
+pub struct ConsoleInspectorPlugin;
+
+impl Plugin for ConsoleInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DefaultInspectorConfigPlugin>() {
+            app.add_plugins(DefaultInspectorConfigPlugin);
+        }
+        app.register_type::<ConsolePermissions>()
+            .register_type::<PermissionLevel>()
+            .init_resource::<InspectorEdits>()
+            .add_systems(EguiPrimaryContextPass, convar_inspector_ui);
+    }
+}
+
+/// In-progress edits, keyed by ConVar name, committed when the widget is released.
+#[derive(Resource, Default)]
+struct InspectorEdits {
+    numbers: HashMap<String, f64>,
+    texts: HashMap<String, String>,
+}
+
+/// The widget used to edit a ConVar.
+#[derive(Debug, Clone, PartialEq)]
+enum ConVarField {
+    /// Checkbox.
+    Bool(bool),
+    /// Slider when both bounds are set, otherwise a drag value.
+    Number {
+        value: f64,
+        range: Option<RangeInclusive<f64>>,
+        kind: NumberKind,
+    },
+    /// Text input holding the formatted value.
+    Text(String),
+}
+
+impl ConVarField {
+    /// Pick the widget for a ConVar from its backing type.
+    fn for_var(meta: &ConVarMeta) -> Self {
+        fn number<T: Copy + Into<f64>>(value: T, min: Option<&T>, max: Option<&T>, kind: NumberKind) -> ConVarField {
+            let range = min.zip(max).map(|(&min, &max)| min.into()..=max.into());
+            ConVarField::Number { value: value.into(), range, kind }
+        }
+
+        if let Some(cvar) = meta.downcast_ref::<bool>() {
+            return Self::Bool(cvar.get());
+        }
+        if let Some(cvar) = meta.downcast_ref::<f32>() {
+            return number(cvar.get(), cvar.get_min(), cvar.get_max(), NumberKind::F32);
+        }
+        if let Some(cvar) = meta.downcast_ref::<f64>() {
+            return number(cvar.get(), cvar.get_min(), cvar.get_max(), NumberKind::F64);
+        }
+        if let Some(cvar) = meta.downcast_ref::<i32>() {
+            return number(cvar.get(), cvar.get_min(), cvar.get_max(), NumberKind::Integer);
+        }
+        if let Some(cvar) = meta.downcast_ref::<i64>() {
+            // i64 has no lossless `Into<f64>`; the widget works in f64 anyway
+            let range = cvar.get_min().zip(cvar.get_max()).map(|(&min, &max)| min as f64..=max as f64);
+            return Self::Number { value: cvar.get() as f64, range, kind: NumberKind::Integer };
+        }
+        Self::Text(meta.get_string())
+    }
+}
+
+/// The field's value formatted for assignment to its ConVar.
+///
+/// It is queued as-is rather than as console text, so `;`, quotes and `//`
+/// in strings stay part of the value instead of being read as syntax.
+fn formatted_value(field: &ConVarField) -> String {
+    match field {
+        ConVarField::Bool(value) => if *value { "1" } else { "0" }.to_string(),
+        ConVarField::Number { value, kind, .. } => kind.format(*value),
+        ConVarField::Text(value) => value.clone(),
+    }
+}
+
+/// Exclusive system drawing the ConVar and permission inspector windows.
+fn convar_inspector_ui(world: &mut World) {
+    let Ok(egui_context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryEguiContext>>()
+        .single(world)
+    else {
+        return;
+    };
+    let mut egui_context = egui_context.clone();
+
+    let registry = world.resource::<ConsoleRegistry>();
+    let mut vars: Vec<_> = registry
+        .vars()
+        .filter(|(_, meta)| !meta.flags.contains(ConVarFlags::HIDDEN))
+        .map(|(name, meta)| {
+            let read_only = meta.flags.contains(ConVarFlags::READ_ONLY);
+            (name.to_string(), meta.description, read_only, ConVarField::for_var(meta))
+        })
+        .collect();
+    vars.sort_by(|a, b| a.0.cmp(&b.0));
+
+    egui::Window::new("Console Permissions")
+        .default_size((0.0, 0.0))
+        .show(egui_context.get_mut(), |ui| {
+            bevy_inspector::ui_for_resource::<ConsolePermissions>(world, ui);
+        });
+
+    let mut commits = Vec::new();
+    world.resource_scope(|_, mut edits: Mut<InspectorEdits>| {
+        egui::Window::new("ConVars")
+            .default_size((320.0, 400.0))
+            .show(egui_context.get_mut(), |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("convar inspector").num_columns(2).striped(true).show(ui, |ui| {
+                        for (name, description, read_only, mut field) in vars {
+                            let label = ui.label(&name);
+                            if !description.is_empty() {
+                                label.on_hover_text(description);
+                            }
+                            ui.add_enabled_ui(!read_only, |ui| {
+                                if field_widget(ui, &name, &mut field, &mut edits) {
+                                    commits.push(QueuedCommand::assignment(
+                                        &name,
+                                        formatted_value(&field),
+                                        CommandSource::Ui,
+                                    ));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+    });
+
+    let mut pending = world.resource_mut::<PendingCommands>();
+    for command in commits {
+        pending.enqueue(command);
+    }
+}
+
+/// Draw the widget for one ConVar. Returns `true` when an edit should be committed.
+fn field_widget(ui: &mut egui::Ui, name: &str, field: &mut ConVarField, edits: &mut InspectorEdits) -> bool {
+    match field {
+        ConVarField::Bool(value) => ui.checkbox(value, "").changed(),
+        ConVarField::Number { value, range, kind } => {
+            let integer = *kind == NumberKind::Integer;
+            if let Some(edit) = edits.numbers.get(name) {
+                *value = *edit;
+            }
+            let response = match range {
+                Some(range) => {
+                    let slider = egui::Slider::new(value, range.clone());
+                    ui.add(if integer { slider.integer() } else { slider })
+                }
+                None => {
+                    let drag = egui::DragValue::new(value);
+                    ui.add(if integer { drag.fixed_decimals(0) } else { drag })
+                }
+            };
+            // Keep the value locally while dragging so the var isn't set every frame
+            if response.dragged() || response.has_focus() {
+                edits.numbers.insert(name.to_string(), *value);
+                false
+            } else {
+                edits.numbers.remove(name).is_some() || response.changed()
+            }
+        }
+        ConVarField::Text(value) => {
+            let text = edits.texts.entry(name.to_string()).or_insert_with(|| value.clone());
+            let response = ui.text_edit_singleline(text);
+            if response.has_focus() {
+                return false;
+            }
+            let text = edits.texts.remove(name).unwrap_or_default();
+            if response.lost_focus() && text != *value {
+                *value = text;
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConEntry, ConVar};
+
+    #[test]
+    fn test_convar_fields() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_cheats", false));
+        registry.register_var(ConVar::new("cl_fov", 90i32).min(60).max(120));
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        registry.register_var(ConVar::new("sv_hostname", String::from("my server")));
+        registry.register_var(ConVar::new("sv_maps", vec!["de_dust".to_string()]));
+
+        let field = |name| match registry.get_entry(name) {
+            Some(ConEntry::Var(meta)) => ConVarField::for_var(meta),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(field("sv_cheats"), ConVarField::Bool(false));
+        assert_eq!(
+            field("cl_fov"),
+            ConVarField::Number { value: 90.0, range: Some(60.0..=120.0), kind: NumberKind::Integer }
+        );
+        assert_eq!(
+            field("sv_gravity"),
+            ConVarField::Number { value: 800.0, range: None, kind: NumberKind::F32 }
+        );
+        assert_eq!(field("sv_hostname"), ConVarField::Text("my server".into()));
+        assert_eq!(field("sv_maps"), ConVarField::Text("de_dust".into()));
+    }
+
+    #[test]
+    fn test_formatted_value() {
+        assert_eq!(formatted_value(&ConVarField::Bool(true)), "1");
+        let fov = ConVarField::Number { value: 89.6, range: None, kind: NumberKind::Integer };
+        assert_eq!(formatted_value(&fov), "90");
+        let gravity = ConVarField::Number { value: 400.5, range: None, kind: NumberKind::F64 };
+        assert_eq!(formatted_value(&gravity), "400.5");
+        let friction = ConVarField::Number { value: 0.1f32.into(), range: None, kind: NumberKind::F32 };
+        assert_eq!(formatted_value(&friction), "0.1");
+        let hostname = ConVarField::Text("a \"b\"; quit".into());
+        assert_eq!(formatted_value(&hostname), "a \"b\"; quit");
+    }
+}
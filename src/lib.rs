@@ -11,14 +11,17 @@
 //!
 //! - `egui` (default): egui-based UI with log capture
 //! - `terminal`: stdin/stdout backend for dedicated servers
+//! - `remote`: password-protected TCP remote console for dedicated servers
+//! - `replicate`: ConVar replication between server and clients
 //! - `persist`: RON configuration persistence (exec, host_writeconfig, alias)
-//! - `full`: Enable egui + persist
+//! - `derive`: `#[derive(ConVarValue)]` for field-less enums
+//! - `full`: Enable egui + persist + derive
 //!
 //! # Quick Start
 //!
 //! ```ignore
 //! use bevy::prelude::*;
-//! use bevy_console::prelude::*;
+//! use bevy_console_two::prelude::*;
 //!
 //! fn main() {
 //!     App::new()
@@ -45,6 +48,14 @@
 //! }
 //! ```
 
+// Lets derive output refer to `::bevy_console_two` inside this crate too
+#[cfg(feature = "derive")]
+extern crate self as bevy_console_two;
+
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
 use bevy::prelude::*;
 
 // Core module (always available, zero optional deps)
@@ -53,16 +64,31 @@ pub mod core;
 // Re-export core types at crate root for convenience
 pub use core::{
     Console, ConsoleRef,
-    ConVar, ConVarFlags, ConVarValue, ConVarDyn,
-    ConCommand, CommandHandler, CommandArgs,
-    ConsoleRegistry, ConEntry, ConVarMeta, CommandHandlers,
+    ConVar, ConVarFlags, ConVarValue, ConVarDyn, CsvList,
+    ConCommand, CommandHandler, CommandArgs, ArgDef, ArgType, StreamingOutput,
+    ConsoleRegistry, ConEntry, ConVarMeta, CommandHandlers, NamespacePolicy,
     Trie,
-    subsequence_match, match_and_sort, MatchResult,
-    tokenize, tokenize_string, split_commands, TokenizedCommand, TokenizeError,
-    ConsoleInputEvent, ConsoleOutputEvent, ConsoleOutputLevel,
-    ConVarChangedEvent, ConsoleToggleEvent, ConsoleClearEvent,
+    subsequence_match, suffix_match, match_and_sort, glob_match, MatchResult,
+    tokenize, tokenize_string, split_commands, expand_vars, TokenizedCommand, TokenizeError,
+    evaluate_arithmetic, ArithmeticError,
+    ConsoleInputEvent, CommandSource, ConsoleOutputEvent, ConsoleOutputLevel,
+    ConVarChangedEvent, TypedConVarChangedEvent, ConsoleToggleEvent, ConsoleClearEvent,
+    ConsolePauseEvent, ConsolePaused,
+    CommandExecutionEvent, CommandOutcome,
+    CommandErrorEvent, CommandError,
     ConsoleEventsPlugin,
-    PermissionLevel, ConsolePermissions,
+    PermissionLevel, ConsolePermissions, ConsoleWhitelist,
+    CommandStats, CommandBudget,
+    ConsoleOutputBuffer, GrepLine,
+    ConsoleInputRateLimit, DEFAULT_REMOTE_INPUTS_PER_SECOND,
+    ConsoleTelemetry, CommandTelemetryEvent,
+    ConVarSnapshot,
+    ConsoleLocalization,
+    ConsoleInputEventLog, DEFAULT_INPUT_LOG_FRAMES,
+    PendingAsyncCommands,
+    ConVarChangeLog, DEFAULT_CHANGE_LOG_SIZE,
+    ConsoleAuditLog, AuditRecord, DEFAULT_AUDIT_LOG_SIZE,
+    ConVarChange, ObserveConVarExt,
 };
 
 
@@ -73,37 +99,120 @@ pub mod config;
 pub mod logging;
 #[cfg(feature = "egui")]
 pub mod ui;
+#[cfg(feature = "inspector")]
+pub mod inspector;
 
 // Terminal backend (feature-gated)
 #[cfg(feature = "terminal")]
 pub mod terminal;
 
+// Remote console backend (feature-gated)
+#[cfg(feature = "remote")]
+pub mod remote;
+
+// ConVar replication (feature-gated)
+#[cfg(feature = "replicate")]
+pub mod replicate;
+
 // Persistence module (feature-gated)
 #[cfg(feature = "persist")]
 pub mod persist;
 
+// ConsoleConfig from a hot-reloadable asset (feature-gated)
+#[cfg(all(feature = "egui", feature = "persist"))]
+pub mod config_asset;
+
+// Built-in commands
+mod builtins;
+
+// Output mirroring (std only)
+pub mod tee;
+
+// Macro recording and playback (std only)
+pub mod macros;
+
 // Re-exports
 #[cfg(feature = "egui")]
 pub use config::{ConsoleConfig, ConsoleTheme};
 
+#[cfg(feature = "inspector")]
+pub use inspector::ConsoleInspectorPlugin;
+
+#[cfg(feature = "egui-dock")]
+pub use ui::{ConsoleDock, ConsoleDockPlugin, ConsoleDockTab};
+
+#[cfg(all(feature = "egui", feature = "persist"))]
+pub use config_asset::ConsoleConfigAsset;
+
 #[cfg(feature = "persist")]
-pub use persist::{ConsoleConfigFile, CommandAliases, ConfigPath, ConfigError};
+pub use persist::{ConsoleConfigFile, CommandAliases, ConfigPath, ConfigError, ExecSearchPath, UiSettings};
 
 #[cfg(feature = "terminal")]
 pub use terminal::{TerminalPlugin, TerminalConfig};
 
+#[cfg(feature = "derive")]
+pub use bevy_console_derive::ConVarValue;
+
+#[cfg(feature = "remote")]
+pub use remote::{RemoteConsolePlugin, RemoteConsoleConfig, RemoteConsoleServer};
+
+#[cfg(feature = "replicate")]
+pub use replicate::{
+    ConVarReplicationPlugin, ReplicationRole, ConVarUpdate, ReplicationTransport, ReplicationSink,
+    ConVarUpdateReceived, ClientConVars, ClientConVarChanged,
+};
+
+pub use tee::TeeState;
+pub use macros::{ConsoleMacros, ConsoleMacro, MacroStep};
+
 /// Prelude for convenient imports.
 pub mod prelude {
     pub use crate::core::{
         Console, ConsoleRef,
         ConVar, ConVarFlags, ConVarValue,
-        ConCommand, CommandArgs,
+        ConCommand, CommandArgs, ArgDef, ArgType,
         ConsoleRegistry, ConEntry,
         ConsoleInputEvent, ConsoleOutputEvent, ConsoleOutputLevel, ConVarChangedEvent,
-        tokenize, split_commands,
-        PermissionLevel, ConsolePermissions,
+        TypedConVarChangedEvent, ConsolePauseEvent,
+        ConVarChange, ObserveConVarExt,
+        tokenize, split_commands, expand_vars,
+        PermissionLevel, ConsolePermissions, ConsoleWhitelist,
     };
-    pub use crate::ConsolePlugin;
+    pub use crate::{ConsolePlugin, ConsoleSet};
+    #[cfg(feature = "derive")]
+    pub use bevy_console_derive::ConVarValue;
+}
+
+/// System sets used by the console.
+///
+/// In `Startup`, [`Register`](Self::Register) runs before
+/// [`LoadConfig`](Self::LoadConfig). Register your ConVars in
+/// `ConsoleSet::Register` so values saved in the config file are applied to
+/// them.
+///
+/// The command pipeline runs [`ParseInput`](Self::ParseInput),
+/// [`ExecuteCommands`](Self::ExecuteCommands) and
+/// [`SendOutputs`](Self::SendOutputs) in that order. Order against these to
+/// observe commands in the same frame, e.g.
+/// `.after(ConsoleSet::ExecuteCommands)` to read ConVar values just set.
+///
+/// # Examples
+///
+/// ```ignore
+/// app.add_systems(Startup, register_game_cvars.in_set(ConsoleSet::Register));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsoleSet {
+    /// Registering ConVars and commands at startup, including the built-in ones.
+    Register,
+    /// Applying the saved config file at startup (`persist` feature).
+    LoadConfig,
+    /// Reading and tokenizing [`ConsoleInputEvent`]s.
+    ParseInput,
+    /// Running queued commands and ConVar assignments.
+    ExecuteCommands,
+    /// Writing output, change and execution events.
+    SendOutputs,
 }
 
 /// Main console plugin.
@@ -111,39 +220,182 @@ pub mod prelude {
 /// # Configuration
 ///
 /// ```ignore
-/// ConsolePlugin::default()
+/// ConsolePlugin::new()
+///     .theme(ConsoleTheme::light())
+///     .open_animation_duration(Duration::from_millis(150))
+///     .command_schedule(FixedUpdate)
 /// ```
 #[derive(Default)]
-pub struct ConsolePlugin;
+pub struct ConsolePlugin {
+    /// Schedule running the command pipeline (`Update` if unset).
+    command_schedule: Option<InternedScheduleLabel>,
+    /// Per-frame queue limit and overflow handling.
+    queue_limit: Option<(usize, OverflowPolicy)>,
+    /// Theme applied to the egui UI at startup.
+    #[cfg(feature = "egui")]
+    theme: Option<ConsoleTheme>,
+    /// Duration of the egui window's open and close animation.
+    #[cfg(feature = "egui")]
+    open_animation_duration: Option<std::time::Duration>,
+    /// Smallest size of the egui window.
+    #[cfg(feature = "egui")]
+    min_window_size: Option<Vec2>,
+    /// Asset path to load the [`ConsoleConfig`] from.
+    #[cfg(all(feature = "egui", feature = "persist"))]
+    config_asset: Option<String>,
+    /// Whether to write the egui window's layout to the config file.
+    #[cfg(all(feature = "egui", feature = "persist"))]
+    save_window_layout: bool,
+}
+
+impl ConsolePlugin {
+    /// Create a plugin with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run command parsing and execution in `schedule` instead of `Update`.
+    pub fn command_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.command_schedule = Some(schedule.intern());
+        self
+    }
+
+    /// Limit how many commands are queued per frame (default [`DEFAULT_MAX_QUEUE_DEPTH`]).
+    pub fn max_queue_depth(mut self, depth: usize, policy: OverflowPolicy) -> Self {
+        self.queue_limit = Some((depth, policy));
+        self
+    }
+
+    /// Set the theme used by the egui UI.
+    #[cfg(feature = "egui")]
+    pub fn theme(mut self, theme: ConsoleTheme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Animate the egui window's height over `duration` when it opens and closes.
+    #[cfg(feature = "egui")]
+    pub fn open_animation_duration(mut self, duration: std::time::Duration) -> Self {
+        self.open_animation_duration = Some(duration);
+        self
+    }
+
+    /// Keep the egui window at least `size` large (default 400×200).
+    #[cfg(feature = "egui")]
+    pub fn min_window_size(mut self, size: Vec2) -> Self {
+        self.min_window_size = Some(size);
+        self
+    }
+
+    /// Load the [`ConsoleConfig`] from a RON [`ConsoleConfigAsset`] at `path`,
+    /// re-applying it whenever the asset changes.
+    ///
+    /// Requires the `AssetPlugin` to be added before this plugin.
+    #[cfg(all(feature = "egui", feature = "persist"))]
+    pub fn config_asset(mut self, path: impl Into<String>) -> Self {
+        self.config_asset = Some(path.into());
+        self
+    }
+
+    /// Write the egui window's position and size to the config file after the
+    /// user moves or resizes it (off by default).
+    ///
+    /// The layout is saved once it has been unchanged for half a second.
+    #[cfg(all(feature = "egui", feature = "persist"))]
+    pub fn save_window_layout(mut self, enabled: bool) -> Self {
+        self.save_window_layout = enabled;
+        self
+    }
+
+    /// Build the command queue resource from the configured limit.
+    fn pending_commands(&self) -> PendingCommands {
+        let (max_queue_depth, overflow_policy) = self
+            .queue_limit
+            .unwrap_or((DEFAULT_MAX_QUEUE_DEPTH, OverflowPolicy::default()));
+        PendingCommands {
+            max_queue_depth,
+            overflow_policy,
+            ..default()
+        }
+    }
+}
 
 impl Plugin for ConsolePlugin {
     fn build(&self, app: &mut App) {
         // Core: Always register the registry, handlers, and events
         app.init_resource::<ConsoleRegistry>()
             .init_resource::<CommandHandlers>()
-            .init_resource::<PendingCommands>()
+            .insert_resource(self.pending_commands())
+            .init_resource::<DeferredAssignments>()
             .init_resource::<ConsolePermissions>()
+            .init_resource::<ConsoleWhitelist>()
+            .init_resource::<CommandStats>()
+            .init_resource::<CommandBudget>()
+            .init_resource::<ConsoleInputRateLimit>()
+            .init_resource::<ConsoleTelemetry>()
+            .init_resource::<ConsoleLocalization>()
+            .init_resource::<ConsoleOutputBuffer>()
+            .init_resource::<ConsoleInputEventLog>()
+            .init_resource::<PendingAsyncCommands>()
+            .init_resource::<ConVarChangeLog>()
+            .init_resource::<ConsoleAuditLog>()
+            .init_resource::<tee::TeeState>()
+            .init_resource::<macros::ConsoleMacros>()
             .add_plugins(core::ConsoleEventsPlugin);
 
-        // Register built-in commands
-        app.add_systems(Startup, register_builtin_commands);
+        // Register built-in commands, then let the config file override what was registered
+        app.configure_sets(Startup, ConsoleSet::Register.before(ConsoleSet::LoadConfig));
+        app.add_systems(Startup, builtins::register_builtin_commands.in_set(ConsoleSet::Register));
+
+        // Warn about commands registered without a handler
+        app.add_systems(PostStartup, check_missing_handlers);
 
         // Process console input events (three-stage pipeline)
         // 1. parse_console_input: Read input events, tokenize, queue commands
         // 2. execute_pending_commands: Execute commands with exclusive World access
         // 3. send_pending_outputs: Send output events
-        app.add_systems(Update, (
-            parse_console_input,
-            execute_pending_commands,
-            send_pending_outputs,
+        let command_schedule = self.command_schedule.unwrap_or_else(|| Update.intern());
+        app.configure_sets(command_schedule, (
+            ConsoleSet::ParseInput,
+            ConsoleSet::ExecuteCommands,
+            ConsoleSet::SendOutputs,
         ).chain());
+        app.add_systems(command_schedule, (
+            (
+                apply_console_pause,
+                (macros::play_macro_steps, macros::record_macro_input).chain(),
+                (core::record_console_input, parse_console_input, queue_deferred_assignments),
+            ).chain().in_set(ConsoleSet::ParseInput),
+            (
+                execute_pending_commands,
+                core::poll_async_commands,
+                notify_registry_changes,
+                core::update_convar_snapshot.run_if(resource_exists::<ConVarSnapshot>),
+            ).chain().in_set(ConsoleSet::ExecuteCommands),
+            (send_pending_outputs, buffer_console_output).chain().in_set(ConsoleSet::SendOutputs),
+        ));
+
+        // Mirror output to a file while `tee` is active
+        #[cfg(feature = "egui")]
+        app.add_systems(PostUpdate, tee::write_tee_output.after(logging::transfer_log_events));
+        #[cfg(not(feature = "egui"))]
+        app.add_systems(PostUpdate, tee::write_tee_output);
+
+        // Keep recent ConVar changes for systems that don't run every frame
+        app.add_systems(PostUpdate, core::record_convar_changes);
 
         // Persistence (feature-gated)
         #[cfg(feature = "persist")]
         {
             app.init_resource::<persist::CommandAliases>()
                 .init_resource::<persist::ConfigPath>()
-                .add_systems(Startup, persist::load_config_on_startup.after(register_builtin_commands));
+                .init_resource::<persist::ExecSearchPath>()
+                .add_systems(Startup, persist::load_config_on_startup.in_set(ConsoleSet::LoadConfig));
+
+            #[cfg(feature = "egui")]
+            if self.save_window_layout {
+                app.add_systems(Last, persist::save_window_layout);
+            }
         }
 
         // egui UI (feature-gated)
@@ -157,6 +409,7 @@ impl Plugin for ConsolePlugin {
                 .init_resource::<ConsoleConfig>()
                 .init_resource::<ui::AutoCompletions>()
                 .register_type::<ConsoleConfig>()
+                .register_type::<config::ConsoleTheme>()
                 .add_systems(
                     Update,
                     (
@@ -168,8 +421,22 @@ impl Plugin for ConsolePlugin {
                 )
                 .add_systems(
                     EguiPrimaryContextPass,
-                    ui::render_ui_system.run_if(|s: Res<ConsoleUiState>| s.open),
+                    ui::render_ui_system.run_if(|s: Res<ConsoleUiState>| s.open_progress > 0.0 && !s.docked),
                 );
+
+            if let Some(theme) = &self.theme {
+                app.world_mut().resource_mut::<ConsoleConfig>().set_theme(theme.clone());
+            }
+            if let Some(duration) = self.open_animation_duration {
+                app.world_mut().resource_mut::<ConsoleConfig>().open_animation_duration = duration;
+            }
+            if let Some(size) = self.min_window_size {
+                app.world_mut().resource_mut::<ConsoleConfig>().min_size = size;
+            }
+            #[cfg(feature = "persist")]
+            if let Some(path) = &self.config_asset {
+                app.add_plugins(config_asset::ConsoleConfigAssetPlugin { path: path.clone() });
+            }
         }
 
         // Terminal backend (feature-gated)
@@ -186,313 +453,270 @@ fn register_cmd(
     handlers: &mut CommandHandlers,
     cmd: ConCommand,
 ) {
-    let (name, handler, autocomplete, _is_new) = registry.register_cmd(cmd);
-    handlers.register(name, handler, autocomplete);
+    if let Some((name, handler, autocomplete, _is_new)) = registry.try_register_cmd(cmd) {
+        handlers.register(name, handler, autocomplete);
+    }
 }
 
-/// Register built-in console commands.
-fn register_builtin_commands(
-    mut registry: ResMut<ConsoleRegistry>,
-    mut handlers: ResMut<CommandHandlers>,
+/// Warn about commands whose metadata was registered without a handler.
+///
+/// This happens when `ConsoleRegistry::register_cmd` is called directly and the
+/// returned handler is never passed to `CommandHandlers::register`.
+fn check_missing_handlers(
+    registry: Res<ConsoleRegistry>,
+    handlers: Res<CommandHandlers>,
 ) {
-    // sv_cheats - Enable cheat-protected commands and variables
-    registry.register_var(
-        ConVar::new("sv_cheats", 0i32)
-            .description("Enable cheat-protected commands and variables")
-            .min(0)
-            .max(1)
-            .permission(PermissionLevel::Admin)
-    );
-
-    // help - Show help for a command or list all commands
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("help", |args, world| {
-        let registry = world.resource::<ConsoleRegistry>();
-
-        if let Some(name) = args.get(0) {
-            // Show help for specific command/var
-            if let Some(entry) = registry.get_entry(name) {
-                let desc = entry.description();
-                let desc = if desc.is_empty() { "No description" } else { desc };
-                info!("{} - {}", name, desc);
-
-                if let ConEntry::Var(meta) = entry {
-                    info!("  Current: {}", meta.get_string());
-                    info!("  Default: {}", meta.default_string());
-                }
-            } else {
-                warn!("Unknown command or variable: {}", name);
-            }
-        } else {
-            // List all commands
-            info!("Commands:");
-            for (name, _) in registry.cmds() {
-                info!("  {}", name);
-            }
-            info!("Use 'help <name>' for details, 'cvarlist' for variables");
-        }
-    }).description("Show help for a command or list all commands"));
-
-    // cvarlist - List all console variables
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("cvarlist", |args, world| {
-        let registry = world.resource::<ConsoleRegistry>();
-        let prefix = args.get(0).unwrap_or("");
-
-        let mut count = 0;
-        for (name, meta) in registry.vars() {
-            if name.starts_with(prefix) && !meta.flags.contains(ConVarFlags::HIDDEN) {
-                let modified = if meta.is_modified() { "*" } else { "" };
-                info!("{}{} = \"{}\"", name, modified, meta.get_string());
-                count += 1;
-            }
+    for (name, _) in registry.cmds() {
+        if handlers.get(name).is_none() {
+            warn!("Console: Command '{}' has no registered handler", name);
         }
-        info!("{} convars", count);
-    }).description("List console variables"));
+    }
+}
 
-    // find - Search commands and variables
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("find", |args, world| {
-        let registry = world.resource::<ConsoleRegistry>();
+/// Queued command for execution.
+#[derive(Debug, Clone)]
+struct QueuedCommand {
+    /// Raw command string for display.
+    raw: String,
+    /// Command/variable name.
+    name: String,
+    /// Arguments.
+    args: Vec<String>,
+    /// Where the command came from.
+    source: CommandSource,
+    /// Higher priorities run first.
+    priority: u8,
+    /// Position in submission order, set by [`PendingCommands::enqueue`].
+    sequence: u64,
+    /// Number of alias expansions that led to this command.
+    #[cfg(feature = "persist")]
+    alias_depth: usize,
+}
 
-        if let Some(query) = args.get(0) {
-            let results = registry.search(query);
-            let count = results.len();
-            for (name, entry) in results {
-                let kind = if entry.is_var() { "var" } else { "cmd" };
-                let desc = entry.description();
-                if desc.is_empty() {
-                    info!("[{}] {}", kind, name);
-                } else {
-                    info!("[{}] {} - {}", kind, name, desc);
-                }
-            }
-            info!("{} results", count);
-        } else {
-            warn!("Usage: find <search term>");
-        }
-    }).description("Search commands and variables by name or description"));
-
-    // echo - Print text to console
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("echo", |args, _world| {
-        info!("{}", args.join(" "));
-    }).description("Print text to console"));
-
-    // clear - Clear console output
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("clear", |_args, world| {
-        let mut pending = world.resource_mut::<PendingCommands>();
-        pending.clear_console = true;
-    }).description("Clear console output"));
-
-    // quit - Exit the application immediately
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("quit", |_args, _world| {
-        std::process::exit(0);
-    }).description("Exit the application"));
-
-    // toggle - Toggle a boolean convar
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("toggle", |args, world| {
-        if let Some(name) = args.get(0) {
-            let mut registry = world.resource_mut::<ConsoleRegistry>();
-
-            if let Some(current) = registry.get::<bool>(name) {
-                registry.set(name, !current);
-                info!("{} = {}", name, if !current { "1" } else { "0" });
-            } else if let Some(current) = registry.get::<i32>(name) {
-                let new_val = if current == 0 { 1 } else { 0 };
-                registry.set(name, new_val);
-                info!("{} = {}", name, new_val);
-            } else {
-                warn!("Cannot toggle '{}': not a boolean or integer", name);
-            }
-        } else {
-            warn!("Usage: toggle <convar>");
+impl QueuedCommand {
+    fn new(tokens: &TokenizedCommand, source: CommandSource, priority: u8) -> Self {
+        Self {
+            raw: tokens.raw.to_string(),
+            name: tokens.command.to_string(),
+            args: tokens.args.iter().map(|s| s.to_string()).collect(),
+            source,
+            priority,
+            sequence: 0,
+            #[cfg(feature = "persist")]
+            alias_depth: 0,
         }
-    }).description("Toggle a boolean convar"));
-
-    // reset - Reset a convar to default
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("reset", |args, world| {
-        if let Some(name) = args.get(0) {
-            let mut registry = world.resource_mut::<ConsoleRegistry>();
-
-            if let Some(ConEntry::Var(meta)) = registry.get_entry_mut(name) {
-                meta.reset();
-                info!("{} reset to \"{}\"", name, meta.get_string());
-            } else {
-                warn!("Unknown variable: {}", name);
-            }
-        } else {
-            warn!("Usage: reset <convar>");
-        }
-    }).description("Reset a convar to its default value"));
-
-    // differences - Show convars that differ from default
-    register_cmd(&mut registry, &mut handlers, ConCommand::new("differences", |_args, world| {
-        let registry = world.resource::<ConsoleRegistry>();
+    }
 
-        let mut count = 0;
-        for (name, meta) in registry.modified_vars() {
-            info!("{} = \"{}\" (default: \"{}\")",
-                name, meta.get_string(), meta.default_string());
-            count += 1;
+    /// Assignment of an already formatted value to the ConVar `name`.
+    ///
+    /// The value is the single argument as-is, so it never goes through the
+    /// tokenizer and `;`, quotes or `//` in it stay part of the value.
+    fn assignment(name: &str, value: String, source: CommandSource) -> Self {
+        Self {
+            raw: format!("{} {}", name, value),
+            name: name.to_string(),
+            args: vec![value],
+            source,
+            priority: 0,
+            sequence: 0,
+            #[cfg(feature = "persist")]
+            alias_depth: 0,
         }
+    }
+}
 
-        if count == 0 {
-            info!("No modified convars");
-        } else {
-            info!("{} modified convars", count);
-        }
-    }).description("Show convars with non-default values"));
+// Ordered for the max-heap: highest priority first, then earliest submitted
+impl Ord for QueuedCommand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
 
-    // Persistence commands (only with persist feature)
-    #[cfg(feature = "persist")]
-    register_persist_commands(&mut registry, &mut handlers);
+impl PartialOrd for QueuedCommand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-/// Register persistence-related commands.
-#[cfg(feature = "persist")]
-fn register_persist_commands(
-    registry: &mut ConsoleRegistry,
-    handlers: &mut CommandHandlers,
-) {
-    // exec - Execute commands from a file
-    register_cmd(registry, handlers, ConCommand::new("exec", |args, world| {
-        if let Some(filename) = args.get(0) {
-            // We need to queue the commands, not execute them directly
-            // So we'll read the file and send input events
-            let path = std::path::Path::new(filename);
-
-            match std::fs::read_to_string(path) {
-                Ok(contents) => {
-                    info!("Executing '{}'...", filename);
-                    let mut count = 0;
-
-                    // Queue each line as a command
-                    for line in contents.lines() {
-                        let line = line.trim();
-                        if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
-                            continue;
-                        }
+impl PartialEq for QueuedCommand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
 
-                        // Queue the command through pending
-                        let mut pending = world.resource_mut::<PendingCommands>();
-                        pending.outputs.push(ConsoleOutputEvent::command(format!("$ {}", line)));
-
-                        if let Ok(tokens) = tokenize(line) {
-                            pending.queue.push(QueuedCommand {
-                                raw: line.to_string(),
-                                name: tokens.command.to_string(),
-                                args: tokens.args.iter().map(|s| s.to_string()).collect(),
-                            });
-                            count += 1;
-                        }
-                    }
+impl Eq for QueuedCommand {}
 
-                    info!("Queued {} commands from '{}'", count, filename);
-                }
-                Err(e) => {
-                    error!("Failed to read '{}': {}", filename, e);
-                }
-            }
-        } else {
-            warn!("Usage: exec <filename>");
-        }
-    }).description("Execute commands from a file"));
+/// Default limit on commands queued per frame.
+pub const DEFAULT_MAX_QUEUE_DEPTH: usize = 256;
 
-    // host_writeconfig - Save ARCHIVE convars to file
-    register_cmd(registry, handlers, ConCommand::new("host_writeconfig", |args, world| {
-        let config_path = world.resource::<persist::ConfigPath>();
-        let filename = args.get(0).unwrap_or(&config_path.0);
+/// What happens to input commands beyond the queue depth limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Report an error, send a [`CommandErrorEvent`] with
+    /// [`CommandError::QueueFull`] and discard the command.
+    #[default]
+    Drop,
+    /// Keep the command for a later frame.
+    DeferToNextFrame,
+}
 
-        let registry = world.resource::<ConsoleRegistry>();
-        let aliases = world.resource::<persist::CommandAliases>();
+/// Resource that holds pending command executions.
+#[derive(Resource)]
+struct PendingCommands {
+    /// Maximum number of queued commands before `overflow_policy` applies.
+    max_queue_depth: usize,
+    overflow_policy: OverflowPolicy,
+    queue: BinaryHeap<QueuedCommand>,
+    /// Sequence number for the next queued command.
+    next_sequence: u64,
+    outputs: OutputQueue,
+    changes: Vec<ConVarChangedEvent>,
+    executions: Vec<CommandExecutionEvent>,
+    errors: Vec<CommandErrorEvent>,
+    clears: Vec<ConsoleClearEvent>,
+    /// Input received while paused or deferred by the queue limit, in submission order.
+    held_input: Vec<ConsoleInputEvent>,
+}
 
-        match persist::save_config(&registry, &aliases, filename) {
-            Ok(()) => {
-                info!("Saved config to '{}'", filename);
-            }
-            Err(e) => {
-                error!("Failed to save config: {}", e);
-            }
+impl Default for PendingCommands {
+    fn default() -> Self {
+        Self {
+            max_queue_depth: DEFAULT_MAX_QUEUE_DEPTH,
+            overflow_policy: OverflowPolicy::default(),
+            queue: BinaryHeap::new(),
+            next_sequence: 0,
+            outputs: OutputQueue::default(),
+            changes: Vec::new(),
+            executions: Vec::new(),
+            errors: Vec::new(),
+            clears: Vec::new(),
+            held_input: Vec::new(),
         }
-    }).description("Save ARCHIVE convars to config file"));
+    }
+}
 
-    // alias - Create or list command aliases
-    register_cmd(registry, handlers, ConCommand::new("alias", |args, world| {
-        let mut aliases = world.resource_mut::<persist::CommandAliases>();
+impl PendingCommands {
+    /// Queue `command` behind everything already queued with the same priority.
+    fn enqueue(&mut self, mut command: QueuedCommand) {
+        command.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.queue.push(command);
+    }
+}
 
-        match (args.get(0), args.get(1)) {
-            (None, None) => {
-                // List all aliases
-                if aliases.is_empty() {
-                    info!("No aliases defined");
-                } else {
-                    info!("Aliases:");
-                    for (name, command) in aliases.iter() {
-                        info!("  {} -> {}", name, command);
-                    }
-                }
-            }
-            (Some(name), None) => {
-                // Show specific alias
-                if let Some(command) = aliases.get(name) {
-                    info!("{} -> {}", name, command);
-                } else {
-                    warn!("Alias '{}' not found", name);
-                }
-            }
-            (Some(name), Some(_)) => {
-                // Create alias (join remaining args as the command)
-                let command = args.join_from(1, " ");
-                aliases.add(name.to_string(), command.clone());
-                info!("Alias '{}' set to '{}'", name, command);
-            }
-            (None, Some(_)) => unreachable!(),
-        }
-    }).description("Create or list command aliases"));
+/// Output events waiting for [`send_pending_outputs`].
+///
+/// Shared so a [`StreamingOutput`] can append to it while its command holds
+/// the world.
+#[derive(Clone, Debug, Default)]
+struct OutputQueue(Arc<Mutex<Vec<ConsoleOutputEvent>>>);
+
+impl OutputQueue {
+    fn push(&self, event: ConsoleOutputEvent) {
+        self.0.lock().unwrap().push(event);
+    }
 
-    // unalias - Remove a command alias
-    register_cmd(registry, handlers, ConCommand::new("unalias", |args, world| {
-        if let Some(name) = args.get(0) {
-            let mut aliases = world.resource_mut::<persist::CommandAliases>();
+    fn extend(&self, events: impl IntoIterator<Item = ConsoleOutputEvent>) {
+        self.0.lock().unwrap().extend(events);
+    }
 
-            if aliases.remove(name).is_some() {
-                info!("Removed alias '{}'", name);
-            } else {
-                warn!("Alias '{}' not found", name);
-            }
-        } else {
-            warn!("Usage: unalias <name>");
-        }
-    }).description("Remove a command alias"));
-}
+    /// Remove and return everything queued so far.
+    fn take(&self) -> Vec<ConsoleOutputEvent> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
 
-/// Queued command for execution.
-#[derive(Debug, Clone)]
-struct QueuedCommand {
-    /// Raw command string for display.
-    raw: String,
-    /// Command/variable name.
-    name: String,
-    /// Arguments.
-    args: Vec<String>,
+    fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
 }
 
-/// Resource that holds pending command executions.
+/// Assignments queued by [`ConsoleRef::set_deferred`].
+///
+/// Behind a mutex so systems with only shared access to the console can queue
+/// them; [`queue_deferred_assignments`] moves them into [`PendingCommands`].
 #[derive(Resource, Default)]
-struct PendingCommands {
-    queue: Vec<QueuedCommand>,
-    outputs: Vec<ConsoleOutputEvent>,
-    changes: Vec<ConVarChangedEvent>,
-    clear_console: bool,
+struct DeferredAssignments(Mutex<Vec<QueuedCommand>>);
+
+/// System that queues assignments from [`ConsoleRef::set_deferred`] for execution.
+fn queue_deferred_assignments(deferred: Res<DeferredAssignments>, mut pending: ResMut<PendingCommands>) {
+    for command in deferred.0.lock().unwrap().drain(..) {
+        pending.enqueue(command);
+    }
+}
+
+/// System that applies pause/resume requests to [`ConsolePaused`].
+fn apply_console_pause(
+    mut pause_events: MessageReader<ConsolePauseEvent>,
+    mut paused: ResMut<ConsolePaused>,
+) {
+    if let Some(event) = pause_events.read().last() {
+        paused.0 = event.paused;
+    }
 }
 
 /// System that parses console input and queues commands for execution.
 fn parse_console_input(
     mut input_events: MessageReader<ConsoleInputEvent>,
     mut pending: ResMut<PendingCommands>,
+    mut rate_limit: ResMut<ConsoleInputRateLimit>,
+    paused: Res<ConsolePaused>,
 ) {
-    for event in input_events.read() {
+    // Drop input over its source's rate limit before doing any work on it
+    let now = std::time::Instant::now();
+    let input: Vec<_> = input_events.read().filter(|event| {
+        if rate_limit.check(&event.source, now) {
+            return true;
+        }
+        pending.outputs.push(ConsoleOutputEvent::error(
+            format!("Input rate limit exceeded, dropping '{}'", event.command)
+        ));
+        let name = event.command.split_whitespace().next().unwrap_or_default();
+        pending.errors.push(CommandErrorEvent::new(name, CommandError::RateLimited));
+        false
+    }).cloned().collect();
+
+    // Messages are only kept for two frames, so hold input back ourselves while paused
+    if paused.0 {
+        pending.held_input.extend(input);
+        return;
+    }
+
+    let held = std::mem::take(&mut pending.held_input);
+    let mut deferred = Vec::new();
+    for event in held.iter().chain(&input) {
+        // Once anything is deferred, keep the rest behind it to preserve order
+        if !deferred.is_empty() {
+            deferred.push(event.clone());
+            continue;
+        }
+
         // Split by semicolons for multiple commands
         let commands = split_commands(&event.command);
 
-        for cmd_str in commands {
+        for (i, cmd_str) in commands.iter().copied().enumerate() {
+            if pending.queue.len() >= pending.max_queue_depth {
+                match pending.overflow_policy {
+                    OverflowPolicy::Drop => {
+                        pending.outputs.push(ConsoleOutputEvent::error(
+                            format!("Command queue full, dropping '{}'", cmd_str)
+                        ));
+                        let name = cmd_str.split_whitespace().next().unwrap_or_default();
+                        pending.errors.push(CommandErrorEvent::new(name, CommandError::QueueFull));
+                        continue;
+                    }
+                    OverflowPolicy::DeferToNextFrame => {
+                        deferred.extend(commands[i..].iter().map(|cmd| {
+                            ConsoleInputEvent::new(*cmd)
+                                .with_source(event.source.clone())
+                                .with_priority(event.priority)
+                        }));
+                        break;
+                    }
+                }
+            }
+
             // Echo the command
             pending.outputs.push(ConsoleOutputEvent::command(format!("$ {}", cmd_str)));
 
@@ -501,17 +725,16 @@ fn parse_console_input(
                 Ok(t) => t,
                 Err(e) => {
                     pending.outputs.push(ConsoleOutputEvent::error(format!("Parse error: {}", e)));
+                    let name = cmd_str.split_whitespace().next().unwrap_or_default();
+                    pending.errors.push(CommandErrorEvent::new(name, CommandError::ParseFailed(e.to_string())));
                     continue;
                 }
             };
 
-            pending.queue.push(QueuedCommand {
-                raw: cmd_str.to_string(),
-                name: tokens.command.to_string(),
-                args: tokens.args.iter().map(|s| s.to_string()).collect(),
-            });
+            pending.enqueue(QueuedCommand::new(&tokens, event.source.clone(), event.priority));
         }
     }
+    pending.held_input = deferred;
 }
 
 /// Check if access is permitted based on flags and permission level.
@@ -519,56 +742,171 @@ fn parse_console_input(
 /// Checks:
 /// 1. If CHEAT flag is set, `sv_cheats` must be enabled
 /// 2. Current permission level must be >= required level
+///
+/// On failure returns the error kind and a human-readable reason.
 fn check_access(
     world: &World,
     flags: ConVarFlags,
     required_permission: PermissionLevel,
-) -> Result<(), String> {
+) -> Result<(), (CommandError, String)> {
     // Check CHEAT flag
     if flags.contains(ConVarFlags::CHEAT) {
         let registry = world.resource::<ConsoleRegistry>();
         if registry.get::<i32>("sv_cheats").unwrap_or(0) == 0 {
-            return Err("Requires sv_cheats to be enabled".into());
+            return Err((CommandError::CheatRequired, "Requires sv_cheats to be enabled".into()));
         }
     }
 
     // Check permission level
     let perms = world.resource::<ConsolePermissions>();
     if !perms.has_permission(required_permission) {
-        return Err(format!(
+        return Err((CommandError::PermissionDenied, format!(
             "Insufficient permission (requires {}, have {})",
             required_permission.name(),
             perms.current_level.name()
-        ));
+        )));
     }
 
     Ok(())
 }
 
+/// Format an arithmetic result for a ConVar of the numeric type `type_name`.
+///
+/// Returns `None` for non-numeric types, and an error instead of rounding or
+/// saturating when the result doesn't fit the type.
+fn format_numeric(result: f64, type_name: &str) -> Option<Result<String, String>> {
+    /// Largest magnitude up to which every integer is exact in an f64.
+    const MAX_EXACT_INTEGER: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+    if !matches!(type_name, "i32" | "i64" | "f32" | "f64") {
+        return None;
+    }
+    if !result.is_finite() {
+        return Some(Err(format!("result {} is not a finite number", result)));
+    }
+    let formatted = match type_name {
+        "i32" | "i64" if result.fract() != 0.0 => Err(format!("result {} is not an integer", result)),
+        "i32" if result < i32::MIN as f64 || result > i32::MAX as f64 => {
+            Err(format!("result {} is out of range for i32", result))
+        }
+        "i32" => Ok((result as i32).format()),
+        "i64" if result.abs() > MAX_EXACT_INTEGER => Err(format!("result {} is too large to be exact as i64", result)),
+        "i64" => Ok((result as i64).format()),
+        "f32" if result.abs() > f32::MAX as f64 => Err(format!("result {} is out of range for f32", result)),
+        "f32" => Ok((result as f32).format()),
+        _ => Ok(result.format()),
+    };
+    Some(formatted)
+}
+
+/// Set the ConVar `name` from console text, returning its new value.
+///
+/// Shared by `name value` input and the `set` command, so both check access
+/// and notify listeners the same way. The [`ConVarChangedEvent`] is queued in
+/// [`PendingCommands`]; typed events and observers are triggered right away.
+fn set_var(world: &mut World, name: &str, value: &str) -> Result<String, (CommandError, String)> {
+    // Events and observers use the registered spelling, whatever case was typed
+    let (name, flags, required_permission) = match world.resource::<ConsoleRegistry>().get_entry(name) {
+        Some(ConEntry::Var(meta)) => (meta.name.to_string(), meta.flags, meta.required_permission),
+        _ => return Err((CommandError::Unknown, "Unknown variable".into())),
+    };
+    let name = name.as_str();
+    check_access(world, flags, required_permission)?;
+
+    let mut registry = world.resource_mut::<ConsoleRegistry>();
+    let Some(ConEntry::Var(meta)) = registry.get_entry_mut(name) else {
+        return Err((CommandError::Unknown, "Unknown variable".into()));
+    };
+    let old_value = meta.get_string();
+    if !meta.set_string(value) {
+        return Err((CommandError::InvalidValue, "invalid value or read-only".into()));
+    }
+    let new_value = meta.get_string();
+    let typed_change = flags.contains(ConVarFlags::NOTIFY).then(|| meta.typed_change_writer());
+
+    // Typed events are written directly since their type is only known here
+    if let Some(write) = typed_change {
+        write(world, name, &old_value, &new_value);
+    }
+    world
+        .resource_mut::<PendingCommands>()
+        .changes
+        .push(ConVarChangedEvent::new(name, old_value, new_value.clone()));
+    Ok(new_value)
+}
+
+/// Exclusive system that reports changes made through [`ConsoleRegistry`]'s
+/// setters, such as [`Console::set`] and the `toggle` and `reset` commands.
+///
+/// Queues a [`ConVarChangedEvent`] and, for NOTIFY variables, writes typed
+/// events and triggers observers, the same as [`set_var`].
+fn notify_registry_changes(world: &mut World) {
+    let changes = world.resource_mut::<ConsoleRegistry>().take_changes();
+    for change in changes {
+        let typed_change = match world.resource::<ConsoleRegistry>().get_entry(&change.name) {
+            Some(ConEntry::Var(meta)) if meta.flags.contains(ConVarFlags::NOTIFY) => Some(meta.typed_change_writer()),
+            _ => None,
+        };
+        if let Some(write) = typed_change {
+            write(world, &change.name, &change.old_value, &change.new_value);
+        }
+        world.resource_mut::<PendingCommands>().changes.push(change);
+    }
+}
+
 /// Exclusive system that executes queued commands with full World access.
 fn execute_pending_commands(world: &mut World) {
-    // Take the pending commands
-    let mut pending = world.resource_mut::<PendingCommands>();
-    let queue = std::mem::take(&mut pending.queue);
-    let mut outputs = std::mem::take(&mut pending.outputs);
-    let mut changes = std::mem::take(&mut pending.changes);
-    drop(pending);
+    if world.resource::<ConsolePaused>().0 {
+        return;
+    }
 
-    if queue.is_empty() && outputs.is_empty() {
+    // Take the pending commands, leaving events collected elsewhere in place
+    // when there is nothing to run
+    let mut pending = world.resource_mut::<PendingCommands>();
+    if pending.queue.is_empty() && pending.outputs.is_empty() {
         return;
     }
+    let mut queue = std::mem::take(&mut pending.queue);
+    let mut outputs = pending.outputs.take();
+    let mut executions = std::mem::take(&mut pending.executions);
+    let mut errors = std::mem::take(&mut pending.errors);
+
+    let budget = world.resource::<CommandBudget>().max_frame_time;
+    let frame_start = std::time::Instant::now();
+    let mut deferred = Vec::new();
+
+    // Audit each command with the permission level it ran under, since a
+    // handler may change the level for the commands after it
+    let audited_from = executions.len();
+    let mut actual_permissions = Vec::new();
+    let mut actual_permission = world.resource::<ConsolePermissions>().current_level;
+
+    let mut ran_any = false;
+    while let Some(mut cmd) = queue.pop() {
+        actual_permissions.resize(executions.len() - audited_from, actual_permission);
+        actual_permission = world.resource::<ConsolePermissions>().current_level;
+
+        // Leave the rest for the next frame once the budget is spent, but always
+        // run one command so a tiny budget still makes progress
+        if ran_any && frame_start.elapsed() > budget {
+            deferred.push(cmd);
+            deferred.extend(queue.drain());
+            break;
+        }
+        ran_any = true;
 
-    for cmd in queue {
         // First, check what type of entry this is and get access info (borrow registry briefly)
         let entry_info = {
             let registry = world.resource::<ConsoleRegistry>();
             match registry.get_entry(&cmd.name) {
                 Some(ConEntry::Cmd(meta)) => Some((
+                    meta.name.clone(),
                     true,  // is_command
                     meta.flags,
                     meta.required_permission,
                 )),
                 Some(ConEntry::Var(meta)) => Some((
+                    meta.name.clone(),
                     false, // is_command
                     meta.flags,
                     meta.required_permission,
@@ -577,13 +915,69 @@ fn execute_pending_commands(world: &mut World) {
             }
         };
 
+        // Use the registered spelling in case the lookup fell back to case-insensitive
+        let entry_info = entry_info.map(|(name, is_command, flags, required_permission)| {
+            if *cmd.name != *name {
+                warn!("Console: '{}' matched '{}' ignoring case", cmd.name, name);
+            }
+            cmd.name = name.into();
+            (is_command, flags, required_permission)
+        });
+
         match entry_info {
             Some((true, flags, required_permission)) => {
+                if !world.resource::<ConsoleWhitelist>().is_allowed(&cmd.name) {
+                    outputs.push(ConsoleOutputEvent::error(
+                        format!("Cannot execute '{}': Access denied: command not in whitelist", cmd.name)
+                    ));
+                    errors.push(CommandErrorEvent::new(cmd.name.clone(), CommandError::NotWhitelisted));
+                    executions.push(CommandExecutionEvent::new(
+                        cmd.name, cmd.args, CommandOutcome::PermissionDenied, cmd.source,
+                    ));
+                    continue;
+                }
+
                 // It's a command - check access first
-                if let Err(msg) = check_access(world, flags, required_permission) {
+                if let Err((error, msg)) = check_access(world, flags, required_permission) {
                     outputs.push(ConsoleOutputEvent::error(
                         format!("Cannot execute '{}': {}", cmd.name, msg)
                     ));
+                    errors.push(CommandErrorEvent::new(cmd.name.clone(), error));
+                    executions.push(CommandExecutionEvent::new(
+                        cmd.name, cmd.args, CommandOutcome::PermissionDenied, cmd.source,
+                    ));
+                    continue;
+                }
+
+                if flags.contains(ConVarFlags::DEPRECATED) {
+                    let replacement = match world.resource::<ConsoleRegistry>().get_entry(&cmd.name) {
+                        Some(ConEntry::Cmd(meta)) => meta.deprecated_replacement,
+                        _ => None,
+                    };
+                    outputs.push(ConsoleOutputEvent::warn(match replacement {
+                        Some(replacement) => format!("'{}' is deprecated, use '{}' instead", cmd.name, replacement),
+                        None => format!("'{}' is deprecated", cmd.name),
+                    }));
+                }
+
+                let schema_error = match world.resource::<ConsoleRegistry>().get_entry(&cmd.name) {
+                    Some(ConEntry::Cmd(meta)) => meta.validate_args(&cmd.args).err().map(|e| (e, meta.usage())),
+                    _ => None,
+                };
+                if let Some((error, usage)) = schema_error {
+                    let reason = match &error {
+                        CommandError::MissingArgument(name) => format!("missing argument '{}'", name),
+                        CommandError::InvalidArgument(name, ty) => format!("argument '{}' must be {}", name, ty),
+                        _ => "invalid arguments".to_string(),
+                    };
+                    outputs.push(ConsoleOutputEvent::error(format!(
+                        "Cannot execute '{}': {} (usage: {})",
+                        cmd.name, reason, usage.unwrap_or_default()
+                    )));
+                    errors.push(CommandErrorEvent::new(cmd.name.clone(), error));
+                    executions.push(CommandExecutionEvent::new(
+                        cmd.name, cmd.args, CommandOutcome::InvalidArguments, cmd.source,
+                    ));
                     continue;
                 }
 
@@ -597,12 +991,15 @@ fn execute_pending_commands(world: &mut World) {
                         let cmd_args = CommandArgs::new(&cmd.raw, args_refs);
 
                         // Execute with panic safety - always restore handler even if panic occurs
+                        let start = std::time::Instant::now();
                         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                             handler(&cmd_args, world);
                         }));
+                        let elapsed = start.elapsed();
 
                         // Always put the handler back, regardless of panic
                         handlers.put(&cmd.name, handler);
+                        world.resource_mut::<CommandStats>().record(&cmd.name, elapsed);
 
                         // Return panic info if one occurred
                         if let Err(panic_info) = result {
@@ -619,14 +1016,26 @@ fn execute_pending_commands(world: &mut World) {
                     None
                 });
 
+                // Keep results the handler pushed or streamed in order with the surrounding output
+                outputs.extend(world.resource::<PendingCommands>().outputs.take());
+
                 // Log panic outside resource_scope so we can add to outputs
-                if let Some(panic_msg) = panic_result {
-                    outputs.push(ConsoleOutputEvent::error(
-                        format!("Command '{}' panicked: {}", cmd_name_for_panic, panic_msg)
-                    ));
-                }
+                let outcome = match panic_result {
+                    Some(panic_msg) => {
+                        outputs.push(ConsoleOutputEvent::error(
+                            format!("Command '{}' panicked: {}", cmd_name_for_panic, panic_msg)
+                        ));
+                        errors.push(CommandErrorEvent::new(
+                            cmd.name.clone(),
+                            CommandError::HandlerPanicked(panic_msg.clone()),
+                        ));
+                        CommandOutcome::Panicked(panic_msg)
+                    }
+                    None => CommandOutcome::Success,
+                };
+                executions.push(CommandExecutionEvent::new(cmd.name, cmd.args, outcome, cmd.source));
             }
-            Some((false, flags, required_permission)) => {
+            Some((false, _, _)) => {
                 // It's a variable - handle get/set
                 if cmd.args.is_empty() {
                     // Get variable (no access check needed for reading)
@@ -643,39 +1052,33 @@ fn execute_pending_commands(world: &mut World) {
                             ));
                         }
                     }
+                    executions.push(CommandExecutionEvent::new(
+                        cmd.name, cmd.args, CommandOutcome::Success, cmd.source,
+                    ));
                 } else {
-                    // Set variable - check access first
-                    if let Err(msg) = check_access(world, flags, required_permission) {
-                        outputs.push(ConsoleOutputEvent::error(
-                            format!("Cannot set '{}': {}", cmd.name, msg)
-                        ));
-                        continue;
-                    }
-
-                    // Re-borrow registry for the actual set
-                    let mut registry = world.resource_mut::<ConsoleRegistry>();
-                    let old_value = registry.get_string(&cmd.name).unwrap_or_default();
-                    let new_value = cmd.args.join(" ");
-
-                    if let Some(ConEntry::Var(meta)) = registry.get_entry_mut(&cmd.name) {
-                        if meta.set_string(&new_value) {
-                            let actual_new = meta.get_string();
+                    let value = cmd.args.join(" ");
+                    let outcome = match set_var(world, &cmd.name, &value) {
+                        Ok(new_value) => {
                             outputs.push(ConsoleOutputEvent::result(
-                                format!("\"{}\" = \"{}\"", cmd.name, actual_new)
-                            ));
-
-                            // Queue change event
-                            changes.push(ConVarChangedEvent::new(
-                                cmd.name.clone(),
-                                old_value,
-                                actual_new,
+                                format!("\"{}\" = \"{}\"", cmd.name, new_value)
                             ));
-                        } else {
+                            CommandOutcome::Success
+                        }
+                        Err((error, msg)) => {
                             outputs.push(ConsoleOutputEvent::error(
-                                format!("Cannot set '{}': invalid value or read-only", cmd.name)
+                                format!("Cannot set '{}': {}", cmd.name, msg)
                             ));
+                            let outcome = match error {
+                                CommandError::PermissionDenied | CommandError::CheatRequired => {
+                                    CommandOutcome::PermissionDenied
+                                }
+                                _ => CommandOutcome::InvalidArguments,
+                            };
+                            errors.push(CommandErrorEvent::new(cmd.name.clone(), error));
+                            outcome
                         }
-                    }
+                    };
+                    executions.push(CommandExecutionEvent::new(cmd.name, cmd.args, outcome, cmd.source));
                 }
             }
             None => {
@@ -688,23 +1091,41 @@ fn execute_pending_commands(world: &mut World) {
                     };
 
                     if let Some(alias_expansion) = alias_cmd {
-                        // Expand the alias: replace the alias name with its expansion
-                        // and append any additional arguments
-                        let expanded = if cmd.args.is_empty() {
-                            alias_expansion
-                        } else {
-                            format!("{} {}", alias_expansion, cmd.args.join(" "))
-                        };
+                        // Stop aliases that expand into themselves, directly or through others
+                        if cmd.alias_depth >= persist::MAX_ALIAS_DEPTH {
+                            outputs.push(ConsoleOutputEvent::error(format!(
+                                "Alias '{}' expanded more than {} times, stopping",
+                                cmd.name,
+                                persist::MAX_ALIAS_DEPTH
+                            )));
+                            errors.push(CommandErrorEvent::new(cmd.name.clone(), CommandError::AliasDepthExceeded));
+                            executions.push(CommandExecutionEvent::new(
+                                cmd.name, cmd.args, CommandOutcome::AliasDepthExceeded, cmd.source,
+                            ));
+                            continue;
+                        }
+
+                        // Expand the alias: substitute $1..$9/$@/$# placeholders,
+                        // or append the arguments if there are none
+                        let expanded = persist::expand_alias(&alias_expansion, &cmd.args);
 
                         // Queue the expanded command
-                        if let Ok(tokens) = tokenize(&expanded) {
-                            let mut pending = world.resource_mut::<PendingCommands>();
-                            pending.queue.push(QueuedCommand {
-                                raw: expanded.clone(),
-                                name: tokens.command.to_string(),
-                                args: tokens.args.iter().map(|s| s.to_string()).collect(),
-                            });
-                        }
+                        let outcome = match tokenize(&expanded) {
+                            Ok(tokens) => {
+                                let mut command = QueuedCommand::new(&tokens, cmd.source.clone(), cmd.priority);
+                                command.alias_depth = cmd.alias_depth + 1;
+                                world.resource_mut::<PendingCommands>().enqueue(command);
+                                CommandOutcome::Success
+                            }
+                            Err(e) => {
+                                outputs.push(ConsoleOutputEvent::error(
+                                    format!("Cannot expand alias '{}': {}", cmd.name, e)
+                                ));
+                                errors.push(CommandErrorEvent::new(cmd.name.clone(), CommandError::ParseFailed(e.to_string())));
+                                CommandOutcome::InvalidArguments
+                            }
+                        };
+                        executions.push(CommandExecutionEvent::new(cmd.name, cmd.args, outcome, cmd.source));
                         continue;
                     }
                 }
@@ -712,14 +1133,76 @@ fn execute_pending_commands(world: &mut World) {
                 outputs.push(ConsoleOutputEvent::error(
                     format!("Unknown command or variable: '{}'", cmd.name)
                 ));
+                errors.push(CommandErrorEvent::new(cmd.name.clone(), CommandError::Unknown));
+                executions.push(CommandExecutionEvent::new(
+                    cmd.name, cmd.args, CommandOutcome::NotFound, cmd.source,
+                ));
             }
         }
     }
 
-    // Store outputs and changes back for the next system to send
+    if !deferred.is_empty() {
+        world.resource_mut::<CommandStats>().budget_exceeded_count += 1;
+        if world.resource_mut::<CommandBudget>().should_warn(std::time::Instant::now()) {
+            warn!(
+                "Console: command budget of {:?} exceeded, deferring {} command(s)",
+                budget,
+                deferred.len()
+            );
+        }
+    }
+
+    let mut stats = world.resource_mut::<CommandStats>();
+    for error in &errors {
+        stats.total_errors += 1;
+        if matches!(
+            error.error,
+            CommandError::PermissionDenied | CommandError::CheatRequired | CommandError::NotWhitelisted
+        ) {
+            stats.total_permission_denials += 1;
+        }
+    }
+
+    if let Some(callback) = &world.resource::<ConsoleTelemetry>().on_command_executed {
+        for execution in &executions {
+            callback(CommandTelemetryEvent::from(execution));
+        }
+    }
+
+    // Unknown commands, aliases and variable reads never reached an access
+    // decision, so they aren't audited
+    actual_permissions.resize(executions.len() - audited_from, actual_permission);
+    let registry = world.resource::<ConsoleRegistry>();
+    let records: Vec<_> = executions[audited_from..]
+        .iter()
+        .zip(actual_permissions)
+        .filter_map(|(execution, actual_permission)| {
+            let entry = registry.get_entry(&execution.name)?;
+            if matches!(entry, ConEntry::Var(_)) && execution.args.is_empty() {
+                return None;
+            }
+            Some(AuditRecord::new(
+                execution.name.clone(),
+                entry.required_permission(),
+                actual_permission,
+                execution.outcome != CommandOutcome::PermissionDenied,
+                execution.args.len(),
+            ))
+        })
+        .collect();
+    let mut audit_log = world.resource_mut::<ConsoleAuditLog>();
+    for record in records {
+        audit_log.push(record);
+    }
+
+    // Store outputs back for the next system to send
     let mut pending = world.resource_mut::<PendingCommands>();
-    pending.outputs = outputs;
-    pending.changes = changes;
+    // Deferred commands keep their sequence, so they run before anything of the
+    // same priority queued by this frame's handlers
+    pending.queue.extend(deferred);
+    pending.outputs.extend(outputs);
+    pending.executions = executions;
+    pending.errors = errors;
 }
 
 /// System that sends queued output events.
@@ -727,20 +1210,41 @@ fn send_pending_outputs(
     mut pending: ResMut<PendingCommands>,
     mut output_events: MessageWriter<ConsoleOutputEvent>,
     mut change_events: MessageWriter<ConVarChangedEvent>,
+    mut execution_events: MessageWriter<CommandExecutionEvent>,
+    mut error_events: MessageWriter<CommandErrorEvent>,
     mut clear_events: MessageWriter<ConsoleClearEvent>,
 ) {
-    for output in pending.outputs.drain(..) {
+    for output in pending.outputs.take() {
         output_events.write(output);
     }
     for change in pending.changes.drain(..) {
         change_events.write(change);
     }
-    if pending.clear_console {
-        pending.clear_console = false;
-        clear_events.write(ConsoleClearEvent);
+    for execution in pending.executions.drain(..) {
+        execution_events.write(execution);
+    }
+    for error in pending.errors.drain(..) {
+        error_events.write(error);
+    }
+    for clear in pending.clears.drain(..) {
+        clear_events.write(clear);
     }
 }
 
+/// System that records output events in the [`ConsoleOutputBuffer`].
+fn buffer_console_output(
+    mut output_events: MessageReader<ConsoleOutputEvent>,
+    mut clear_events: MessageReader<ConsoleClearEvent>,
+    mut buffer: ResMut<ConsoleOutputBuffer>,
+) {
+    // The buffer has no levels, so filtered clears leave it alone
+    if clear_events.read().any(|clear| clear.filter.is_none()) {
+        buffer.clear();
+    }
+    for output in output_events.read() {
+        buffer.push(&output.message);
+    }
+}
 
 // Integration tests run without egui feature since MinimalPlugins doesn't provide
 // the resources that egui UI systems require (ButtonInput, etc.)
@@ -748,6 +1252,7 @@ fn send_pending_outputs(
 #[cfg(all(test, not(feature = "egui")))]
 mod tests {
     use super::*;
+    use crate::builtins::help_lines;
 
     /// Test resource to track command execution.
     #[derive(Resource, Default)]
@@ -764,12 +1269,53 @@ mod tests {
             let tokens = tokenize(cmd_str).expect("Failed to tokenize test command");
             let mut pending = app.world_mut().resource_mut::<PendingCommands>();
             pending.outputs.push(ConsoleOutputEvent::command(format!("$ {}", cmd_str)));
-            pending.queue.push(QueuedCommand {
-                raw: cmd_str.to_string(),
-                name: tokens.command.to_string(),
-                args: tokens.args.iter().map(|s| s.to_string()).collect(),
-            });
+            pending.enqueue(QueuedCommand::new(&tokens, CommandSource::Programmatic, 0));
+        }
+    }
+
+    /// Helper to run `f`, returning the messages it logs on this thread.
+    fn capture_logs(f: impl FnOnce()) -> Vec<String> {
+        use std::sync::{Arc, Mutex};
+        use bevy::log::tracing::{field::Field, Event, Subscriber};
+        use bevy::log::tracing_subscriber::{field::Visit, layer::{Context, SubscriberExt}, Layer, Registry};
+
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        impl<S: Subscriber> Layer<S> for Capture {
+            fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+                struct Message<'a>(&'a Mutex<Vec<String>>);
+                impl Visit for Message<'_> {
+                    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                        if field.name() == "message" {
+                            self.0.lock().unwrap().push(format!("{value:?}"));
+                        }
+                    }
+                }
+                event.record(&mut Message(&self.0));
+            }
         }
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = Registry::default().with(Capture(messages.clone()));
+        bevy::log::tracing::subscriber::with_default(subscriber, f);
+        messages.lock().unwrap().clone()
+    }
+
+    /// Messages of type `M` collected by [`collect_messages`].
+    #[derive(Resource)]
+    struct CollectedMessages<M: Message>(Vec<M>);
+
+    /// Helper to collect every `M` message written from now on, for [`take_messages`].
+    pub(crate) fn collect_messages<M: Message + Clone>(app: &mut App) {
+        app.insert_resource(CollectedMessages::<M>(Vec::new()));
+        app.add_systems(Last, |mut messages: MessageReader<M>, mut collected: ResMut<CollectedMessages<M>>| {
+            collected.0.extend(messages.read().cloned());
+        });
+    }
+
+    /// Helper to take the `M` messages collected since the last call.
+    pub(crate) fn take_messages<M: Message>(app: &mut App) -> Vec<M> {
+        std::mem::take(&mut app.world_mut().resource_mut::<CollectedMessages<M>>().0)
     }
 
     #[test]
@@ -778,7 +1324,7 @@ mod tests {
 
         // Add minimal plugins needed for the test
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Register a test command that increments a counter
         app.init_resource::<TestCommandExecuted>();
@@ -810,11 +1356,103 @@ mod tests {
         assert_eq!(tracker.last_args, vec!["arg1", "arg2"]);
     }
 
+    #[test]
+    fn test_handler_registered_via_console() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut console: Console| {
+            console.register_cmd(ConCommand::new("my_cmd", |_args, _world| {}));
+        });
+
+        app.update();
+
+        let handlers = app.world().resource::<CommandHandlers>();
+        assert!(handlers.iter_names().any(|name| name == "my_cmd"));
+
+        // Built-in commands are registered the same way
+        assert!(handlers.iter_names().any(|name| name == "help"));
+    }
+
+    #[test]
+    fn test_console_ref_writes() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32));
+            registry.register_var(ConVar::new("sv_hostname", String::from("server")));
+            registry.register_var(ConVar::new("sv_maps", Vec::<String>::new()));
+        });
+        app.add_systems(Update, (|console: ConsoleRef| {
+            if console.get::<i32>("sv_gravity") == Some(800) {
+                console.set_deferred("sv_gravity", 400i32);
+                console.set_deferred("sv_hostname", String::from("my \"server\"; quit // x"));
+                console.set_deferred("sv_maps", vec![String::from("de dust"), String::from("a\\b")]);
+            }
+        }).before(ConsoleSet::ParseInput));
+        app.update();
+        app.update();
+        {
+            let registry = app.world().resource::<ConsoleRegistry>();
+            assert_eq!(registry.get::<i32>("sv_gravity"), Some(400));
+            // The string is stored whole instead of being run as `quit`
+            assert_eq!(registry.get_string("sv_hostname").as_deref(), Some("my \"server\"; quit // x"));
+            assert_eq!(
+                registry.get::<Vec<String>>("sv_maps"),
+                Some(vec![String::from("de dust"), String::from("a\\b")])
+            );
+        }
+
+        let doubled = Console::scope(app.world_mut(), |console| {
+            let gravity: i32 = console.get("sv_gravity").unwrap();
+            console.set("sv_gravity", gravity * 2);
+            gravity * 2
+        });
+        assert_eq!(doubled, 800);
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity"), Some(800));
+    }
+
+    #[test]
+    fn test_input_observed_by_multiple_readers() {
+        #[derive(Resource, Default)]
+        struct Seen(Vec<&'static str>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<Seen>();
+        app.add_systems(Last, (
+            |mut events: MessageReader<ConsoleInputEvent>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|_| "first"));
+            },
+            |mut events: MessageReader<ConsoleInputEvent>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|_| "second"));
+            },
+        ).chain());
+
+        app.update();
+        let start = app.world().resource::<ConsoleInputEventLog>().frame();
+        app.world_mut().write_message(ConsoleInputEvent::new("echo shared"));
+        app.update();
+        app.update();
+
+        // Each reader has its own cursor, and the pipeline still executed the command
+        assert_eq!(app.world().resource::<Seen>().0, ["first", "second"]);
+        assert_eq!(app.world().resource::<CommandStats>().total_executed, 1);
+
+        let log = app.world().resource::<ConsoleInputEventLog>();
+        let commands: Vec<_> = log.since(start).map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, ["echo shared"]);
+    }
+
     #[test]
     fn test_convar_get_set_via_input() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Register a test variable
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
@@ -846,11 +1484,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_convar_set_case_insensitive() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        });
+
+        app.update();
+
+        // Wrong case should resolve to the registered name
+        queue_command(&mut app, "SV_GRAVITY 1000");
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        assert_eq!(registry.get::<f32>("sv_gravity"), Some(1000.0));
+    }
+
     #[test]
     fn test_builtin_echo_command() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Run startup (registers built-in commands)
         app.update();
@@ -865,11 +1523,47 @@ mod tests {
         // but if we got here without panicking, the command executed
     }
 
+    #[test]
+    fn test_builtin_find_filters() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32)
+                .description("World gravity")
+                .flags(ConVarFlags::ARCHIVE));
+            registry.register_var(ConVar::new("sv_gravity_scale", 1i32).description("Multiplier for gravity"));
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("gravity_reset", |_args, _world| {}).description("Reset gravity"));
+        });
+        app.update();
+
+        let mut find = |cmd: &str| {
+            queue_command(&mut app, cmd);
+            capture_logs(|| app.update())
+        };
+
+        assert_eq!(find("find sv_grav"), [
+            "[cmd] set - Set a convar; numeric convars accept arithmetic like 'sv_gravity+100'",
+            "[var] sv_gravity - World gravity",
+            "[var] sv_gravity_scale - Multiplier for gravity",
+            "3 results",
+        ]);
+        assert_eq!(find("find --type var --flag archive --verbose sv_grav"), [
+            "[var] sv_gravity = \"800\" - World gravity",
+            "1 results",
+        ]);
+        assert_eq!(find("find --type cmd gravity_"), ["[cmd] gravity_reset - Reset gravity", "1 results"]);
+        assert_eq!(find("find --description world"), ["[var] sv_gravity - World gravity", "1 results"]);
+        assert_eq!(find("find --type bogus grav"), ["Unknown type 'bogus', expected 'var' or 'cmd'"]);
+        assert_eq!(find("find --flag bogus grav"), ["Unknown flag 'bogus'"]);
+    }
+
     #[test]
     fn test_multiple_commands_semicolon() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
         app.init_resource::<TestCommandExecuted>();
 
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
@@ -899,7 +1593,7 @@ mod tests {
     fn test_convar_changed_event() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
             registry.register_var(ConVar::new("test_var", 10i32));
@@ -924,7 +1618,7 @@ mod tests {
     fn test_builtin_help_command() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Run startup (registers built-in commands)
         app.update();
@@ -943,7 +1637,7 @@ mod tests {
     fn test_builtin_toggle_command() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
             registry.register_var(ConVar::new("test_bool", false));
@@ -983,7 +1677,7 @@ mod tests {
     fn test_builtin_reset_command() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
             registry.register_var(ConVar::new("test_var", 42i32));
@@ -1013,12 +1707,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builtin_set_arithmetic() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32).min(0).max(1000));
+            registry.register_var(ConVar::new("host_timescale", 0.5f32));
+            registry.register_var(ConVar::new("sv_hostname", String::from("server")));
+        });
+        app.update();
+
+        queue_command(&mut app, "set sv_gravity sv_gravity+100");
+        queue_command(&mut app, "set host_timescale (host_timescale + 1) * 3 / 2");
+        queue_command(&mut app, "set sv_hostname my server+1");
+        app.update();
+        {
+            let registry = app.world().resource::<ConsoleRegistry>();
+            assert_eq!(registry.get::<i32>("sv_gravity"), Some(900));
+            assert_eq!(registry.get::<f32>("host_timescale"), Some(2.25));
+            assert_eq!(registry.get_string("sv_hostname").as_deref(), Some("my server+1"));
+        }
+
+        // Results are clamped to the range, and errors leave the value alone
+        queue_command(&mut app, "set sv_gravity sv_gravity * 2");
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity"), Some(1000));
+        queue_command(&mut app, "set sv_gravity 1 / (sv_gravity - 1000)");
+        queue_command(&mut app, "set sv_gravity sv_hostname");
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity"), Some(1000));
+
+        // Results that don't fit the type are rejected rather than cast
+        queue_command(&mut app, "set sv_gravity 7 / 2");
+        queue_command(&mut app, "set sv_gravity 0 - 3000000000");
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity"), Some(1000));
+        assert!(format_numeric(9.1e15, "i64").unwrap().is_err());
+        assert!(format_numeric(1e39, "f32").unwrap().is_err());
+        assert_eq!(format_numeric(-4.0, "i64"), Some(Ok("-4".to_string())));
+        assert_eq!(format_numeric(1.0, "String"), None);
+    }
+
     #[cfg(feature = "persist")]
     #[test]
     fn test_alias_expansion() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
         app.init_resource::<TestCommandExecuted>();
 
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
@@ -1054,12 +1792,74 @@ mod tests {
         assert_eq!(tracker.last_args, vec!["arg1", "arg2"], "Args should be passed through");
     }
 
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_alias_placeholder_expansion() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("test_cmd", |args, world| {
+                    let mut tracker = world.resource_mut::<TestCommandExecuted>();
+                    tracker.count += 1;
+                    tracker.last_args = args.iter().map(|s| s.to_string()).collect();
+                })
+            );
+        });
+
+        app.update();
+
+        queue_command(&mut app, r#"alias swap "test_cmd $2 $1 ($#)""#);
+        app.update();
+
+        queue_command(&mut app, "swap a b c");
+        app.update();
+        app.update();
+
+        let tracker = app.world().resource::<TestCommandExecuted>();
+        assert_eq!(tracker.count, 1);
+        assert_eq!(tracker.last_args, vec!["b", "a", "(3)"]);
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_recursive_alias_stops() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<CommandErrorEvent>(&mut app);
+        collect_messages::<CommandExecutionEvent>(&mut app);
+
+        app.update();
+
+        queue_command(&mut app, "alias ping pong");
+        queue_command(&mut app, "alias pong ping");
+        queue_command(&mut app, "ping");
+        for _ in 0..persist::MAX_ALIAS_DEPTH + 2 {
+            app.update();
+        }
+
+        let errors = take_messages::<CommandErrorEvent>(&mut app);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, CommandError::AliasDepthExceeded);
+
+        // Every expansion reports, the last one with the depth error
+        let events = take_messages::<CommandExecutionEvent>(&mut app);
+        assert_eq!(events.len(), 2 + persist::MAX_ALIAS_DEPTH + 1);
+        assert!(events[2..events.len() - 1].iter().all(|e| e.outcome == CommandOutcome::Success));
+        assert_eq!(events.last().unwrap().outcome, CommandOutcome::AliasDepthExceeded);
+        assert!(app.world().resource::<PendingCommands>().queue.is_empty());
+    }
+
     #[cfg(feature = "persist")]
     #[test]
     fn test_alias_command() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Run startup (registers built-in commands including alias)
         app.update();
@@ -1089,7 +1889,7 @@ mod tests {
     fn test_cheat_enforcement() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Register a cheat-protected variable
         app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
@@ -1144,7 +1944,7 @@ mod tests {
     fn test_permission_enforcement() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
         app.init_resource::<TestCommandExecuted>();
 
         // Register an admin-only command
@@ -1199,7 +1999,7 @@ mod tests {
     fn test_combined_cheat_and_permission() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
         app.init_resource::<TestCommandExecuted>();
 
         // Register a command that requires BOTH cheats AND admin permission
@@ -1251,7 +2051,7 @@ mod tests {
     fn test_sv_cheats_requires_admin() {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
-        app.add_plugins(ConsolePlugin);
+        app.add_plugins(ConsolePlugin::default());
 
         // Run startup
         app.update();
@@ -1288,4 +2088,1087 @@ mod tests {
             assert_eq!(registry.get::<i32>("sv_cheats"), Some(1));
         }
     }
+
+    #[test]
+    fn test_pause_holds_commands_until_resumed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("track", |args, world| {
+                    let mut tracker = world.resource_mut::<TestCommandExecuted>();
+                    tracker.count += 1;
+                    tracker.last_args.extend(args.iter().map(|s| s.to_string()));
+                })
+            );
+        });
+
+        app.update();
+
+        app.world_mut().write_message(ConsolePauseEvent::pause());
+        app.world_mut().write_message(ConsoleInputEvent::new("track a"));
+        app.update();
+        app.world_mut().write_message(ConsoleInputEvent::new("track b; track c"));
+        // Run enough frames for unread messages to have been dropped
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert!(app.world().resource::<ConsolePaused>().0);
+        assert_eq!(app.world().resource::<TestCommandExecuted>().count, 0);
+
+        app.world_mut().write_message(ConsolePauseEvent::resume());
+        app.world_mut().write_message(ConsoleInputEvent::new("track d"));
+        app.update();
+
+        let tracker = app.world().resource::<TestCommandExecuted>();
+        assert_eq!(tracker.count, 4);
+        assert_eq!(tracker.last_args, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_execution_event_outcomes() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("explode", |_args, _world| panic!("boom"))
+            );
+            registry.register_var(ConVar::new("sv_gravity", 800i32));
+        });
+        collect_messages::<CommandExecutionEvent>(&mut app);
+
+        app.update();
+
+        queue_command(&mut app, "echo hi");
+        app.update();
+        let events = take_messages::<CommandExecutionEvent>(&mut app);
+        assert_eq!(events.len(), 1);
+        assert_eq!(&*events[0].name, "echo");
+        assert_eq!(events[0].args, vec!["hi"]);
+        assert_eq!(events[0].outcome, CommandOutcome::Success);
+
+        queue_command(&mut app, "does_not_exist 1");
+        app.update();
+        let events = take_messages::<CommandExecutionEvent>(&mut app);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].outcome, CommandOutcome::NotFound);
+
+        queue_command(&mut app, "explode");
+        app.update();
+        let events = take_messages::<CommandExecutionEvent>(&mut app);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].outcome, CommandOutcome::Panicked("boom".into()));
+
+        // Variable reads and writes report too
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+        queue_command(&mut app, "sv_cheats");
+        queue_command(&mut app, "sv_cheats 1");
+        queue_command(&mut app, "sv_gravity lots");
+        queue_command(&mut app, "sv_gravity 600");
+        app.update();
+        let events: Vec<_> = take_messages::<CommandExecutionEvent>(&mut app).into_iter().map(|e| (e.name, e.outcome)).collect();
+        assert_eq!(events, vec![
+            ("sv_cheats".into(), CommandOutcome::Success),
+            ("sv_cheats".into(), CommandOutcome::PermissionDenied),
+            ("sv_gravity".into(), CommandOutcome::InvalidArguments),
+            ("sv_gravity".into(), CommandOutcome::Success),
+        ]);
+    }
+
+    #[test]
+    fn test_telemetry_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(ConsoleTelemetry {
+            on_command_executed: Some(Box::new(move |event| sink.lock().unwrap().push(event))),
+        });
+        app.update();
+
+        queue_command(&mut app, "echo a b");
+        queue_command(&mut app, "does_not_exist");
+        app.update();
+
+        let received = received.lock().unwrap();
+        assert_eq!(*received, vec![
+            CommandTelemetryEvent { name: "echo".into(), arg_count: 2, source: CommandSource::Programmatic, success: true },
+            CommandTelemetryEvent { name: "does_not_exist".into(), arg_count: 0, source: CommandSource::Programmatic, success: false },
+        ]);
+    }
+
+    #[test]
+    fn test_convar_snapshot_updated_after_commands() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut commands: Commands| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32));
+            commands.insert_resource(registry.make_snapshot());
+        });
+        app.update();
+
+        let snapshot = app.world().resource::<ConVarSnapshot>().clone();
+        assert_eq!(snapshot.get("sv_gravity").as_deref(), Some("800"));
+
+        queue_command(&mut app, "sv_gravity 400");
+        app.update();
+        assert_eq!(snapshot.get("sv_gravity").as_deref(), Some("400"));
+    }
+
+    #[test]
+    fn test_command_error_events() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("noclip", |_args, _world| {}).flags(ConVarFlags::CHEAT)
+            );
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("kick", |_args, _world| {}).permission(PermissionLevel::Admin)
+            );
+        });
+        collect_messages::<CommandErrorEvent>(&mut app);
+
+        app.update();
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+
+        queue_command(&mut app, "noclip");
+        queue_command(&mut app, "kick player");
+        queue_command(&mut app, "missing");
+        app.update();
+
+        let errors = take_messages::<CommandErrorEvent>(&mut app);
+        let errors: Vec<_> = errors.iter().map(|e| (&*e.command_name, e.error.clone())).collect();
+        assert_eq!(errors, vec![
+            ("noclip", CommandError::CheatRequired),
+            ("kick", CommandError::PermissionDenied),
+            ("missing", CommandError::Unknown),
+        ]);
+    }
+
+    /// Test resource collecting observed f32 changes.
+    #[derive(Resource, Default)]
+    struct RecordedGravity(Vec<(String, f32, f32)>);
+
+    #[test]
+    fn test_typed_convar_changed_event() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800.0f32).flags(ConVarFlags::NOTIFY));
+            registry.register_var(ConVar::new("sv_friction", 4.0f32));
+        });
+        collect_messages::<TypedConVarChangedEvent<f32>>(&mut app);
+
+        app.update();
+
+        queue_command(&mut app, "sv_gravity 1000.5");
+        // Not flagged NOTIFY, so no typed event
+        queue_command(&mut app, "sv_friction 6");
+        app.update();
+
+        let recorded: Vec<_> = take_messages::<TypedConVarChangedEvent<f32>>(&mut app)
+            .into_iter()
+            .map(|e| (e.name.to_string(), e.old, e.new))
+            .collect();
+        assert_eq!(recorded, [("sv_gravity".to_string(), 800.0, 1000.5)]);
+    }
+
+    #[test]
+    fn test_convar_change_observer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, (|mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800.0f32).flags(ConVarFlags::NOTIFY));
+            registry.register_var(ConVar::new("sv_friction", 4.0f32));
+        }).in_set(ConsoleSet::Register));
+        app.init_resource::<RecordedGravity>();
+        app.add_systems(Startup, (|mut commands: Commands| {
+            commands.observe_convar("sv_gravity", |change: On<ConVarChange<f32>>, mut recorded: ResMut<RecordedGravity>| {
+                recorded.0.push((change.name.to_string(), change.old, change.new));
+            });
+            // Not flagged NOTIFY, so it gets no entity
+            commands.observe_convar("sv_friction", |_: On<ConVarChange<f32>>| {});
+        }).after(ConsoleSet::Register));
+
+        app.update();
+        let registry = app.world().resource::<ConsoleRegistry>();
+        let entity = |name| match registry.get_entry(name) {
+            Some(ConEntry::Var(meta)) => meta.entity,
+            _ => None,
+        };
+        assert!(entity("sv_gravity").is_some());
+        assert!(entity("sv_friction").is_none());
+
+        queue_command(&mut app, "sv_gravity 1000.5");
+        queue_command(&mut app, "set sv_gravity 900");
+        app.update();
+
+        let recorded = &app.world().resource::<RecordedGravity>().0;
+        assert_eq!(recorded, &vec![
+            ("sv_gravity".to_string(), 800.0, 1000.5),
+            ("sv_gravity".to_string(), 1000.5, 900.0),
+        ]);
+
+        // Setters outside the command pipeline notify too
+        app.world_mut().resource_mut::<RecordedGravity>().0.clear();
+        app.add_systems(Update, (|mut console: Console, mut done: Local<bool>| {
+            if !std::mem::replace(&mut *done, true) {
+                console.set("sv_gravity", 500.0f32);
+            }
+        }).before(ConsoleSet::ParseInput));
+        app.update();
+        queue_command(&mut app, "reset sv_gravity");
+        app.update();
+
+        let recorded = &app.world().resource::<RecordedGravity>().0;
+        assert_eq!(recorded, &vec![
+            ("sv_gravity".to_string(), 900.0, 500.0),
+            ("sv_gravity".to_string(), 500.0, 800.0),
+        ]);
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_exec_input_has_script_source() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "// comment").unwrap();
+        writeln!(file, "echo one").unwrap();
+        writeln!(file, "echo two").unwrap();
+        let path = file.path().to_string_lossy().into_owned();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleInputEvent>(&mut app);
+
+        app.update();
+
+        queue_command(&mut app, &format!("exec \"{}\"", path));
+        app.update();
+
+        let recorded = take_messages::<ConsoleInputEvent>(&mut app);
+        let commands: Vec<_> = recorded.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["echo one", "echo two"]);
+        for event in recorded {
+            assert_eq!(event.source, CommandSource::Script { file: path.as_str().into() });
+        }
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_source_uses_search_path_only() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("script.ron"), "echo sourced").unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleInputEvent>(&mut app);
+
+        app.update();
+        app.world_mut().resource_mut::<persist::ExecSearchPath>().dirs = vec![dir.path().into()];
+
+        queue_command(&mut app, "source script");
+        // Exists in the working directory, but not in the search path
+        queue_command(&mut app, "source Cargo.toml");
+        app.update();
+
+        let recorded = take_messages::<ConsoleInputEvent>(&mut app);
+        let commands: Vec<_> = recorded.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["echo sourced"]);
+        assert_eq!(recorded[0].source, CommandSource::Script { file: "script".into() });
+    }
+
+    #[test]
+    fn test_convar_change_log_outlives_events() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32));
+        });
+        app.update();
+
+        queue_command(&mut app, "sv_gravity 400");
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let log = app.world().resource::<ConVarChangeLog>();
+        let changes: Vec<_> = log.changes_for("sv_gravity").collect();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old_value, "800");
+        assert_eq!(changes[0].new_value, "400");
+    }
+
+    #[test]
+    fn test_priority_commands_run_first() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers, ConCommand::new("mark", |args, world| {
+                let mut executed = world.resource_mut::<TestCommandExecuted>();
+                executed.count += 1;
+                executed.last_args.extend(args.iter().map(String::from));
+            }));
+        });
+        app.update();
+
+        app.world_mut().write_message(ConsoleInputEvent::new("mark a; mark b"));
+        app.world_mut().write_message(ConsoleInputEvent::new("mark urgent").with_priority(255));
+        app.world_mut().write_message(ConsoleInputEvent::new("mark c"));
+        app.world_mut().write_message(ConsoleInputEvent::new("mark soon").with_priority(1));
+        app.update();
+
+        let executed = app.world().resource::<TestCommandExecuted>();
+        assert_eq!(executed.last_args, ["urgent", "soon", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_streaming_command_output() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers, ConCommand::new_streaming("scan", |args, world, output| {
+                output.emit(ConsoleOutputEvent::info("step 1"));
+                // Emitted output is pending before the handler returns
+                assert!(!world.resource::<PendingCommands>().outputs.is_empty());
+                output.emit(ConsoleOutputEvent::info("step 2"));
+                if args.get(0) == Some("fail") {
+                    panic!("disk error");
+                }
+                output.emit(ConsoleOutputEvent::result("done"));
+            }));
+        });
+        collect_messages::<ConsoleOutputEvent>(&mut app);
+        app.update();
+
+        queue_command(&mut app, "scan");
+        queue_command(&mut app, "scan fail");
+        app.update();
+
+        let output = take_messages::<ConsoleOutputEvent>(&mut app);
+        let messages: Vec<_> = output.iter()
+            .map(|e| e.message.as_str())
+            .filter(|m| !m.starts_with('$'))
+            .collect();
+        assert_eq!(messages, [
+            "step 1", "step 2", "done",
+            "step 1", "step 2", "Command 'scan' panicked: disk error",
+        ]);
+    }
+
+    #[test]
+    fn test_async_command_output() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers, ConCommand::new_async("fetch", |args, _world| {
+                let url = args.get(0).unwrap_or_default().to_string();
+                async move {
+                    std::thread::sleep(std::time::Duration::ZERO);
+                    vec![ConsoleOutputEvent::result(format!("fetched {}", url))]
+                }
+            }));
+        });
+        collect_messages::<ConsoleOutputEvent>(&mut app);
+        app.update();
+
+        queue_command(&mut app, "fetch example.com");
+        app.update();
+
+        // The task runs on another thread, so it may take a few frames
+        for _ in 0..100 {
+            if app.world().resource::<PendingAsyncCommands>().is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+
+        assert!(app.world().resource::<PendingAsyncCommands>().is_empty());
+        let recorded = take_messages::<ConsoleOutputEvent>(&mut app);
+        assert_eq!(recorded.last().unwrap().message, "fetched example.com");
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_cvar_dump_json() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32)
+                .description("World gravity")
+                .flags(ConVarFlags::ARCHIVE | ConVarFlags::NOTIFY));
+            registry.register_var(ConVar::new("sv_password", String::new()).flags(ConVarFlags::HIDDEN));
+        });
+        collect_messages::<ConsoleOutputEvent>(&mut app);
+        app.update();
+
+        queue_command(&mut app, "sv_gravity 400");
+        queue_command(&mut app, "cvar_dump");
+        app.update();
+
+        let recorded = take_messages::<ConsoleOutputEvent>(&mut app);
+        let begin = recorded.iter().position(|e| e.message == "BEGIN_JSON").unwrap();
+        assert_eq!(recorded[begin + 2].message, "END_JSON");
+        assert_eq!(recorded[begin + 1].level, ConsoleOutputLevel::Result);
+
+        let json: serde_json::Value = serde_json::from_str(&recorded[begin + 1].message).unwrap();
+        let vars = json.as_array().unwrap();
+        assert!(vars.iter().all(|var| var["name"] != "sv_password"));
+        let gravity = vars.iter().find(|var| var["name"] == "sv_gravity").unwrap();
+        assert_eq!(gravity["type"], "i32");
+        assert_eq!(gravity["value"], "400");
+        assert_eq!(gravity["default"], "800");
+        assert_eq!(gravity["flags"], serde_json::json!(["ARCHIVE", "NOTIFY"]));
+        assert_eq!(gravity["description"], "World gravity");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cvars.json");
+        queue_command(&mut app, &format!("cvar_dump --file \"{}\"", path.display()));
+        app.update();
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, json);
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_config_applies_to_vars_registered_in_register_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.ron");
+        let mut config = ConsoleConfigFile::new();
+        config.convars.insert("sv_gravity".into(), "400".into());
+        config.save(&path).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(ConfigPath(path.display().to_string()));
+        app.add_systems(Startup, (|mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32).flags(ConVarFlags::ARCHIVE));
+        }).in_set(ConsoleSet::Register));
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        assert_eq!(registry.get_string("sv_gravity").as_deref(), Some("400"));
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_help_shows_help_text() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        let lines = help_lines("alias", registry.get_entry("alias").unwrap(), &ConsoleLocalization::default());
+        assert_eq!(lines[0], "alias - Create or list command aliases");
+        assert_eq!(lines[1], "");
+        assert_eq!(lines[2], "Usage:");
+        assert!(lines.iter().any(|l| l.contains("alias greet")));
+
+        // Without help text only the description is shown
+        let lines = help_lines("echo", registry.get_entry("echo").unwrap(), &ConsoleLocalization::default());
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn test_deprecated_command_warns_and_runs() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("old_cmd", |_args, world| {
+                    world.resource_mut::<TestCommandExecuted>().count += 1;
+                })
+                .description("Old command")
+                .deprecated("new_cmd")
+            );
+        });
+        collect_messages::<ConsoleOutputEvent>(&mut app);
+
+        app.update();
+        queue_command(&mut app, "old_cmd");
+        app.update();
+
+        assert_eq!(app.world().resource::<TestCommandExecuted>().count, 1);
+        let recorded = take_messages::<ConsoleOutputEvent>(&mut app);
+        assert!(recorded.iter().any(|e| e.level == ConsoleOutputLevel::Warn
+            && e.message == "'old_cmd' is deprecated, use 'new_cmd' instead"));
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        let lines = help_lines("old_cmd", registry.get_entry("old_cmd").unwrap(), &ConsoleLocalization::default());
+        assert_eq!(lines, vec!["old_cmd - Old command", "[DEPRECATED: use new_cmd]"]);
+    }
+
+    #[test]
+    fn test_command_schedule_fixed_update() {
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::new().command_schedule(FixedUpdate));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("test_var", 1i32));
+        });
+
+        app.update();
+        queue_command(&mut app, "test_var 2");
+
+        // No fixed step has elapsed yet
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("test_var"), Some(1));
+
+        let timestep = app.world().resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("test_var"), Some(2));
+    }
+
+    #[test]
+    fn test_console_set_ordering() {
+        #[derive(Resource, Default)]
+        struct SeenGravity(Vec<f32>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<SeenGravity>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800.0f32));
+        });
+        app.add_systems(Update, (|registry: Res<ConsoleRegistry>, mut seen: ResMut<SeenGravity>| {
+            seen.0.push(registry.get::<f32>("sv_gravity").unwrap());
+        }).after(ConsoleSet::ExecuteCommands));
+
+        app.update();
+        app.world_mut().write_message(ConsoleInputEvent::new("sv_gravity 1000"));
+        app.update();
+
+        assert_eq!(app.world().resource::<SeenGravity>().0, vec![800.0, 1000.0]);
+    }
+
+    #[test]
+    fn test_queue_depth_drop() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::new().max_queue_depth(2, OverflowPolicy::Drop));
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("track", |args, world| {
+                    let mut tracker = world.resource_mut::<TestCommandExecuted>();
+                    tracker.count += 1;
+                    tracker.last_args.extend(args.iter().map(|s| s.to_string()));
+                })
+            );
+        });
+        collect_messages::<CommandErrorEvent>(&mut app);
+
+        app.update();
+        app.world_mut().write_message(ConsoleInputEvent::new("track a; track b; track c"));
+        app.update();
+        app.update();
+
+        let tracker = app.world().resource::<TestCommandExecuted>();
+        assert_eq!(tracker.last_args, vec!["a", "b"]);
+        let errors = take_messages::<CommandErrorEvent>(&mut app);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((&*errors[0].command_name, &errors[0].error), ("track", &CommandError::QueueFull));
+    }
+
+    #[test]
+    fn test_queue_depth_defer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::new().max_queue_depth(2, OverflowPolicy::DeferToNextFrame));
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("track", |args, world| {
+                    let mut tracker = world.resource_mut::<TestCommandExecuted>();
+                    tracker.count += 1;
+                    tracker.last_args.extend(args.iter().map(|s| s.to_string()));
+                })
+            );
+        });
+
+        app.update();
+        app.world_mut().write_message(ConsoleInputEvent::new("track a; track b; track c"));
+        app.world_mut().write_message(ConsoleInputEvent::new("track d"));
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a", "b"]);
+
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_command_stats() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("kick", |_args, _world| {}).permission(PermissionLevel::Admin)
+            );
+        });
+
+        app.update();
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+
+        queue_command(&mut app, "echo a; echo b; kick; missing");
+        app.update();
+
+        let stats = app.world().resource::<CommandStats>();
+        assert_eq!(stats.total_executed, 2);
+        assert_eq!(stats.counts.get("echo"), Some(&2));
+        assert_eq!(stats.total_errors, 2);
+        assert_eq!(stats.total_permission_denials, 1);
+
+        queue_command(&mut app, "stats reset");
+        app.update();
+        // The reset itself is recorded after clearing
+        let stats = app.world().resource::<CommandStats>();
+        assert_eq!(stats.total_executed, 1);
+        assert_eq!(stats.total_errors, 0);
+    }
+
+    #[test]
+    fn test_command_budget_defers_remaining() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(CommandBudget::new(std::time::Duration::from_millis(2)));
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("slow", |args, world| {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                    let mut tracker = world.resource_mut::<TestCommandExecuted>();
+                    tracker.count += 1;
+                    tracker.last_args.extend(args.iter().map(|s| s.to_string()));
+                })
+            );
+        });
+
+        app.update();
+        queue_command(&mut app, "slow a; slow b; slow c");
+
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a"]);
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a", "b"]);
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a", "b", "c"]);
+
+        assert_eq!(app.world().resource::<CommandStats>().budget_exceeded_count, 2);
+    }
+
+    #[test]
+    fn test_command_budget_zero_still_progresses() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(CommandBudget::new(std::time::Duration::ZERO));
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("track", |args, world| {
+                    world.resource_mut::<TestCommandExecuted>().last_args.extend(args.iter().map(|s| s.to_string()));
+                })
+            );
+        });
+
+        app.update();
+        queue_command(&mut app, "track a; track b");
+
+        // One command per frame
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a"]);
+        app.update();
+        assert_eq!(app.world().resource::<TestCommandExecuted>().last_args, vec!["a", "b"]);
+        assert_eq!(app.world().resource::<CommandStats>().budget_exceeded_count, 1);
+    }
+
+    #[test]
+    fn test_help_lists_string_list_items() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_admins", Vec::<String>::new()));
+        });
+
+        app.update();
+        queue_command(&mut app, "sv_admins alice bob");
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        let lines = help_lines("sv_admins", registry.get_entry("sv_admins").unwrap(), &ConsoleLocalization::default());
+        assert_eq!(&lines[lines.len() - 3..], ["  Items (2):", "    alice", "    bob"]);
+    }
+
+    #[test]
+    fn test_output_buffer_records_and_clears() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.update();
+        queue_command(&mut app, "echo hello");
+        queue_command(&mut app, "missing_cmd");
+        app.update();
+
+        let buffer = app.world().resource::<ConsoleOutputBuffer>();
+        let matches = buffer.grep("MISSING", false, 0);
+        assert_eq!(matches[0].text, "$ [missing]_cmd");
+        assert_eq!(buffer.grep("echo", false, 0).len(), 1);
+
+        queue_command(&mut app, "clear");
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ConsoleOutputBuffer>().grep("hello", false, 0).len(), 0);
+    }
+
+    #[test]
+    fn test_clear_errors_keeps_buffer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleClearEvent>(&mut app);
+
+        app.update();
+        queue_command(&mut app, "echo hello");
+        app.update();
+        queue_command(&mut app, "clear_errors");
+        app.update();
+        app.update();
+
+        let recorded = take_messages::<ConsoleClearEvent>(&mut app);
+        assert_eq!(recorded, &[ConsoleClearEvent::level(ConsoleOutputLevel::Error)]);
+        assert_eq!(app.world().resource::<ConsoleOutputBuffer>().grep("hello", false, 0).len(), 1);
+    }
+
+    #[test]
+    fn test_clear_level_argument() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleClearEvent>(&mut app);
+
+        app.update();
+        for command in ["clear warn", "clear all", "clear", "clear loud"] {
+            queue_command(&mut app, command);
+            app.update();
+        }
+
+        let recorded = take_messages::<ConsoleClearEvent>(&mut app);
+        assert_eq!(recorded, &[
+            ConsoleClearEvent::level(ConsoleOutputLevel::Warn),
+            ConsoleClearEvent::all(),
+            ConsoleClearEvent::all(),
+        ]);
+    }
+
+    #[test]
+    fn test_quit_clean_requests_app_exit() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.update();
+        assert_eq!(app.should_exit(), None);
+
+        queue_command(&mut app, "quit_clean");
+        app.update();
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+    }
+
+    #[test]
+    fn test_tee_command_writes_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.log");
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+
+        app.update();
+        queue_command(&mut app, &format!("tee \"{}\"", path.display()));
+        app.update();
+        queue_command(&mut app, "echo after");
+        app.update();
+        queue_command(&mut app, "tee stop");
+        app.update();
+        queue_command(&mut app, "echo ignored");
+        app.update();
+
+        assert!(!app.world().resource::<TeeState>().is_active());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("$ echo after"));
+        assert!(!contents.contains("ignored"));
+    }
+
+    #[test]
+    fn test_audit_log_records_access() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.csv");
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.update();
+
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+        queue_command(&mut app, "echo a b; audit_clear; missing; sv_cheats 1");
+        app.update();
+
+        let log = app.world().resource::<ConsoleAuditLog>();
+        let records: Vec<_> = log.iter().map(|r| (&*r.command_name, r.allowed, r.args_count)).collect();
+        assert_eq!(records, [("echo", true, 2), ("audit_clear", false, 0), ("sv_cheats", false, 1)]);
+        assert_eq!(log.iter().nth(1).unwrap().required_permission, PermissionLevel::Server);
+
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::Server;
+        queue_command(&mut app, &format!("audit_export \"{}\"", path.display()));
+        app.update();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.lines().nth(2).unwrap().ends_with(",audit_clear,Server,User,false,0"));
+
+        queue_command(&mut app, "audit_clear");
+        app.update();
+        // Only the audit_clear invocation itself remains
+        assert_eq!(app.world().resource::<ConsoleAuditLog>().len(), 1);
+
+        // Commands after one that lowers the level are audited at the new level
+        app.world_mut().resource_scope(|world, mut registry: Mut<ConsoleRegistry>| {
+            register_cmd(&mut registry, &mut world.resource_mut::<CommandHandlers>(),
+                ConCommand::new("logout", |_args, world| {
+                    world.resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+                })
+            );
+        });
+        queue_command(&mut app, "logout; audit_clear");
+        app.update();
+        let records: Vec<_> = app.world().resource::<ConsoleAuditLog>().iter()
+            .skip(1)
+            .map(|r| (&*r.command_name, r.actual_permission, r.allowed))
+            .collect();
+        assert_eq!(records, [("logout", PermissionLevel::Server, true), ("audit_clear", PermissionLevel::User, false)]);
+    }
+
+    #[test]
+    fn test_macro_record_and_playback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("saved.cfg");
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleInputEvent>(&mut app);
+
+        app.update();
+        for command in ["record demo", "echo one", "echo two; echo three", "record stop"] {
+            app.world_mut().write_message(ConsoleInputEvent::new(command));
+            app.update();
+        }
+
+        let macros = app.world().resource::<ConsoleMacros>();
+        let recorded: Vec<_> = macros.get("demo").unwrap().commands().map(String::from).collect();
+        assert_eq!(recorded, vec!["echo one", "echo two; echo three"]);
+
+        take_messages::<ConsoleInputEvent>(&mut app);
+        queue_command(&mut app, "playback demo");
+        app.update();
+        app.update();
+
+        let replayed = take_messages::<ConsoleInputEvent>(&mut app);
+        let commands: Vec<_> = replayed.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["echo one", "echo two; echo three"]);
+        assert_eq!(replayed[0].source, CommandSource::Macro { name: "demo".into() });
+
+        // Save, then load back under the file's name
+        queue_command(&mut app, &format!("macro_save demo \"{}\"", path.display()));
+        app.update();
+        queue_command(&mut app, &format!("macro_load \"{}\"", path.display()));
+        app.update();
+        let macros = app.world().resource::<ConsoleMacros>();
+        let loaded: Vec<_> = macros.get("saved").unwrap().commands().map(String::from).collect();
+        assert_eq!(loaded, recorded);
+    }
+
+    #[test]
+    fn test_remote_input_rate_limited() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("inc", |_args, world| {
+                    world.resource_mut::<TestCommandExecuted>().count += 1;
+                })
+            );
+        });
+        app.update();
+
+        for _ in 0..DEFAULT_REMOTE_INPUTS_PER_SECOND + 5 {
+            app.world_mut().write_message(ConsoleInputEvent::new("inc").with_source(CommandSource::Remote));
+        }
+        // Local input is not limited by default
+        for _ in 0..20 {
+            app.world_mut().write_message(ConsoleInputEvent::new("inc").with_source(CommandSource::Ui));
+        }
+        app.update();
+
+        assert_eq!(app.world().resource::<TestCommandExecuted>().count, DEFAULT_REMOTE_INPUTS_PER_SECOND + 20);
+        assert_eq!(app.world().resource::<CommandStats>().total_errors, 5);
+    }
+
+    #[test]
+    fn test_whitelist_blocks_commands() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<CommandErrorEvent>(&mut app);
+        app.update();
+
+        app.world_mut().resource_mut::<ConsoleWhitelist>().allow("echo");
+        queue_command(&mut app, "sandbox_mode on");
+        app.update();
+        assert!(app.world().resource::<ConsoleWhitelist>().enabled);
+
+        queue_command(&mut app, "echo allowed");
+        queue_command(&mut app, "quit");
+        app.update();
+
+        let errors = take_messages::<CommandErrorEvent>(&mut app);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(&*errors[0].command_name, "quit");
+        assert_eq!(errors[0].error, CommandError::NotWhitelisted);
+
+        // sandbox_mode itself is never blocked
+        queue_command(&mut app, "sandbox_mode off");
+        app.update();
+        assert!(!app.world().resource::<ConsoleWhitelist>().enabled);
+    }
+
+    #[test]
+    fn test_sandbox_commands() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<CommandErrorEvent>(&mut app);
+        app.update();
+
+        queue_command(&mut app, "sandbox_mode on");
+        queue_command(&mut app, "sandbox_allow differences");
+        queue_command(&mut app, "sandbox_deny echo");
+        app.update();
+        let whitelist = app.world().resource::<ConsoleWhitelist>();
+        assert!(whitelist.is_allowed("differences"));
+        assert!(!whitelist.is_allowed("echo"));
+        assert!(whitelist.is_allowed("help"));
+
+        // Users can't change sandbox mode
+        queue_command(&mut app, "sandbox_mode off");
+        app.update();
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+        queue_command(&mut app, "sandbox_mode on");
+        app.update();
+        assert!(!app.world().resource::<ConsoleWhitelist>().enabled);
+        let errors = take_messages::<CommandErrorEvent>(&mut app);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, CommandError::PermissionDenied);
+    }
+
+    #[test]
+    fn test_schema_rejects_invalid_args() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.init_resource::<TestCommandExecuted>();
+
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>, mut handlers: ResMut<CommandHandlers>| {
+            register_cmd(&mut registry, &mut handlers,
+                ConCommand::new("spawn", |_args, world| {
+                    world.resource_mut::<TestCommandExecuted>().count += 1;
+                }).schema(&[
+                    ArgDef { name: "entity", ty: ArgType::String, required: true, description: "Entity to spawn" },
+                    ArgDef { name: "count", ty: ArgType::Int, required: false, description: "" },
+                ])
+            );
+        });
+        app.update();
+
+        let help = help_lines("spawn", app.world().resource::<ConsoleRegistry>().get_entry("spawn").unwrap(), &ConsoleLocalization::default());
+        assert_eq!(help[1], "Usage: spawn <entity:String> [count:Int]");
+        assert_eq!(help[2], "  entity  Entity to spawn");
+
+        queue_command(&mut app, "spawn");
+        queue_command(&mut app, "spawn crate many");
+        queue_command(&mut app, "spawn crate 3");
+        app.update();
+
+        assert_eq!(app.world().resource::<TestCommandExecuted>().count, 1);
+        assert_eq!(app.world().resource::<CommandStats>().total_errors, 2);
+    }
+
+    #[test]
+    fn test_help_markdown() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        collect_messages::<ConsoleOutputEvent>(&mut app);
+        app.update();
+
+        queue_command(&mut app, "help --markdown echo");
+        app.update();
+
+        let recorded = take_messages::<ConsoleOutputEvent>(&mut app);
+        let markdown = recorded.iter().find(|e| e.message.starts_with("## echo")).unwrap();
+        assert_eq!(markdown.level, ConsoleOutputLevel::Result);
+    }
 }
@@ -8,6 +8,9 @@ use tracing::Subscriber;
 use tracing_subscriber::field::Visit;
 use tracing_subscriber::Layer;
 
+use crate::core::ConsoleOutputLevel;
+use crate::ui::{COMMAND_MESSAGE_NAME, COMMAND_RESULT_NAME, COMMAND_SUCCESS_NAME};
+
 /// A function that implements the log reading functionality for the
 /// developer console via [`LogPlugin::custom_layer`](bevy::log::LogPlugin::custom_layer).
 pub fn custom_log_layer(app: &mut App) -> Option<BoxedLayer> {
@@ -57,8 +60,23 @@ pub struct LogMessage {
     pub time: SystemTime,
 }
 
+impl LogMessage {
+    /// The console output level this message is shown as.
+    pub fn output_level(&self) -> ConsoleOutputLevel {
+        match (self.name, self.level) {
+            (COMMAND_SUCCESS_NAME, _) => ConsoleOutputLevel::Success,
+            (COMMAND_MESSAGE_NAME, _) => ConsoleOutputLevel::Command,
+            (COMMAND_RESULT_NAME, _) => ConsoleOutputLevel::Result,
+            (_, Level::ERROR) => ConsoleOutputLevel::Error,
+            (_, Level::WARN) => ConsoleOutputLevel::Warn,
+            (_, Level::INFO) => ConsoleOutputLevel::Info,
+            _ => ConsoleOutputLevel::Debug,
+        }
+    }
+}
+
 /// Transfers information from the [`CapturedLogEvents`] resource to [`MessageWriter<LogMessage>`](LogMessage).
-fn transfer_log_events(
+pub(crate) fn transfer_log_events(
     receiver: NonSend<CapturedLogEvents>,
     mut log_events: MessageWriter<LogMessage>,
 ) {
@@ -69,7 +87,7 @@ fn transfer_log_events(
 
 /// This struct temporarily stores [`LogMessage`]s before they are
 /// written to [`MessageWriter<LogMessage>`] by [`transfer_log_events`].
-struct CapturedLogEvents(mpsc::Receiver<LogMessage>);
+pub(crate) struct CapturedLogEvents(mpsc::Receiver<LogMessage>);
 
 /// A [`Layer`] that captures log events and saves them to [`CapturedLogEvents`].
 struct LogCaptureLayer {
@@ -0,0 +1,266 @@
+//! Recording and playback of console command macros.
+//!
+//! `record <name>` captures every [`ConsoleInputEvent`] into a named macro in
+//! [`ConsoleMacros`] until `record stop`; `playback <name>` submits the
+//! commands again. Macro files are plain command scripts, so they also run
+//! with `exec`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+
+use crate::core::{CommandSource, ConsoleInputEvent};
+
+/// Line prefix storing the delay before the next command in a macro file.
+const DELAY_PREFIX: &str = "# delay ";
+
+/// One recorded command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroStep {
+    /// The raw command line.
+    pub command: String,
+    /// Time since the previous command (or the start of recording).
+    pub delay: Duration,
+}
+
+/// A named sequence of recorded commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsoleMacro {
+    /// Commands in the order they were submitted.
+    pub steps: Vec<MacroStep>,
+}
+
+impl ConsoleMacro {
+    /// The recorded command lines.
+    pub fn commands(&self) -> impl Iterator<Item = &str> {
+        self.steps.iter().map(|step| step.command.as_str())
+    }
+
+    /// Render as a script, one command per line with `# delay <ms>` comments.
+    pub fn to_script(&self) -> String {
+        let mut out = String::new();
+        for step in &self.steps {
+            if !step.delay.is_zero() {
+                out.push_str(&format!("{}{}\n", DELAY_PREFIX, step.delay.as_millis()));
+            }
+            out.push_str(&step.command);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Parse a script written by [`to_script`](Self::to_script).
+    ///
+    /// Blank lines and other `//` or `#` comments are skipped, as with `exec`.
+    pub fn from_script(script: &str) -> Self {
+        let mut steps = Vec::new();
+        let mut delay = Duration::ZERO;
+        for line in script.lines() {
+            let line = line.trim();
+            if let Some(ms) = line.strip_prefix(DELAY_PREFIX).and_then(|ms| ms.trim().parse().ok()) {
+                delay = Duration::from_millis(ms);
+                continue;
+            }
+            if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+                continue;
+            }
+            steps.push(MacroStep { command: line.to_string(), delay });
+            delay = Duration::ZERO;
+        }
+        Self { steps }
+    }
+
+    /// Write the macro to `path` as a script.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_script())
+    }
+
+    /// Read a macro script from `path`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path).map(|script| Self::from_script(&script))
+    }
+}
+
+/// Macro being captured by `record`.
+#[derive(Debug)]
+struct Recording {
+    name: String,
+    steps: Vec<MacroStep>,
+    last: Instant,
+}
+
+/// Resource holding recorded macros and the recording/playback state.
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleMacros {
+    macros: BTreeMap<String, ConsoleMacro>,
+    recording: Option<Recording>,
+    /// Steps still to be submitted, with the macro they came from.
+    playback: VecDeque<(Box<str>, MacroStep)>,
+    /// When the previous playback step was submitted.
+    playback_clock: Option<Instant>,
+}
+
+impl ConsoleMacros {
+    /// Get a macro by name.
+    pub fn get(&self, name: &str) -> Option<&ConsoleMacro> {
+        self.macros.get(name)
+    }
+
+    /// Store a macro, replacing any with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, console_macro: ConsoleMacro) {
+        self.macros.insert(name.into(), console_macro);
+    }
+
+    /// Remove a macro.
+    pub fn remove(&mut self, name: &str) -> Option<ConsoleMacro> {
+        self.macros.remove(name)
+    }
+
+    /// Iterate over macros, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ConsoleMacro)> {
+        self.macros.iter().map(|(name, m)| (name.as_str(), m))
+    }
+
+    /// Start capturing input into the macro `name`, discarding any unfinished recording.
+    pub fn start_recording(&mut self, name: impl Into<String>) {
+        self.recording = Some(Recording {
+            name: name.into(),
+            steps: Vec::new(),
+            last: Instant::now(),
+        });
+    }
+
+    /// Stop capturing and store the macro.
+    ///
+    /// Returns the macro name and number of recorded commands.
+    pub fn stop_recording(&mut self) -> Option<(String, usize)> {
+        let recording = self.recording.take()?;
+        let count = recording.steps.len();
+        self.macros.insert(recording.name.clone(), ConsoleMacro { steps: recording.steps });
+        Some((recording.name, count))
+    }
+
+    /// Name of the macro being recorded.
+    pub fn recording(&self) -> Option<&str> {
+        self.recording.as_ref().map(|r| r.name.as_str())
+    }
+
+    /// Queue the macro `name` for playback.
+    ///
+    /// Commands are submitted all at once, or with their recorded delays if
+    /// `timed` is set. Returns `false` if there is no such macro.
+    pub fn play(&mut self, name: &str, timed: bool) -> bool {
+        let Some(console_macro) = self.macros.get(name) else {
+            return false;
+        };
+        let source: Box<str> = name.into();
+        for step in &console_macro.steps {
+            let delay = if timed { step.delay } else { Duration::ZERO };
+            self.playback.push_back((source.clone(), MacroStep { command: step.command.clone(), delay }));
+        }
+        self.playback_clock.get_or_insert_with(Instant::now);
+        true
+    }
+
+    /// Whether a macro is still being played back.
+    pub fn is_playing(&self) -> bool {
+        !self.playback.is_empty()
+    }
+
+    /// Drop all queued playback steps.
+    pub fn stop_playback(&mut self) {
+        self.playback.clear();
+        self.playback_clock = None;
+    }
+}
+
+/// System that adds submitted commands to the macro being recorded.
+pub(crate) fn record_macro_input(
+    mut events: MessageReader<ConsoleInputEvent>,
+    mut macros: ResMut<ConsoleMacros>,
+) {
+    let Some(recording) = &mut macros.recording else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        // Played-back commands are already captured by the `playback` line
+        let is_record = event.command.split_whitespace().next() == Some("record");
+        if is_record || matches!(event.source, CommandSource::Macro { .. }) {
+            continue;
+        }
+        let now = Instant::now();
+        recording.steps.push(MacroStep {
+            command: event.command.clone(),
+            delay: now - recording.last,
+        });
+        recording.last = now;
+    }
+}
+
+/// System that submits macro playback steps once their delay has passed.
+pub(crate) fn play_macro_steps(
+    mut macros: ResMut<ConsoleMacros>,
+    mut events: MessageWriter<ConsoleInputEvent>,
+) {
+    let Some(mut clock) = macros.playback_clock else {
+        return;
+    };
+
+    let now = Instant::now();
+    while let Some((_, step)) = macros.playback.front() {
+        if now < clock + step.delay {
+            break;
+        }
+        clock = now;
+        let (name, step) = macros.playback.pop_front().unwrap();
+        events.write(ConsoleInputEvent::new(step.command).with_source(CommandSource::Macro { name }));
+    }
+
+    macros.playback_clock = if macros.playback.is_empty() { None } else { Some(clock) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macro_script_round_trip() {
+        let console_macro = ConsoleMacro {
+            steps: vec![
+                MacroStep { command: "sv_gravity 400".into(), delay: Duration::ZERO },
+                MacroStep { command: "echo \"done; really\"".into(), delay: Duration::from_millis(250) },
+            ],
+        };
+
+        let script = console_macro.to_script();
+        assert_eq!(script, "sv_gravity 400\n# delay 250\necho \"done; really\"\n");
+        assert_eq!(ConsoleMacro::from_script(&script), console_macro);
+
+        let parsed = ConsoleMacro::from_script("// header\n\n# note\necho hi\n");
+        assert_eq!(parsed.commands().collect::<Vec<_>>(), vec!["echo hi"]);
+    }
+
+    #[test]
+    fn test_macro_record_and_play() {
+        let mut macros = ConsoleMacros::default();
+        assert!(!macros.play("missing", false));
+
+        macros.start_recording("demo");
+        assert_eq!(macros.recording(), Some("demo"));
+        assert_eq!(macros.stop_recording(), Some(("demo".into(), 0)));
+        assert_eq!(macros.recording(), None);
+        assert!(macros.get("demo").is_some());
+
+        macros.insert("demo", ConsoleMacro::from_script("echo a\necho b"));
+        assert!(macros.play("demo", false));
+        assert!(macros.is_playing());
+        macros.stop_playback();
+        assert!(!macros.is_playing());
+    }
+}
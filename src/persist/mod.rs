@@ -4,16 +4,19 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::ConsoleRegistry;
+use crate::core::{split_commands, tokenize, ConVarFlags, ConsoleRegistry};
 
 /// Default config file name.
 pub const DEFAULT_CONFIG_FILE: &str = "console.ron";
 
+/// Maximum number of alias expansions followed when running or tracing a chain.
+pub const MAX_ALIAS_DEPTH: usize = 16;
+
 /// Serializable console configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ConsoleConfigFile {
@@ -23,6 +26,25 @@ pub struct ConsoleConfigFile {
     /// Command aliases (alias -> command).
     #[serde(default)]
     pub aliases: HashMap<String, String>,
+    /// Name of the active UI theme preset.
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Whether the UI wraps long output lines.
+    #[serde(default)]
+    pub word_wrap: Option<bool>,
+    /// Name of the key that opens the UI, e.g. `"F1"`.
+    #[serde(default)]
+    pub open_key: Option<String>,
+    /// Top-left corner of the UI window.
+    #[serde(default)]
+    pub window_pos: Option<[f32; 2]>,
+    /// Size of the UI window.
+    #[serde(default)]
+    pub window_size: Option<[f32; 2]>,
+    /// Which log levels the UI shows.
+    #[cfg(feature = "egui")]
+    #[serde(default)]
+    pub log_filter: Option<crate::ui::LogFilter>,
 }
 
 impl ConsoleConfigFile {
@@ -140,6 +162,104 @@ impl CommandAliases {
     pub fn is_empty(&self) -> bool {
         self.aliases.is_empty()
     }
+
+    /// Trace how `input` expands without executing anything.
+    ///
+    /// Each step expands the alias at the start of the command string, keeping
+    /// any commands after the first `;`. Stops when the first command is not an
+    /// alias, a previous expansion repeats, or [`MAX_ALIAS_DEPTH`] is reached.
+    pub fn trace(&self, input: &str) -> (Vec<AliasStep>, AliasTraceEnd) {
+        let mut steps: Vec<AliasStep> = Vec::new();
+        let mut seen = vec![input.to_string()];
+        let mut current = input.to_string();
+
+        loop {
+            let commands = split_commands(&current);
+            let Some(tokens) = commands.first().and_then(|first| tokenize(first).ok()) else {
+                return (steps, AliasTraceEnd::Resolved);
+            };
+            let Some(template) = self.get(tokens.command) else {
+                return (steps, AliasTraceEnd::Resolved);
+            };
+            if steps.len() == MAX_ALIAS_DEPTH {
+                return (steps, AliasTraceEnd::DepthLimit);
+            }
+
+            let args: Vec<String> = tokens.args.iter().map(|s| s.to_string()).collect();
+            let mut expanded = expand_alias(template, &args);
+            for rest in &commands[1..] {
+                expanded.push_str("; ");
+                expanded.push_str(rest);
+            }
+
+            let cycle = seen.contains(&expanded);
+            steps.push(AliasStep { from: current, to: expanded.clone() });
+            if cycle {
+                return (steps, AliasTraceEnd::Cycle);
+            }
+            seen.push(expanded.clone());
+            current = expanded;
+        }
+    }
+}
+
+/// A single expansion in an alias trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasStep {
+    /// Command string before expansion.
+    pub from: String,
+    /// Command string after expansion.
+    pub to: String,
+}
+
+/// Why an alias trace stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasTraceEnd {
+    /// The first command is not an alias.
+    Resolved,
+    /// The last expansion repeated an earlier one.
+    Cycle,
+    /// [`MAX_ALIAS_DEPTH`] expansions were followed.
+    DepthLimit,
+}
+
+/// Expand an alias command string with the given arguments.
+///
+/// `$1`..`$9` are replaced by the matching argument (empty if missing), `$@` by
+/// all arguments joined with spaces and `$#` by the argument count. Other `$x`
+/// sequences are left as-is. Templates without placeholders get the arguments
+/// appended instead.
+pub fn expand_alias(template: &str, args: &[String]) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut substituted = false;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some(&d @ '1'..='9') => {
+                let index = d as usize - '1' as usize;
+                expanded.push_str(args.get(index).map(String::as_str).unwrap_or(""));
+            }
+            Some('@') => expanded.push_str(&args.join(" ")),
+            Some('#') => expanded.push_str(&args.len().to_string()),
+            _ => {
+                expanded.push('$');
+                continue;
+            }
+        }
+        chars.next();
+        substituted = true;
+    }
+
+    if !substituted && !args.is_empty() {
+        expanded.push(' ');
+        expanded.push_str(&args.join(" "));
+    }
+    expanded
 }
 
 /// Resource tracking the config file path.
@@ -152,6 +272,56 @@ impl Default for ConfigPath {
     }
 }
 
+/// Extension tried when a script name is given without one.
+pub const SCRIPT_EXTENSION: &str = "ron";
+
+/// Resource listing directories searched by `exec` for bare script names.
+#[derive(Resource, Debug, Clone)]
+pub struct ExecSearchPath {
+    /// Directories searched in order.
+    pub dirs: Vec<PathBuf>,
+}
+
+impl Default for ExecSearchPath {
+    fn default() -> Self {
+        Self {
+            dirs: vec![".".into(), "cfg".into(), "configs".into()],
+        }
+    }
+}
+
+impl ExecSearchPath {
+    /// Add a directory to the end of the search path.
+    pub fn push(&mut self, path: impl Into<PathBuf>) {
+        self.dirs.push(path.into());
+    }
+
+    /// Resolve a script name to an existing file.
+    ///
+    /// Names with a directory component are used as-is. Bare names are tried
+    /// in each directory, first unchanged and then with [`SCRIPT_EXTENSION`].
+    pub fn resolve(&self, name: &str) -> Option<PathBuf> {
+        let path = Path::new(name);
+        if path.parent().is_some_and(|parent| !parent.as_os_str().is_empty()) {
+            return path.is_file().then(|| path.to_path_buf());
+        }
+
+        self.dirs.iter().find_map(|dir| {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            if candidate.extension().is_none() {
+                let candidate = candidate.with_extension(SCRIPT_EXTENSION);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+            None
+        })
+    }
+}
+
 /// Extract ARCHIVE convars from registry into a config.
 pub fn extract_archive_convars(registry: &ConsoleRegistry) -> ConsoleConfigFile {
     let mut config = ConsoleConfigFile::new();
@@ -179,6 +349,8 @@ pub fn load_config_on_startup(
     mut registry: ResMut<ConsoleRegistry>,
     mut aliases: ResMut<CommandAliases>,
     config_path: Res<ConfigPath>,
+    #[cfg(feature = "egui")] ui_config: Option<ResMut<crate::config::ConsoleConfig>>,
+    #[cfg(feature = "egui")] ui_state: Option<ResMut<crate::ui::ConsoleUiState>>,
 ) {
     let path = &config_path.0;
 
@@ -198,6 +370,39 @@ pub fn load_config_on_startup(
                 debug!("Loaded alias: {} -> {}", name, command);
             }
 
+            // Restore the UI theme and open key
+            #[cfg(feature = "egui")]
+            if let Some(mut ui_config) = ui_config {
+                if let Some(name) = &config.theme
+                    && !ui_config.set_theme_by_name(name)
+                {
+                    warn!("Unknown console theme '{}'", name);
+                }
+                if let Some(name) = &config.open_key {
+                    match crate::config::parse_key_code(name) {
+                        Some(key) => ui_config.open_key = key,
+                        None => warn!("Unknown console key '{}'", name),
+                    }
+                }
+            }
+            #[cfg(feature = "egui")]
+            if let Some(mut ui_state) = ui_state {
+                if let Some(word_wrap) = config.word_wrap {
+                    ui_state.word_wrap = word_wrap;
+                }
+                if let Some(log_filter) = config.log_filter {
+                    ui_state.log_filter = log_filter;
+                }
+                if let Some([x, y]) = config.window_pos {
+                    ui_state.window_pos = Some(bevy_egui::egui::pos2(x, y));
+                }
+                if let Some([width, height]) = config.window_size {
+                    ui_state.width = width;
+                    ui_state.height = height;
+                    ui_state.window_size = Some(bevy_egui::egui::vec2(width, height));
+                }
+            }
+
             info!("Loaded {} convars and {} aliases",
                 config.convars.len(), config.aliases.len());
         }
@@ -207,13 +412,41 @@ pub fn load_config_on_startup(
     }
 }
 
-/// Save current ARCHIVE convars to file.
+/// UI settings written by [`save_config`]. `None` fields are left out of the file.
+#[derive(Debug, Clone, Default)]
+pub struct UiSettings<'a> {
+    /// Name of the active theme preset.
+    pub theme_name: Option<&'a str>,
+    /// Whether long output lines wrap.
+    pub word_wrap: Option<bool>,
+    /// Name of the key that opens the UI.
+    pub open_key: Option<&'a str>,
+    /// Top-left corner of the UI window.
+    pub window_pos: Option<[f32; 2]>,
+    /// Size of the UI window's content.
+    pub window_size: Option<[f32; 2]>,
+    /// Which log levels the UI shows.
+    #[cfg(feature = "egui")]
+    pub log_filter: Option<crate::ui::LogFilter>,
+}
+
+/// Save current ARCHIVE convars, aliases, and UI settings to file.
 pub fn save_config(
     registry: &ConsoleRegistry,
     aliases: &CommandAliases,
+    ui: UiSettings,
     path: impl AsRef<Path>,
 ) -> Result<(), ConfigError> {
     let mut config = extract_archive_convars(registry);
+    config.theme = ui.theme_name.map(str::to_string);
+    config.word_wrap = ui.word_wrap;
+    config.open_key = ui.open_key.map(str::to_string);
+    config.window_pos = ui.window_pos;
+    config.window_size = ui.window_size;
+    #[cfg(feature = "egui")]
+    {
+        config.log_filter = ui.log_filter;
+    }
 
     // Add aliases
     for (name, command) in aliases.iter() {
@@ -223,18 +456,240 @@ pub fn save_config(
     config.save(path)
 }
 
+/// A UI window position and content size as stored in [`ConsoleConfigFile`].
+#[cfg(feature = "egui")]
+type WindowLayout = (Option<[f32; 2]>, Option<[f32; 2]>);
+
+/// The UI window's position and size in the form stored in [`ConsoleConfigFile`].
+#[cfg(feature = "egui")]
+pub(crate) fn window_layout(state: &crate::ui::ConsoleUiState) -> WindowLayout {
+    (
+        state.window_pos.map(|pos| [pos.x, pos.y]),
+        state.window_size.map(|size| [size.x, size.y]),
+    )
+}
+
+/// How long the window layout has to stay unchanged before it is saved.
+#[cfg(feature = "egui")]
+pub(crate) const WINDOW_LAYOUT_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Layout tracking for [`save_window_layout`].
+#[cfg(feature = "egui")]
+#[derive(Default)]
+pub(crate) struct WindowLayoutSave {
+    /// The layout last written (or loaded at startup).
+    saved: Option<WindowLayout>,
+    /// A changed layout and the real time it was first seen.
+    pending: Option<(WindowLayout, std::time::Duration)>,
+}
+
+/// System that writes the UI window's position and size to the config file
+/// once the user has finished moving or resizing it.
+///
+/// A change is written after it has been stable for
+/// [`WINDOW_LAYOUT_SAVE_DELAY`], so dragging doesn't write every frame even
+/// without mouse input. Only the layout fields are rewritten; everything else
+/// in the file is kept. Added by
+/// [`ConsolePlugin::save_window_layout`](crate::ConsolePlugin::save_window_layout).
+#[cfg(feature = "egui")]
+pub(crate) fn save_window_layout(
+    ui_state: Res<crate::ui::ConsoleUiState>,
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    time: Res<Time<Real>>,
+    config_path: Res<ConfigPath>,
+    mut state: Local<WindowLayoutSave>,
+) {
+    let layout = window_layout(&ui_state);
+    let Some(saved) = state.saved else {
+        // The first layout seen is the one loaded at startup
+        state.saved = Some(layout);
+        return;
+    };
+    if saved == layout {
+        state.pending = None;
+        return;
+    }
+
+    // Restart the delay whenever the layout moves or the mouse is held
+    let now = time.elapsed();
+    let dragging = mouse.is_some_and(|mouse| mouse.pressed(MouseButton::Left));
+    match state.pending {
+        Some((pending, since)) if pending == layout && !dragging => {
+            if now.saturating_sub(since) < WINDOW_LAYOUT_SAVE_DELAY {
+                return;
+            }
+        }
+        _ => {
+            state.pending = Some((layout, now));
+            return;
+        }
+    }
+    state.saved = Some(layout);
+    state.pending = None;
+
+    let path = Path::new(&config_path.0);
+    let mut config = if path.exists() {
+        match ConsoleConfigFile::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Not saving console window layout: {}", e);
+                return;
+            }
+        }
+    } else {
+        ConsoleConfigFile::new()
+    };
+    (config.window_pos, config.window_size) = layout;
+    if let Err(e) = config.save(path) {
+        warn!("Failed to save console window layout: {}", e);
+    }
+}
+
+/// One ConVar in the `cvar_dump` JSON output.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConVarDump {
+    /// Variable name.
+    pub name: String,
+    /// Backing Rust type, without module paths.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Current value, formatted as the console shows it.
+    pub value: String,
+    /// Default value.
+    pub default: String,
+    /// Names of the set flags.
+    pub flags: Vec<&'static str>,
+    /// Description.
+    pub description: &'static str,
+}
+
+/// Collect all non-HIDDEN convars, sorted by name.
+pub fn dump_convars(registry: &ConsoleRegistry) -> Vec<ConVarDump> {
+    let mut dump: Vec<_> = registry
+        .vars()
+        .filter(|(_, meta)| !meta.flags.contains(ConVarFlags::HIDDEN))
+        .map(|(name, meta)| ConVarDump {
+            name: name.to_string(),
+            ty: crate::builtins::short_type_name(meta.type_name),
+            value: meta.get_string(),
+            default: meta.default_string(),
+            flags: meta.flags.names(),
+            description: meta.description,
+        })
+        .collect();
+    dump.sort_by(|a, b| a.name.cmp(&b.name));
+    dump
+}
+
+/// Render all non-HIDDEN convars as a pretty-printed JSON array.
+pub fn dump_convars_json(registry: &ConsoleRegistry) -> Result<String, ConfigError> {
+    serde_json::to_string_pretty(&dump_convars(registry))
+        .map_err(|e| ConfigError::Serialize(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expand_alias_positional() {
+        assert_eq!(expand_alias("echo Hello $1!", &args(&["World"])), "echo Hello World!");
+        // Missing arguments become empty
+        assert_eq!(expand_alias("echo $1 and $2", &args(&["a"])), "echo a and ");
+        // Extra arguments are dropped once placeholders are used
+        assert_eq!(expand_alias("echo $2", &args(&["a", "b", "c"])), "echo b");
+    }
+
+    #[test]
+    fn test_expand_alias_all_and_count() {
+        assert_eq!(expand_alias("echo $@", &args(&["a", "b", "c"])), "echo a b c");
+        assert_eq!(expand_alias("echo $# args", &args(&["a", "b"])), "echo 2 args");
+        // Unrecognized sequences are kept verbatim
+        assert_eq!(expand_alias("echo $x $0 $1 $", &args(&["a"])), "echo $x $0 a $");
+    }
+
+    #[test]
+    fn test_exec_search_path_resolve() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        fs::write(second.path().join("autoexec.ron"), "echo hi").unwrap();
+        fs::write(second.path().join("plain"), "echo hi").unwrap();
+        fs::write(first.path().join("plain"), "echo hi").unwrap();
+
+        let mut search = ExecSearchPath { dirs: vec![first.path().into()] };
+        assert_eq!(search.resolve("autoexec"), None);
+
+        search.push(second.path());
+        assert_eq!(search.resolve("autoexec"), Some(second.path().join("autoexec.ron")));
+        // Earlier directories win
+        assert_eq!(search.resolve("plain"), Some(first.path().join("plain")));
+        assert_eq!(search.resolve("missing"), None);
+
+        // Paths with a directory component bypass the search path
+        let literal = second.path().join("plain");
+        let literal = literal.to_str().unwrap();
+        assert_eq!(ExecSearchPath { dirs: vec![] }.resolve(literal), Some(literal.into()));
+    }
+
+    #[test]
+    fn test_alias_trace() {
+        let mut aliases = CommandAliases::new();
+        aliases.add("boom", "noclip; godmode");
+        aliases.add("go", "boom");
+
+        let (steps, end) = aliases.trace("go");
+        assert_eq!(end, AliasTraceEnd::Resolved);
+        assert_eq!(steps, vec![
+            AliasStep { from: "go".into(), to: "boom".into() },
+            AliasStep { from: "boom".into(), to: "noclip; godmode".into() },
+        ]);
+
+        let (steps, end) = aliases.trace("noclip");
+        assert!(steps.is_empty());
+        assert_eq!(end, AliasTraceEnd::Resolved);
+    }
+
+    #[test]
+    fn test_alias_trace_cycle() {
+        let mut aliases = CommandAliases::new();
+        aliases.add("a", "b");
+        aliases.add("b", "a");
+
+        let (steps, end) = aliases.trace("a");
+        assert_eq!(end, AliasTraceEnd::Cycle);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].to, "a");
+
+        // Growing expansions never repeat, so they hit the depth limit
+        aliases.add("grow", "grow x");
+        let (steps, end) = aliases.trace("grow");
+        assert_eq!(end, AliasTraceEnd::DepthLimit);
+        assert_eq!(steps.len(), MAX_ALIAS_DEPTH);
+    }
+
+    #[test]
+    fn test_expand_alias_without_placeholders() {
+        assert_eq!(expand_alias("test_cmd", &args(&["a", "b"])), "test_cmd a b");
+        assert_eq!(expand_alias("test_cmd", &[]), "test_cmd");
+    }
+
     #[test]
     fn test_config_file_roundtrip() {
         let mut config = ConsoleConfigFile::new();
         config.convars.insert("sv_gravity".to_string(), "800".to_string());
         config.convars.insert("sv_cheats".to_string(), "0".to_string());
         config.aliases.insert("quit".to_string(), "exit".to_string());
+        config.theme = Some("light".to_string());
+        config.word_wrap = Some(false);
+        config.open_key = Some("F1".to_string());
+        config.window_pos = Some([10.0, 20.0]);
+        config.window_size = Some([640.0, 300.0]);
 
         let temp = NamedTempFile::new().unwrap();
         config.save(temp.path()).unwrap();
@@ -243,6 +698,68 @@ mod tests {
         assert_eq!(loaded.convars.get("sv_gravity"), Some(&"800".to_string()));
         assert_eq!(loaded.convars.get("sv_cheats"), Some(&"0".to_string()));
         assert_eq!(loaded.aliases.get("quit"), Some(&"exit".to_string()));
+        assert_eq!(loaded.theme.as_deref(), Some("light"));
+        assert_eq!(loaded.word_wrap, Some(false));
+        assert_eq!(loaded.open_key.as_deref(), Some("F1"));
+        assert_eq!(loaded.window_pos, Some([10.0, 20.0]));
+        assert_eq!(loaded.window_size, Some([640.0, 300.0]));
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn test_save_window_layout_keeps_other_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.ron");
+        let mut config = ConsoleConfigFile::new();
+        config.convars.insert("sv_gravity".to_string(), "400".to_string());
+        config.save(&path).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(ConfigPath(path.display().to_string()));
+        world.init_resource::<crate::ui::ConsoleUiState>();
+        world.init_resource::<Time<Real>>();
+        let mut system = IntoSystem::into_system(save_window_layout);
+        system.initialize(&mut world);
+
+        // The startup layout is not written back
+        system.run((), &mut world).unwrap();
+        assert_eq!(ConsoleConfigFile::load(&path).unwrap().window_pos, None);
+
+        // A moving window is only written once it has settled
+        let advance = |world: &mut World, ms: u64| {
+            let mut time = world.resource_mut::<Time<Real>>();
+            time.update_with_duration(std::time::Duration::from_millis(ms));
+        };
+        for x in [1.0, 2.0, 5.0] {
+            world.resource_mut::<crate::ui::ConsoleUiState>().window_pos = Some(bevy_egui::egui::pos2(x, 6.0));
+            system.run((), &mut world).unwrap();
+            advance(&mut world, 100);
+        }
+        system.run((), &mut world).unwrap();
+        assert_eq!(ConsoleConfigFile::load(&path).unwrap().window_pos, None);
+
+        advance(&mut world, WINDOW_LAYOUT_SAVE_DELAY.as_millis() as u64);
+        system.run((), &mut world).unwrap();
+        let loaded = ConsoleConfigFile::load(&path).unwrap();
+        assert_eq!(loaded.window_pos, Some([5.0, 6.0]));
+        assert_eq!(loaded.convars.get("sv_gravity").map(String::as_str), Some("400"));
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn test_config_file_log_filter_roundtrip() {
+        let mut config = ConsoleConfigFile::new();
+        config.log_filter = Some(crate::ui::LogFilter { show_debug: false, show_trace: false, ..default() });
+
+        let temp = NamedTempFile::new().unwrap();
+        config.save(temp.path()).unwrap();
+        assert_eq!(ConsoleConfigFile::load(temp.path()).unwrap().log_filter, config.log_filter);
+
+        // Filters saved by older versions may lack newer levels
+        let loaded: ConsoleConfigFile = ron::from_str("(log_filter: Some((show_info: false)))").unwrap();
+        let log_filter = loaded.log_filter.unwrap();
+        assert!(!log_filter.show_info);
+        assert!(log_filter.show_error);
     }
 
     #[test]
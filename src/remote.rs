@@ -0,0 +1,412 @@
+//! Remote console access over TCP for headless servers.
+//!
+//! Clients connect to [`RemoteConsoleConfig::bind_addr`] and send the password
+//! as the first line; the server answers `OK` or `DENIED`. After that every
+//! line is executed as a command and all console output is sent back.
+//!
+//! Output is handed to a writer thread per client through a bounded queue, so
+//! a client that stops reading is disconnected instead of stalling the game.
+//! Lines longer than [`MAX_LINE_LENGTH`] and connections beyond
+//! [`RemoteConsoleConfig::max_connections`] are dropped.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::core::{CommandSource, ConsoleInputEvent, ConsoleOutputEvent};
+
+/// Default address the remote console listens on.
+pub const DEFAULT_REMOTE_ADDR: &str = "127.0.0.1:27015";
+
+/// Default time a client has to send the password.
+pub const DEFAULT_AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default number of connections, authenticated or not, served at once.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 8;
+
+/// Longest line in bytes, password included, a client may send.
+pub const MAX_LINE_LENGTH: usize = 4096;
+
+/// Frames of output queued for a client before it is considered too slow.
+const CLIENT_QUEUE_SIZE: usize = 256;
+
+/// How long a write to a client may block its writer thread.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Plugin that accepts console commands over TCP.
+///
+/// Reads [`RemoteConsoleConfig`] at startup, so insert it before running the app.
+pub struct RemoteConsolePlugin;
+
+impl Plugin for RemoteConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RemoteConsoleConfig>()
+            .add_systems(Startup, start_remote_server)
+            .add_systems(Update, read_remote_commands.run_if(resource_exists::<RemoteConsoleServer>))
+            .add_systems(PostUpdate, write_remote_output.run_if(resource_exists::<RemoteConsoleServer>));
+    }
+}
+
+/// Configuration for the remote console.
+#[derive(Resource, Debug, Clone)]
+pub struct RemoteConsoleConfig {
+    /// Address to listen on.
+    pub bind_addr: SocketAddr,
+    /// Password clients must send first. An empty password rejects everyone.
+    pub password: String,
+    /// Time a client has to send the password before it is disconnected.
+    pub auth_timeout: Duration,
+    /// Connections served at once. Further connections are closed on accept.
+    pub max_connections: usize,
+}
+
+impl Default for RemoteConsoleConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: DEFAULT_REMOTE_ADDR.parse().unwrap(),
+            password: String::new(),
+            auth_timeout: DEFAULT_AUTH_TIMEOUT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+}
+
+/// Resource for a running remote console listener.
+#[derive(Resource)]
+pub struct RemoteConsoleServer {
+    local_addr: SocketAddr,
+    commands: Mutex<Receiver<String>>,
+    clients: Arc<Mutex<Vec<SyncSender<Arc<str>>>>>,
+}
+
+impl RemoteConsoleServer {
+    /// The address the listener is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of authenticated clients.
+    pub fn client_count(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+}
+
+fn start_remote_server(config: Res<RemoteConsoleConfig>, mut commands: Commands) {
+    let listener = match TcpListener::bind(config.bind_addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Remote console: failed to bind {}: {}", config.bind_addr, e);
+            return;
+        }
+    };
+    let local_addr = listener.local_addr().unwrap_or(config.bind_addr);
+    info!("Remote console listening on {}", local_addr);
+
+    let (sender, receiver) = mpsc::channel();
+    let clients = Arc::new(Mutex::new(Vec::new()));
+    spawn_listener(listener, sender, clients.clone(), config.clone());
+
+    commands.insert_resource(RemoteConsoleServer {
+        local_addr,
+        commands: Mutex::new(receiver),
+        clients,
+    });
+}
+
+fn spawn_listener(
+    listener: TcpListener,
+    sender: Sender<String>,
+    clients: Arc<Mutex<Vec<SyncSender<Arc<str>>>>>,
+    config: RemoteConsoleConfig,
+) {
+    let active = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if active.fetch_add(1, Ordering::SeqCst) >= config.max_connections {
+                active.fetch_sub(1, Ordering::SeqCst);
+                warn!("Remote console: connection limit reached, closing new connection");
+                let _ = stream.shutdown(Shutdown::Both);
+                continue;
+            }
+            let slot = ConnectionSlot(active.clone());
+            let sender = sender.clone();
+            let clients = clients.clone();
+            let config = config.clone();
+            thread::spawn(move || {
+                handle_client(stream, sender, clients, &config);
+                drop(slot);
+            });
+        }
+    });
+}
+
+/// Frees a connection slot when the client thread ends.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Read one line of at most [`MAX_LINE_LENGTH`] bytes, without the newline.
+///
+/// Returns `Ok(None)` at end of stream and an error for oversized or non-UTF-8
+/// lines, so a client can't grow the buffer by never sending a newline.
+fn read_line(reader: &mut impl BufRead, buf: &mut Vec<u8>) -> io::Result<Option<String>> {
+    buf.clear();
+    let read = reader.take(MAX_LINE_LENGTH as u64 + 1).read_until(b'\n', buf)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+    } else if read > MAX_LINE_LENGTH {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long"));
+    }
+    String::from_utf8(std::mem::take(buf))
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn handle_client(
+    mut stream: TcpStream,
+    sender: Sender<String>,
+    clients: Arc<Mutex<Vec<SyncSender<Arc<str>>>>>,
+    config: &RemoteConsoleConfig,
+) {
+    // Clones share the socket, so these timeouts apply to the reader and writer too
+    if stream.set_read_timeout(Some(config.auth_timeout)).is_err()
+        || stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    let authenticated = matches!(
+        read_line(&mut reader, &mut buf),
+        Ok(Some(line)) if !config.password.is_empty() && constant_time_eq(line.trim().as_bytes(), config.password.as_bytes())
+    );
+    if !authenticated {
+        let _ = stream.write_all(b"DENIED\n");
+        return;
+    }
+    // Authenticated clients may stay idle between commands
+    if stream.write_all(b"OK\n").is_err() || stream.set_read_timeout(None).is_err() {
+        return;
+    }
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let (output, queue) = mpsc::sync_channel(CLIENT_QUEUE_SIZE);
+    thread::spawn(move || write_client_output(writer, queue));
+    clients.lock().unwrap().push(output);
+
+    while let Ok(Some(line)) = read_line(&mut reader, &mut buf) {
+        let text = line.trim();
+        if !text.is_empty() && sender.send(text.to_string()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Writer thread for one client. Ends, closing the connection, when the
+/// client is dropped from the server or a write fails.
+fn write_client_output(mut stream: TcpStream, queue: Receiver<Arc<str>>) {
+    for text in queue {
+        if stream.write_all(text.as_bytes()).is_err() {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Compare without returning early, so the time taken doesn't reveal how much
+/// of the password matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = a.len() ^ b.len();
+    for (i, &byte) in a.iter().enumerate() {
+        diff |= usize::from(byte ^ b.get(i).copied().unwrap_or(0));
+    }
+    diff == 0
+}
+
+fn read_remote_commands(server: Res<RemoteConsoleServer>, mut events: MessageWriter<ConsoleInputEvent>) {
+    let rx = server.commands.lock().unwrap();
+    while let Ok(line) = rx.try_recv() {
+        events.write(ConsoleInputEvent::new(line).with_source(CommandSource::Remote));
+    }
+}
+
+fn write_remote_output(server: Res<RemoteConsoleServer>, mut events: MessageReader<ConsoleOutputEvent>) {
+    let mut clients = server.clients.lock().unwrap();
+    if clients.is_empty() {
+        events.clear();
+        return;
+    }
+
+    let mut text = String::new();
+    for event in events.read() {
+        text.push_str(&event.message);
+        text.push('\n');
+    }
+    if !text.is_empty() {
+        let text: Arc<str> = text.into();
+        // Disconnected clients and ones too far behind on reading are dropped
+        clients.retain(|client| match client.try_send(text.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                warn!("Remote console: disconnecting a client that stopped reading output");
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+}
+
+// Uses MinimalPlugins, which lacks the resources the egui UI systems require
+#[cfg(all(test, not(feature = "egui")))]
+mod tests {
+    use super::*;
+    use crate::{ConVar, ConsolePlugin, ConsoleRegistry};
+    use std::time::Duration;
+
+    fn connect(app: &App, password: &str) -> (TcpStream, BufReader<TcpStream>) {
+        let addr = app.world().resource::<RemoteConsoleServer>().local_addr();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        writeln!(stream, "{}", password).unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        (stream, reader)
+    }
+
+    fn is_timeout(e: &std::io::Error) -> bool {
+        matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut)
+    }
+
+    fn read_line(reader: &mut BufReader<TcpStream>) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    #[test]
+    fn test_remote_console_executes_commands() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(RemoteConsoleConfig {
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            password: "secret".into(),
+            ..default()
+        });
+        app.add_plugins(RemoteConsolePlugin);
+        app.add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+            registry.register_var(ConVar::new("sv_gravity", 800i32));
+        });
+        app.update();
+
+        let (_stream, mut reader) = connect(&app, "wrong");
+        assert_eq!(read_line(&mut reader), "DENIED");
+
+        let (mut stream, mut reader) = connect(&app, "secret");
+        assert_eq!(read_line(&mut reader), "OK");
+        writeln!(stream, "sv_gravity 400").unwrap();
+
+        // Wait for the client thread to forward the command
+        for _ in 0..500 {
+            app.update();
+            if app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity") == Some(400) {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.world().resource::<ConsoleRegistry>().get::<i32>("sv_gravity"), Some(400));
+        assert_eq!(read_line(&mut reader), "$ sv_gravity 400");
+        assert_eq!(read_line(&mut reader), "\"sv_gravity\" = \"400\"");
+    }
+
+    #[test]
+    fn test_remote_console_auth_timeout() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(RemoteConsoleConfig {
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            password: "secret".into(),
+            auth_timeout: Duration::from_millis(50),
+            ..default()
+        });
+        app.add_plugins(RemoteConsolePlugin);
+        app.update();
+
+        // Never sending the password gets the client turned away
+        let addr = app.world().resource::<RemoteConsoleServer>().local_addr();
+        let stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(read_line(&mut BufReader::new(stream)), "DENIED");
+
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secreT", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    #[test]
+    fn test_remote_console_limits() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(ConsolePlugin::default());
+        app.insert_resource(RemoteConsoleConfig {
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            password: "secret".into(),
+            max_connections: 1,
+            ..default()
+        });
+        app.add_plugins(RemoteConsolePlugin);
+        app.update();
+        let addr = app.world().resource::<RemoteConsoleServer>().local_addr();
+
+        // A password that never ends is cut off instead of buffered
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let _ = stream.write_all(&vec![b'a'; MAX_LINE_LENGTH * 4]);
+        let mut line = String::new();
+        match BufReader::new(&stream).read_line(&mut line) {
+            Ok(_) => assert!(line.is_empty() || line == "DENIED\n", "unexpected reply {:?}", line),
+            Err(e) => assert!(!is_timeout(&e), "connection left open"),
+        }
+        drop(stream);
+
+        // The slot is freed once that client is gone, then the cap applies
+        let mut idle = None;
+        for _ in 0..500 {
+            let stream = TcpStream::connect(addr).unwrap();
+            stream.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+            let mut byte = [0u8; 1];
+            if matches!((&stream).read(&mut byte), Err(e) if is_timeout(&e)) {
+                idle = Some(stream);
+                break;
+            }
+        }
+        let _idle = idle.expect("no connection slot was freed");
+
+        let extra = TcpStream::connect(addr).unwrap();
+        extra.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut byte = [0u8; 1];
+        match (&extra).read(&mut byte) {
+            Ok(read) => assert_eq!(read, 0),
+            Err(e) => assert!(!is_timeout(&e), "connection over the limit left open"),
+        }
+    }
+}
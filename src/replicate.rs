@@ -0,0 +1,419 @@
+//! ConVar replication between a server and its clients.
+//!
+//! The console doesn't do any networking itself. The game implements
+//! [`ReplicationTransport`] on top of its own transport and inserts it as a
+//! [`ReplicationSink`]. Updates arriving from the other side are handed back
+//! by writing [`ConVarUpdateReceived`] messages.
+//!
+//! - On the server, changes to [`ConVarFlags::REPLICATE_S2C`] variables are
+//!   sent, and client updates are only accepted for
+//!   [`ConVarFlags::REPLICATE_C2S`] variables. Each client's values are kept
+//!   in [`ClientConVars`] rather than set on the server's own variables.
+//! - On a client it's the other way around, and received values are set like
+//!   console input: with the same access checks, change events and observers.
+//!
+//! Received values go through the same parsing and constraints as console
+//! input, so invalid values are rejected and out-of-range ones clamped.
+
+use std::collections::HashMap;
+
+use bevy::ecs::message::MessageCursor;
+use bevy::prelude::*;
+
+use crate::core::{ConEntry, ConVarChangedEvent, ConVarFlags, ConsoleRegistry};
+use crate::ConsoleSet;
+
+/// Plugin that sends replicated ConVar changes and applies received ones.
+pub struct ConVarReplicationPlugin {
+    /// Which side of the connection this app is.
+    pub role: ReplicationRole,
+}
+
+impl Plugin for ConVarReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.role)
+            .init_resource::<ReplicationState>()
+            .init_resource::<ClientConVars>()
+            .add_message::<ConVarUpdateReceived>()
+            .add_message::<ClientConVarChanged>()
+            .add_systems(
+                Update,
+                (
+                    // Changes are queued with the console's own and sent in SendOutputs
+                    apply_received_updates
+                        .after(ConsoleSet::ExecuteCommands)
+                        .before(ConsoleSet::SendOutputs),
+                    send_replicated_changes.after(ConsoleSet::SendOutputs),
+                ),
+            );
+    }
+}
+
+/// Which side of the connection an app is.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationRole {
+    /// Owns [`REPLICATE_S2C`](ConVarFlags::REPLICATE_S2C) variables.
+    Server,
+    /// Owns [`REPLICATE_C2S`](ConVarFlags::REPLICATE_C2S) variables.
+    Client,
+}
+
+impl ReplicationRole {
+    /// The flag of variables whose changes this side sends.
+    pub fn outgoing_flag(self) -> ConVarFlags {
+        match self {
+            ReplicationRole::Server => ConVarFlags::REPLICATE_S2C,
+            ReplicationRole::Client => ConVarFlags::REPLICATE_C2S,
+        }
+    }
+
+    /// The flag of variables this side accepts updates for.
+    pub fn incoming_flag(self) -> ConVarFlags {
+        match self {
+            ReplicationRole::Server => ConVarFlags::REPLICATE_C2S,
+            ReplicationRole::Client => ConVarFlags::REPLICATE_S2C,
+        }
+    }
+}
+
+/// A ConVar value sent to or received from the other side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConVarUpdate {
+    /// The ConVar name.
+    pub name: Box<str>,
+    /// The new value as a string.
+    pub value: String,
+}
+
+impl ConVarUpdate {
+    /// Create a new update.
+    pub fn new(name: impl Into<Box<str>>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// The game's network transport for ConVar updates.
+///
+/// # Examples
+///
+/// ```ignore
+/// struct RenetTransport(RenetServer);
+///
+/// impl ReplicationTransport for RenetTransport {
+///     fn send(&mut self, update: &ConVarUpdate) {
+///         let packet = format!("{} {}", update.name, update.value);
+///         self.0.broadcast_message(CONVAR_CHANNEL, packet.into_bytes());
+///     }
+/// }
+/// ```
+pub trait ReplicationTransport: Send + Sync + 'static {
+    /// Send `update` to the other side: every client on the server, or the
+    /// server on a client.
+    fn send(&mut self, update: &ConVarUpdate);
+}
+
+/// Resource holding the game's [`ReplicationTransport`].
+///
+/// Changes made while it is missing are not sent.
+#[derive(Resource)]
+pub struct ReplicationSink(Box<dyn ReplicationTransport>);
+
+impl ReplicationSink {
+    /// Wrap a transport.
+    pub fn new(transport: impl ReplicationTransport) -> Self {
+        Self(Box::new(transport))
+    }
+}
+
+/// Message the game writes when a [`ConVarUpdate`] arrives from the other side.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct ConVarUpdateReceived {
+    /// The received update.
+    pub update: ConVarUpdate,
+    /// The game's id for the client that sent it. Required on the server.
+    pub client: Option<u64>,
+}
+
+impl ConVarUpdateReceived {
+    /// An update a client received from the server.
+    pub fn from_server(update: ConVarUpdate) -> Self {
+        Self { update, client: None }
+    }
+
+    /// An update the server received from the client `client`.
+    pub fn from_client(client: u64, update: ConVarUpdate) -> Self {
+        Self { update, client: Some(client) }
+    }
+}
+
+/// Resource on the server holding the [`REPLICATE_C2S`](ConVarFlags::REPLICATE_C2S)
+/// values each client sent.
+///
+/// Values are parsed and constrained like the ConVar's own, then formatted.
+/// Clients only ever change their own entries, never the server's variables.
+#[derive(Resource, Debug, Default)]
+pub struct ClientConVars {
+    values: HashMap<u64, HashMap<Box<str>, String>>,
+}
+
+impl ClientConVars {
+    /// The value `client` sent for the ConVar `name`, if any.
+    pub fn get(&self, client: u64, name: &str) -> Option<&str> {
+        self.values.get(&client)?.get(name).map(String::as_str)
+    }
+
+    /// All values `client` sent, by ConVar name.
+    pub fn client(&self, client: u64) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .get(&client)
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| (name.as_ref(), value.as_str()))
+    }
+
+    /// Forget the values of a client, e.g. when it disconnects.
+    pub fn remove_client(&mut self, client: u64) {
+        self.values.remove(&client);
+    }
+}
+
+/// Message sent on the server when a client changes one of its values in [`ClientConVars`].
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct ClientConVarChanged {
+    /// The client that sent the value.
+    pub client: u64,
+    /// The ConVar name.
+    pub name: Box<str>,
+    /// The client's previous value, if it had sent one.
+    pub old_value: Option<String>,
+    /// The new value.
+    pub new_value: String,
+}
+
+/// Values applied from the other side, so they aren't sent straight back.
+#[derive(Resource, Default)]
+struct ReplicationState {
+    received: HashMap<Box<str>, String>,
+}
+
+/// Exclusive system that applies received updates to variables this side accepts them for.
+fn apply_received_updates(world: &mut World, mut cursor: Local<MessageCursor<ConVarUpdateReceived>>) {
+    let received: Vec<_> = cursor
+        .read(world.resource::<Messages<ConVarUpdateReceived>>())
+        .cloned()
+        .collect();
+    let role = *world.resource::<ReplicationRole>();
+
+    for ConVarUpdateReceived { update, client } in received {
+        let name = match world.resource::<ConsoleRegistry>().get_entry(&update.name) {
+            Some(ConEntry::Var(meta)) if meta.flags.contains(role.incoming_flag()) => meta.name.clone(),
+            Some(ConEntry::Var(_)) => {
+                warn!("Rejected replicated update for '{}': not replicated in this direction", update.name);
+                continue;
+            }
+            _ => {
+                warn!("Rejected replicated update for unknown ConVar '{}'", update.name);
+                continue;
+            }
+        };
+
+        match (role, client) {
+            (ReplicationRole::Server, Some(client)) => apply_client_value(world, client, name, &update.value),
+            (ReplicationRole::Server, None) => {
+                warn!("Rejected replicated update for '{}': no client id", name);
+            }
+            (ReplicationRole::Client, _) => match crate::set_var(world, &name, &update.value) {
+                Ok(new_value) => {
+                    world.resource_mut::<ReplicationState>().received.insert(name, new_value);
+                }
+                Err((_, reason)) => {
+                    warn!("Rejected replicated value '{}' for '{}': {}", update.value, name, reason);
+                }
+            },
+        }
+    }
+}
+
+/// Store `client`'s value for the ConVar `name` in [`ClientConVars`].
+fn apply_client_value(world: &mut World, client: u64, name: Box<str>, value: &str) {
+    let new_value = match world.resource::<ConsoleRegistry>().get_entry(&name) {
+        Some(ConEntry::Var(meta)) => meta.validate_string(value),
+        _ => None,
+    };
+    let Some(new_value) = new_value else {
+        warn!("Rejected replicated value '{}' for '{}' from client {}", value, name, client);
+        return;
+    };
+
+    let mut clients = world.resource_mut::<ClientConVars>();
+    let old_value = clients
+        .values
+        .entry(client)
+        .or_default()
+        .insert(name.clone(), new_value.clone());
+    if old_value.as_ref() != Some(&new_value) {
+        world.write_message(ClientConVarChanged { client, name, old_value, new_value });
+    }
+}
+
+/// System that sends changes to variables this side owns.
+fn send_replicated_changes(
+    mut changes: MessageReader<ConVarChangedEvent>,
+    registry: Res<ConsoleRegistry>,
+    mut state: ResMut<ReplicationState>,
+    sink: Option<ResMut<ReplicationSink>>,
+    role: Res<ReplicationRole>,
+) {
+    let Some(mut sink) = sink else {
+        changes.clear();
+        return;
+    };
+
+    for change in changes.read() {
+        if state.received.remove(&change.name).is_some_and(|value| value == change.new_value) {
+            continue;
+        }
+        let replicated = registry
+            .get_entry(&change.name)
+            .is_some_and(|entry| entry.flags().contains(role.outgoing_flag()));
+        if replicated {
+            sink.0.send(&ConVarUpdate::new(change.name.clone(), change.new_value.clone()));
+        }
+    }
+}
+
+// Tests run without egui, since MinimalPlugins lacks what the egui UI needs
+#[cfg(all(test, not(feature = "egui")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::core::{ConVar, ConVarChange, ObserveConVarExt, TypedConVarChangedEvent};
+    use crate::tests::{collect_messages, take_messages};
+    use crate::{ConsolePermissions, ConsolePlugin, PermissionLevel};
+
+    #[derive(Clone, Default)]
+    struct RecordingTransport(Arc<Mutex<Vec<ConVarUpdate>>>);
+
+    impl ReplicationTransport for RecordingTransport {
+        fn send(&mut self, update: &ConVarUpdate) {
+            self.0.lock().unwrap().push(update.clone());
+        }
+    }
+
+    fn app(role: ReplicationRole) -> (App, RecordingTransport) {
+        let transport = RecordingTransport::default();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ConsolePlugin::default(), ConVarReplicationPlugin { role }))
+            .insert_resource(ReplicationSink::new(transport.clone()))
+            .add_systems(Startup, |mut registry: ResMut<ConsoleRegistry>| {
+                registry.register_var(ConVar::new("sv_gravity", 800i32)
+                    .flags(ConVarFlags::REPLICATE_S2C | ConVarFlags::NOTIFY));
+                registry.register_var(ConVar::new("cl_name", "player".to_string()).flags(ConVarFlags::REPLICATE_C2S));
+                registry.register_var(ConVar::new("cl_fov", 90i32).min(60).max(120)
+                    .flags(ConVarFlags::REPLICATE_C2S));
+                registry.register_var(ConVar::new("sv_rcon_password", String::new())
+                    .flags(ConVarFlags::REPLICATE_S2C)
+                    .permission(PermissionLevel::Admin));
+                registry.register_var(ConVar::new("mp_team", 0i32)
+                    .flags(ConVarFlags::REPLICATE_S2C | ConVarFlags::REPLICATE_C2S));
+            });
+        app.update();
+        (app, transport)
+    }
+
+    #[test]
+    fn test_server_sends_s2c_changes() {
+        let (mut app, transport) = app(ReplicationRole::Server);
+        app.world_mut().write_message(ConVarChangedEvent::new("sv_gravity", "800", "400"));
+        app.world_mut().write_message(ConVarChangedEvent::new("cl_name", "player", "bob"));
+        app.world_mut().write_message(ConVarChangedEvent::new("cl_fov", "90", "100"));
+        app.update();
+
+        assert_eq!(*transport.0.lock().unwrap(), [ConVarUpdate::new("sv_gravity", "400")]);
+    }
+
+    #[test]
+    fn test_server_keeps_client_values_apart() {
+        let (mut app, transport) = app(ReplicationRole::Server);
+        collect_messages::<ClientConVarChanged>(&mut app);
+        let received = [
+            ConVarUpdateReceived::from_client(1, ConVarUpdate::new("cl_name", "alice")),
+            ConVarUpdateReceived::from_client(2, ConVarUpdate::new("cl_name", "bob")),
+            ConVarUpdateReceived::from_client(2, ConVarUpdate::new("cl_fov", "500")),
+            ConVarUpdateReceived::from_client(1, ConVarUpdate::new("cl_fov", "wide")),
+            ConVarUpdateReceived::from_client(1, ConVarUpdate::new("sv_gravity", "0")),
+            ConVarUpdateReceived::from_server(ConVarUpdate::new("cl_name", "nobody")),
+        ];
+        app.world_mut().write_message_batch(received);
+        app.update();
+
+        let clients = app.world().resource::<ClientConVars>();
+        assert_eq!(clients.get(1, "cl_name"), Some("alice"));
+        assert_eq!(clients.get(2, "cl_name"), Some("bob"));
+        // Clamped like console input; invalid values are rejected
+        assert_eq!(clients.get(2, "cl_fov"), Some("120"));
+        assert_eq!(clients.get(1, "cl_fov"), None);
+        assert_eq!(clients.get(1, "sv_gravity"), None);
+
+        // The server's own values are untouched
+        let registry = app.world().resource::<ConsoleRegistry>();
+        assert_eq!(registry.get_string("cl_name").as_deref(), Some("player"));
+        assert_eq!(registry.get_string("sv_gravity").as_deref(), Some("800"));
+        assert!(transport.0.lock().unwrap().is_empty());
+
+        let changes: Vec<_> = take_messages::<ClientConVarChanged>(&mut app).into_iter().map(|c| (c.client, c.name, c.new_value)).collect();
+        assert_eq!(changes, [
+            (1, "cl_name".into(), "alice".to_string()),
+            (2, "cl_name".into(), "bob".to_string()),
+            (2, "cl_fov".into(), "120".to_string()),
+        ]);
+
+        app.world_mut().resource_mut::<ClientConVars>().remove_client(1);
+        assert_eq!(app.world().resource::<ClientConVars>().client(1).count(), 0);
+    }
+
+    #[test]
+    fn test_client_applies_s2c_without_echo() {
+        let (mut app, transport) = app(ReplicationRole::Client);
+        collect_messages::<TypedConVarChangedEvent<i32>>(&mut app);
+        app.init_resource::<ObservedGravity>();
+        app.world_mut().commands().observe_convar("sv_gravity", |change: On<ConVarChange<i32>>, mut seen: ResMut<ObservedGravity>| {
+            seen.0 = change.new;
+        });
+        app.world_mut().flush();
+
+        app.world_mut().write_message(ConVarUpdateReceived::from_server(ConVarUpdate::new("sv_gravity", "400")));
+        app.world_mut().write_message(ConVarUpdateReceived::from_server(ConVarUpdate::new("mp_team", "2")));
+        app.world_mut().write_message(ConVarChangedEvent::new("cl_name", "player", "bob"));
+        app.update();
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        assert_eq!(registry.get_string("sv_gravity").as_deref(), Some("400"));
+        assert_eq!(registry.get_string("mp_team").as_deref(), Some("2"));
+        assert_eq!(*transport.0.lock().unwrap(), [ConVarUpdate::new("cl_name", "bob")]);
+
+        // Received values notify like console input
+        let typed: Vec<_> = take_messages::<TypedConVarChangedEvent<i32>>(&mut app).into_iter().map(|e| (e.old, e.new)).collect();
+        assert_eq!(typed, [(800, 400)]);
+        assert_eq!(app.world().resource::<ObservedGravity>().0, 400);
+    }
+
+    #[derive(Resource, Default)]
+    struct ObservedGravity(i32);
+
+    #[test]
+    fn test_client_checks_access() {
+        let (mut app, _) = app(ReplicationRole::Client);
+        app.world_mut().resource_mut::<ConsolePermissions>().current_level = PermissionLevel::User;
+        app.world_mut().write_message(ConVarUpdateReceived::from_server(ConVarUpdate::new("sv_rcon_password", "hunter2")));
+        app.update();
+
+        let registry = app.world().resource::<ConsoleRegistry>();
+        assert_eq!(registry.get_string("sv_rcon_password").as_deref(), Some(""));
+    }
+}
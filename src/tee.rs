@@ -0,0 +1,189 @@
+//! Mirror console output to a file.
+//!
+//! The `tee` command opens a file that receives every [`ConsoleOutputEvent`]
+//! in addition to the normal display. With the `egui` feature it also receives
+//! the log messages the console captures, which is where most built-in
+//! commands print their results. Uses only std, so it is always available.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+
+use crate::core::ConsoleOutputEvent;
+#[cfg(feature = "egui")]
+use crate::logging::LogMessage;
+#[cfg(feature = "egui")]
+use crate::ui::COMMAND_MESSAGE_NAME;
+
+/// Resource holding the file that console output is mirrored to.
+#[derive(Resource, Default)]
+pub struct TeeState {
+    writer: Option<BufWriter<File>>,
+    path: Option<PathBuf>,
+}
+
+impl TeeState {
+    /// Start writing output to `path`, replacing any previous target.
+    ///
+    /// The file is truncated unless `append` is set.
+    pub fn start(&mut self, path: impl AsRef<Path>, append: bool) -> io::Result<()> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
+        self.stop();
+        self.writer = Some(BufWriter::new(file));
+        self.path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Stop writing output, flushing anything buffered.
+    ///
+    /// Returns the path that was being written, if any.
+    pub fn stop(&mut self) -> Option<PathBuf> {
+        if let Some(mut writer) = self.writer.take() {
+            let _ = writer.flush();
+        }
+        self.path.take()
+    }
+
+    /// The file currently receiving output.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Whether output is currently being written to a file.
+    pub fn is_active(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Write one message, prefixed with a UTC `HH:MM:SS` timestamp.
+    pub fn write_line(&mut self, message: &str) -> io::Result<()> {
+        let Some(writer) = &mut self.writer else {
+            return Ok(());
+        };
+        let timestamp = format_timestamp(SystemTime::now());
+        for line in message.lines() {
+            writeln!(writer, "[{}] {}", timestamp, line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a SystemTime as HH:MM:SS string.
+pub(crate) fn format_timestamp(t: SystemTime) -> String {
+    let secs = t.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs();
+    format!("{:02}:{:02}:{:02}", (secs / 3600) % 24, (secs / 60) % 60, secs % 60)
+}
+
+/// System that writes output events and captured log messages to the active tee file.
+pub(crate) fn write_tee_output(
+    mut events: MessageReader<ConsoleOutputEvent>,
+    #[cfg(feature = "egui")] mut logs: Option<MessageReader<LogMessage>>,
+    mut tee: ResMut<TeeState>,
+) {
+    if !tee.is_active() {
+        events.clear();
+        #[cfg(feature = "egui")]
+        if let Some(logs) = &mut logs {
+            logs.clear();
+        }
+        return;
+    }
+
+    let mut result = Ok(());
+    for event in events.read() {
+        result = result.and_then(|_| tee.write_line(&event.message));
+    }
+    // Submitted commands are already echoed as output events
+    #[cfg(feature = "egui")]
+    if let Some(logs) = &mut logs {
+        for log in logs.read().filter(|log| log.name != COMMAND_MESSAGE_NAME) {
+            result = result.and_then(|_| tee.write_line(&log.message));
+        }
+    }
+    if let Some(writer) = &mut tee.writer {
+        result = result.and_then(|_| writer.flush());
+    }
+
+    if let Err(e) = result {
+        let path = tee.stop().unwrap_or_default();
+        error!("tee: failed to write '{}', stopping: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tee_write_and_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+
+        let mut tee = TeeState::default();
+        tee.start(&path, false).unwrap();
+        assert!(tee.is_active());
+        tee.write_line("first\nsecond").unwrap();
+        assert_eq!(tee.stop().as_deref(), Some(path.as_path()));
+        assert!(!tee.is_active());
+
+        tee.start(&path, true).unwrap();
+        tee.write_line("third").unwrap();
+        tee.stop();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with('[') && lines[0].ends_with("] first"));
+        assert!(lines[2].ends_with("] third"));
+
+        // Without append the file is truncated
+        tee.start(&path, false).unwrap();
+        tee.stop();
+        assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn test_tee_writes_captured_logs() {
+        use bevy::log::Level;
+        use crate::ui::COMMAND_RESULT_NAME;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+
+        let mut app = App::new();
+        app.add_message::<ConsoleOutputEvent>()
+            .add_message::<LogMessage>()
+            .init_resource::<TeeState>()
+            .add_systems(Update, write_tee_output);
+        app.world_mut().resource_mut::<TeeState>().start(&path, false).unwrap();
+
+        let log = |name, message: &str| LogMessage {
+            message: message.to_string(),
+            name,
+            target: "bevy_console_two",
+            level: Level::INFO,
+            module_path: None,
+            file: None,
+            line: None,
+            time: SystemTime::now(),
+        };
+        app.world_mut().write_message(ConsoleOutputEvent::command("$ cvarlist"));
+        app.world_mut().write_message(log(COMMAND_MESSAGE_NAME, "$ cvarlist"));
+        app.world_mut().write_message(log(COMMAND_RESULT_NAME, "sv_gravity = 800"));
+        app.update();
+        app.world_mut().resource_mut::<TeeState>().stop();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().map(|line| line.split_once("] ").unwrap().1).collect();
+        assert_eq!(lines, ["$ cvarlist", "sv_gravity = 800"]);
+    }
+}
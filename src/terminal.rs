@@ -2,7 +2,11 @@
 //!
 //! This module provides stdin/stdout integration for running the console
 //! without a graphical UI, useful for dedicated servers.
+//!
+//! An optional prompt such as `"[${sv_hostname}]> "` is shown before each
+//! line is read, with `${var}` replaced by ConVar values.
 
+use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Mutex;
@@ -10,65 +14,213 @@ use std::thread::{self, JoinHandle};
 
 use bevy::prelude::*;
 
-use crate::core::{ConsoleInputEvent, ConsoleOutputEvent, ConsoleOutputLevel};
+use crate::core::{
+    expand_vars, CommandSource, ConVarChangedEvent, ConsoleInputEvent, ConsoleOutputEvent,
+    ConsoleOutputLevel, ConsoleRegistry,
+};
 
 /// Plugin that adds terminal (stdin/stdout) console support.
 pub struct TerminalPlugin;
 
 impl Plugin for TerminalPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<TerminalConfig>();
+        let has_prompt = app.world().resource::<TerminalConfig>().prompt.is_some();
+
         let (sender, receiver) = mpsc::channel();
-        let _handle = spawn_stdin_reader(sender);
+        let (prompt_sender, prompt_receiver) = mpsc::channel();
+        let _handle = spawn_stdin_reader(sender, prompt_receiver, has_prompt);
 
         app.insert_resource(StdinReceiver(Mutex::new(receiver)))
-            .insert_resource(TerminalConfig::default())
-            .add_systems(Update, (read_stdin, write_stdout));
+            .insert_resource(PromptSender(Mutex::new(prompt_sender)))
+            .add_systems(PostStartup, send_prompt)
+            .add_systems(Update, (read_stdin, write_stdout, update_prompt));
+    }
+}
+
+/// ANSI colors for one output level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalColor {
+    /// Foreground: an SGR code (e.g. `31`, `92`), or a palette index if `palette_256` is set.
+    pub foreground: u8,
+    /// Optional background: an SGR code (e.g. `41`), or a palette index if `palette_256` is set.
+    pub background: Option<u8>,
+    /// Whether the codes are 256-color palette indices.
+    pub palette_256: bool,
+}
+
+impl TerminalColor {
+    /// A color from the basic 16-color SGR codes.
+    pub const fn basic(foreground: u8) -> Self {
+        Self { foreground, background: None, palette_256: false }
+    }
+
+    /// A color from the 256-color palette.
+    pub const fn indexed(foreground: u8) -> Self {
+        Self { foreground, background: None, palette_256: true }
+    }
+
+    /// Set the background color, using the same kind of code as the foreground.
+    pub const fn on(mut self, background: u8) -> Self {
+        self.background = Some(background);
+        self
     }
 }
 
 /// Configuration for terminal behavior.
+///
+/// Insert before adding [`TerminalPlugin`] to override the defaults.
 #[derive(Resource)]
 pub struct TerminalConfig {
     /// Whether to use colored output (ANSI escape codes).
     pub colored: bool,
+    /// Color used for each output level. Levels without an entry are printed uncolored.
+    pub level_colors: HashMap<ConsoleOutputLevel, TerminalColor>,
+    /// Prompt shown before reading each line, with `${var}` replaced by ConVar values.
+    pub prompt: Option<String>,
+    /// ConVars whose changes re-render the prompt.
+    pub prompt_update_vars: Vec<String>,
 }
 
 impl Default for TerminalConfig {
     fn default() -> Self {
-        Self { colored: false }  // Disabled by default - causes issues on some terminals
+        Self {
+            colored: false, // Disabled by default - causes issues on some terminals
+            level_colors: HashMap::from([
+                (ConsoleOutputLevel::Debug, TerminalColor::basic(90)),
+                (ConsoleOutputLevel::Info, TerminalColor::basic(39)),
+                (ConsoleOutputLevel::Warn, TerminalColor::basic(33)),
+                (ConsoleOutputLevel::Error, TerminalColor::basic(31)),
+                (ConsoleOutputLevel::Success, TerminalColor::basic(92)),
+                (ConsoleOutputLevel::Command, TerminalColor::basic(36)),
+                (ConsoleOutputLevel::Result, TerminalColor::basic(32)),
+            ]),
+            prompt: None,
+            prompt_update_vars: Vec::new(),
+        }
+    }
+}
+
+impl TerminalConfig {
+    /// Colored output using the 256-color palette.
+    pub fn from_256_colors() -> Self {
+        Self {
+            colored: true,
+            level_colors: HashMap::from([
+                (ConsoleOutputLevel::Debug, TerminalColor::indexed(245)),
+                (ConsoleOutputLevel::Info, TerminalColor::indexed(252)),
+                (ConsoleOutputLevel::Warn, TerminalColor::indexed(214)),
+                (ConsoleOutputLevel::Error, TerminalColor::indexed(196)),
+                (ConsoleOutputLevel::Success, TerminalColor::indexed(120)),
+                (ConsoleOutputLevel::Command, TerminalColor::indexed(80)),
+                (ConsoleOutputLevel::Result, TerminalColor::indexed(114)),
+            ]),
+            ..Self::default()
+        }
+    }
+
+    /// Show `prompt` before each input line, re-rendered when any of `update_vars` change.
+    ///
+    /// ```ignore
+    /// TerminalConfig::default().with_prompt("[${sv_hostname}][${sv_cheats}]> ", ["sv_hostname", "sv_cheats"])
+    /// ```
+    pub fn with_prompt(
+        mut self,
+        prompt: impl Into<String>,
+        update_vars: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.prompt = Some(prompt.into());
+        self.prompt_update_vars = update_vars.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Turn off colored output, keeping the configured colors.
+    pub fn disable_colors(mut self) -> Self {
+        self.colored = false;
+        self
     }
 }
 
 #[derive(Resource)]
 struct StdinReceiver(Mutex<Receiver<String>>);
 
-fn spawn_stdin_reader(sender: Sender<String>) -> JoinHandle<()> {
+/// Sends rendered prompts to the stdin thread.
+#[derive(Resource)]
+struct PromptSender(Mutex<Sender<String>>);
+
+fn spawn_stdin_reader(
+    sender: Sender<String>,
+    prompts: Receiver<String>,
+    has_prompt: bool,
+) -> JoinHandle<()> {
     thread::spawn(move || {
         let stdin = io::stdin();
-        let handle = stdin.lock();
-
-        for line in handle.lines().flatten() {
-            let text = line.trim().to_string();
-            if !text.is_empty() {
-                if sender.send(text).is_err() {
-                    break;
-                }
+        let mut handle = stdin.lock();
+
+        // Wait for the first render so the initial prompt isn't blank
+        let mut prompt = if has_prompt { prompts.recv().unwrap_or_default() } else { String::new() };
+        let mut line = String::new();
+
+        loop {
+            if let Some(latest) = prompts.try_iter().last() {
+                prompt = latest;
+            }
+            if !prompt.is_empty() {
+                print!("{}", prompt);
+                let _ = io::stdout().flush();
+            }
+
+            line.clear();
+            match handle.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let text = line.trim();
+            if !text.is_empty() && sender.send(text.to_string()).is_err() {
+                break;
             }
         }
     })
 }
 
+/// Render the prompt template with current ConVar values.
+fn render_prompt(template: &str, registry: &ConsoleRegistry) -> String {
+    expand_vars(template, |name| registry.get_string(name))
+}
+
+/// System that sends the initial prompt once ConVars are registered.
+fn send_prompt(config: Res<TerminalConfig>, registry: Res<ConsoleRegistry>, sender: Res<PromptSender>) {
+    if let Some(template) = &config.prompt {
+        let _ = sender.0.lock().unwrap().send(render_prompt(template, &registry));
+    }
+}
+
+/// System that re-sends the prompt when a ConVar it shows changes.
+fn update_prompt(
+    mut changes: MessageReader<ConVarChangedEvent>,
+    config: Res<TerminalConfig>,
+    registry: Res<ConsoleRegistry>,
+    sender: Res<PromptSender>,
+) {
+    let relevant = changes
+        .read()
+        .any(|event| config.prompt_update_vars.iter().any(|name| **name == *event.name));
+    if let (true, Some(template)) = (relevant, &config.prompt) {
+        let _ = sender.0.lock().unwrap().send(render_prompt(template, &registry));
+    }
+}
+
 fn read_stdin(receiver: Res<StdinReceiver>, mut events: MessageWriter<ConsoleInputEvent>) {
     let rx = receiver.0.lock().unwrap();
     while let Ok(line) = rx.try_recv() {
-        events.write(ConsoleInputEvent::new(line));
+        events.write(ConsoleInputEvent::new(line).with_source(CommandSource::Terminal));
     }
 }
 
 fn write_stdout(mut events: MessageReader<ConsoleOutputEvent>, config: Res<TerminalConfig>) {
     for event in events.read() {
-        if config.colored {
-            print_colored(&event.message, event.level);
+        if let Some(color) = config.level_colors.get(&event.level).filter(|_| config.colored) {
+            print_colored(&event.message, color);
         } else {
             println!("{}", event.message);
         }
@@ -76,14 +228,64 @@ fn write_stdout(mut events: MessageReader<ConsoleOutputEvent>, config: Res<Termi
     }
 }
 
-fn print_colored(message: &str, level: ConsoleOutputLevel) {
-    let color = match level {
-        ConsoleOutputLevel::Debug => "\x1b[90m",
-        ConsoleOutputLevel::Info => "\x1b[0m",
-        ConsoleOutputLevel::Warn => "\x1b[33m",
-        ConsoleOutputLevel::Error => "\x1b[31m",
-        ConsoleOutputLevel::Command => "\x1b[36m",
-        ConsoleOutputLevel::Result => "\x1b[32m",
-    };
-    println!("{}{}\x1b[0m", color, message);
+fn print_colored(message: &str, color: &TerminalColor) {
+    println!("{}{}\x1b[0m", ansi_code(color), message);
+}
+
+/// The escape sequence that switches the terminal to `color`.
+fn ansi_code(color: &TerminalColor) -> String {
+    match (color.palette_256, color.background) {
+        (false, None) => format!("\x1b[{}m", color.foreground),
+        (false, Some(bg)) => format!("\x1b[{};{}m", color.foreground, bg),
+        (true, None) => format!("\x1b[38;5;{}m", color.foreground),
+        (true, Some(bg)) => format!("\x1b[38;5;{};48;5;{}m", color.foreground, bg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi_code() {
+        assert_eq!(ansi_code(&TerminalColor::basic(31)), "\x1b[31m");
+        assert_eq!(ansi_code(&TerminalColor::basic(30).on(47)), "\x1b[30;47m");
+        assert_eq!(ansi_code(&TerminalColor::indexed(214)), "\x1b[38;5;214m");
+        assert_eq!(ansi_code(&TerminalColor::indexed(0).on(252)), "\x1b[38;5;0;48;5;252m");
+    }
+
+    #[test]
+    fn test_prompt_updates_on_relevant_change() {
+        use crate::ConVar;
+
+        let mut app = App::new();
+        let (sender, receiver) = mpsc::channel();
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("sv_hostname", "box".to_string()));
+        registry.register_var(ConVar::new("sv_gravity", 800i32));
+        app.insert_resource(registry)
+            .insert_resource(PromptSender(Mutex::new(sender)))
+            .insert_resource(TerminalConfig::default().with_prompt("[${sv_hostname}]> ", ["sv_hostname"]))
+            .add_message::<ConVarChangedEvent>()
+            .add_systems(Update, update_prompt);
+
+        app.world_mut().write_message(ConVarChangedEvent::new("sv_gravity", "800", "400"));
+        app.update();
+        assert!(receiver.try_recv().is_err());
+
+        app.world_mut().resource_mut::<ConsoleRegistry>().set_string("sv_hostname", "prod");
+        app.world_mut().write_message(ConVarChangedEvent::new("sv_hostname", "box", "prod"));
+        app.update();
+        assert_eq!(receiver.try_recv().unwrap(), "[prod]> ");
+    }
+
+    #[test]
+    fn test_terminal_config_presets() {
+        assert!(!TerminalConfig::default().colored);
+        let config = TerminalConfig::from_256_colors();
+        assert!(config.colored);
+        assert_eq!(config.level_colors.len(), TerminalConfig::default().level_colors.len());
+        assert!(config.level_colors.values().all(|color| color.palette_256));
+        assert!(!config.disable_colors().colored);
+    }
 }
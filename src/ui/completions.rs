@@ -6,7 +6,9 @@ use crate::config::ConsoleConfig;
 
 use super::{AutoCompletions, CompletionSuggestion, ConsoleUiState};
 
-/// The max amount of completion suggestions shown at once.
+/// The default for [`ConsoleConfig::max_completions`].
+///
+/// Kept for compatibility; the limit is read from the config at runtime.
 pub const MAX_COMPLETION_SUGGESTIONS: usize = 6;
 
 pub fn completions(
@@ -43,7 +45,10 @@ pub fn completions(
             state.selected_completion = 0;
         }
 
-        if cursor_index.is_some() {
+        // After `<convar> `, the suggestions are values for the variable
+        let value_position = state.command.ends_with(char::is_whitespace) && !completions.is_empty();
+
+        if cursor_index.is_some() || state.history_search || value_position {
             if !completions.is_empty() {
                 egui::Popup::open_id(ui.ctx(), text_edit_complete_id);
             }
@@ -51,43 +56,53 @@ pub fn completions(
             egui::Popup::close_id(ui.ctx(), text_edit_complete_id);
         }
 
-        if let Some(cursor_index) = cursor_index {
-            // Accept completion with Tab or ArrowRight (when popup is open)
-            let accept_completion = ui.input(|i| i.key_pressed(egui::Key::Tab))
-                || (!completions.is_empty() && ui.input(|i| i.key_pressed(egui::Key::ArrowRight)));
-
-            if accept_completion {
-                // Remove the old text
-                let before_cursor = &state.command[..=cursor_index];
-                let index_before = match before_cursor.rfind(non_keyword) {
-                    Some(index) => index + 1,
-                    None => 0,
-                };
-                let after_cursor = &state.command[cursor_index..];
-                match after_cursor.find(non_keyword) {
-                    Some(characters_after) => state
-                        .command
-                        .drain(index_before..cursor_index + characters_after),
-                    None => state.command.drain(index_before..),
-                };
-
-                // Add the completed text
-                if let Some(completion) = completions.0.get(state.selected_completion) {
-                    let completed_text = &completion.suggestion;
-                    state.command.insert_str(index_before, completed_text);
-
-                    // Set the cursor position
-                    let mut text_edit_state = text_edit.state;
-                    let mut cursor_range = egui::text::CCursorRange::two(primary, secondary);
-
-                    cursor_range.primary.index +=
-                        completed_text.len() - (cursor_index - index_before) - 1;
-                    cursor_range.secondary.index +=
-                        completed_text.len() - (cursor_index - index_before) - 1;
-
-                    text_edit_state.cursor.set_char_range(Some(cursor_range));
-                    egui::TextEdit::store_state(ui.ctx(), text_edit_id, text_edit_state);
-                }
+        // Accept completion with Tab or ArrowRight (when popup is open). Hints can't be accepted.
+        let accept_completion = (ui.input(|i| i.key_pressed(egui::Key::Tab))
+            || (!completions.is_empty() && ui.input(|i| i.key_pressed(egui::Key::ArrowRight))))
+            && completions.get(state.selected_completion).is_some_and(|completion| !completion.hint);
+
+        // History search entries are loaded whole with Enter instead
+        if value_position && accept_completion && !state.history_search {
+            state.command.push_str(&completions[state.selected_completion].suggestion);
+
+            let mut text_edit_state = text_edit.state;
+            let end = egui::text::CCursor::new(state.command.chars().count());
+            text_edit_state.cursor.set_char_range(Some(egui::text::CCursorRange::one(end)));
+            egui::TextEdit::store_state(ui.ctx(), text_edit_id, text_edit_state);
+        } else if let Some(cursor_index) = cursor_index
+            && accept_completion
+            && !state.history_search
+        {
+            // Remove the old text
+            let before_cursor = &state.command[..=cursor_index];
+            let index_before = match before_cursor.rfind(non_keyword) {
+                Some(index) => index + 1,
+                None => 0,
+            };
+            let after_cursor = &state.command[cursor_index..];
+            match after_cursor.find(non_keyword) {
+                Some(characters_after) => state
+                    .command
+                    .drain(index_before..cursor_index + characters_after),
+                None => state.command.drain(index_before..),
+            };
+
+            // Add the completed text
+            if let Some(completion) = completions.0.get(state.selected_completion) {
+                let completed_text = &completion.suggestion;
+                state.command.insert_str(index_before, completed_text);
+
+                // Set the cursor position
+                let mut text_edit_state = text_edit.state;
+                let mut cursor_range = egui::text::CCursorRange::two(primary, secondary);
+
+                cursor_range.primary.index +=
+                    completed_text.len() - (cursor_index - index_before) - 1;
+                cursor_range.secondary.index +=
+                    completed_text.len() - (cursor_index - index_before) - 1;
+
+                text_edit_state.cursor.set_char_range(Some(cursor_range));
+                egui::TextEdit::store_state(ui.ctx(), text_edit_id, text_edit_state);
             }
         }
     }
@@ -103,15 +118,18 @@ pub fn completions(
                     CompletionSuggestion {
                         suggestion,
                         highlighted_indices,
+                        hint,
                     },
-                ) in completions.iter().take(MAX_COMPLETION_SUGGESTIONS).enumerate()
+                ) in completions.iter().take(config.max_completions).enumerate()
                 {
                     let mut layout = egui::text::LayoutJob::default();
-                    for (i, _) in suggestion.char_indices() {
+                    for (i, c) in suggestion.chars().enumerate() {
                         layout.append(
-                            &suggestion[i..=i],
+                            c.encode_utf8(&mut [0; 4]),
                             0.0,
-                            if highlighted_indices.contains(&i) {
+                            if *hint {
+                                config.theme.format_dark()
+                            } else if highlighted_indices.contains(&i) {
                                 config.theme.format_bold()
                             } else {
                                 config.theme.format_text()
@@ -127,6 +145,22 @@ pub fn completions(
         });
 }
 
+/// Get the part of the top suggestion that extends the word being typed.
+///
+/// Returns `None` unless the suggestion starts with the last word of `command`.
+pub fn ghost_suffix<'a>(command: &str, completions: &'a [CompletionSuggestion]) -> Option<&'a str> {
+    if command.ends_with(char::is_whitespace) {
+        return None;
+    }
+
+    let keyword = command.split_whitespace().last()?;
+    let suggestion = &completions.first()?.suggestion;
+
+    suggestion
+        .strip_prefix(keyword)
+        .filter(|suffix| !suffix.is_empty())
+}
+
 /// Also consumes the up and down arrow keys.
 pub fn change_selected_completion(
     ui: &mut egui::Ui,
@@ -143,3 +177,33 @@ pub fn change_selected_completion(
             .min(completions.len().saturating_sub(1));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestions(names: &[&str]) -> Vec<CompletionSuggestion> {
+        names
+            .iter()
+            .map(|name| CompletionSuggestion {
+                suggestion: name.to_string(),
+                highlighted_indices: Vec::new(),
+                hint: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ghost_suffix() {
+        let completions = suggestions(&["sv_gravity", "sv_cheats"]);
+
+        assert_eq!(ghost_suffix("sv_gr", &completions), Some("avity"));
+        assert_eq!(ghost_suffix("toggle sv_gr", &completions), Some("avity"));
+
+        // Fuzzy matches that aren't prefixes get no ghost text
+        assert_eq!(ghost_suffix("svg", &completions), None);
+        assert_eq!(ghost_suffix("sv_gravity", &completions), None);
+        assert_eq!(ghost_suffix("sv_gr ", &completions), None);
+        assert_eq!(ghost_suffix("sv_gr", &[]), None);
+    }
+}
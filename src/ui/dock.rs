@@ -0,0 +1,174 @@
+//! Docking the console at the bottom of the screen with `egui_dock`.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use egui_dock::{DockArea, DockState, Style, TabViewer};
+
+use crate::config::ToColor32;
+
+use super::{var_panel, ConsolePanel, ConsoleUiState};
+
+/// Plugin that shows the console as tabs in a [`DockArea`] instead of a
+/// floating window.
+///
+/// The dock is a resizable panel along the bottom of the screen, shown while
+/// the console is open. The "Console" tab counts errors that arrived while it
+/// wasn't visible, such as when the "Variables" tab was selected. Requires
+/// [`ConsolePlugin`](crate::ConsolePlugin).
+///
+/// ```ignore
+/// app.add_plugins((EguiPlugin::default(), ConsolePlugin::new(), ConsoleDockPlugin));
+/// ```
+pub struct ConsoleDockPlugin;
+
+impl Plugin for ConsoleDockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleDock>()
+            .add_systems(Startup, |mut state: ResMut<ConsoleUiState>| state.docked = true)
+            .add_systems(
+                EguiPrimaryContextPass,
+                render_console_dock.run_if(|s: Res<ConsoleUiState>| s.open_progress > 0.0),
+            );
+    }
+}
+
+/// A tab in the [`ConsoleDock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsoleDockTab {
+    /// The log and command input.
+    Console,
+    /// Sliders for bounded numeric ConVars.
+    Variables,
+}
+
+/// Resource holding the tab layout of the console dock.
+///
+/// Starts with the console and variables tabs side by side in one leaf; users
+/// can rearrange them by dragging.
+#[derive(Resource, Deref, DerefMut)]
+pub struct ConsoleDock(pub DockState<ConsoleDockTab>);
+
+impl Default for ConsoleDock {
+    fn default() -> Self {
+        Self(DockState::new(vec![ConsoleDockTab::Console, ConsoleDockTab::Variables]))
+    }
+}
+
+/// Draws the console tabs from a [`ConsolePanel`].
+struct ConsoleTabViewer<'a, 'w> {
+    panel: &'a mut ConsolePanel<'w>,
+}
+
+impl TabViewer for ConsoleTabViewer<'_, '_> {
+    type Tab = ConsoleDockTab;
+
+    fn title(&mut self, tab: &mut ConsoleDockTab) -> egui::WidgetText {
+        match tab {
+            ConsoleDockTab::Console if self.panel.state.unread_errors > 0 => {
+                egui::RichText::new(self.panel.tab_title())
+                    .color(self.panel.config.theme.error.to_color32())
+                    .into()
+            }
+            ConsoleDockTab::Console => self.panel.tab_title().into(),
+            ConsoleDockTab::Variables => "Variables".into(),
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut ConsoleDockTab) {
+        let panel = &mut *self.panel;
+        match tab {
+            ConsoleDockTab::Console => panel.show_log(ui),
+            ConsoleDockTab::Variables => {
+                var_panel::var_sliders(ui, &mut panel.state, &panel.registry, &mut panel.input_events);
+            }
+        }
+    }
+
+    // The default id is the title, which changes with the error count
+    fn id(&mut self, tab: &mut ConsoleDockTab) -> egui::Id {
+        egui::Id::new(("console dock tab", *tab))
+    }
+
+    fn is_closeable(&self, _tab: &ConsoleDockTab) -> bool {
+        false
+    }
+
+    // Both tabs scroll their own content
+    fn scroll_bars(&self, _tab: &ConsoleDockTab) -> [bool; 2] {
+        [false, false]
+    }
+}
+
+/// Show the [`ConsoleDock`] in `ui`.
+fn show_dock(ui: &mut egui::Ui, dock: &mut ConsoleDock, panel: &mut ConsolePanel) {
+    DockArea::new(&mut dock.0)
+        .id(egui::Id::new("console dock"))
+        .style(Style::from_egui(ui.style()))
+        .show_leaf_close_all_buttons(false)
+        .show_inside(ui, &mut ConsoleTabViewer { panel });
+}
+
+/// System drawing the console dock along the bottom of the screen.
+fn render_console_dock(
+    mut contexts: EguiContexts,
+    mut dock: ResMut<ConsoleDock>,
+    mut panel: ConsolePanel,
+) -> Result<(), BevyError> {
+    let height = panel.state.height;
+    egui::TopBottomPanel::bottom("console dock panel")
+        .resizable(true)
+        .default_height(height)
+        .show(contexts.ctx_mut()?, |ui| show_dock(ui, &mut dock, &mut panel));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::config::ConsoleConfig;
+    use crate::core::{ConsoleClearEvent, ConsoleInputEvent, ConsoleRegistry};
+    use crate::ui::AutoCompletions;
+
+    #[test]
+    fn test_dock_tab_error_badge() {
+        let mut world = World::new();
+        world.insert_resource(ConsoleUiState { unread_errors: 2, ..default() });
+        world.init_resource::<ConsoleConfig>();
+        world.init_resource::<ConsoleRegistry>();
+        world.init_resource::<AutoCompletions>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Messages<ConsoleInputEvent>>();
+        world.init_resource::<Messages<ConsoleClearEvent>>();
+
+        // Start with the variables tab selected
+        let mut dock = ConsoleDock::default();
+        let variables = dock.find_tab(&ConsoleDockTab::Variables).unwrap();
+        dock.set_active_tab(variables);
+        world.insert_resource(dock);
+
+        let draw = |mut dock: ResMut<ConsoleDock>, mut panel: ConsolePanel| -> String {
+            let title = ConsoleTabViewer { panel: &mut panel }
+                .title(&mut ConsoleDockTab::Console)
+                .text()
+                .to_string();
+            let ctx = egui::Context::default();
+            let _ = ctx.run(egui::RawInput::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| show_dock(ui, &mut dock, &mut panel));
+            });
+            title
+        };
+
+        // The hidden console tab keeps its count
+        assert_eq!(world.run_system_once(draw).unwrap(), "Console (2)");
+        assert_eq!(world.resource::<ConsoleUiState>().unread_errors(), 2);
+
+        let mut dock = world.resource_mut::<ConsoleDock>();
+        let console = dock.find_tab(&ConsoleDockTab::Console).unwrap();
+        dock.set_active_tab(console);
+        world.run_system_once(draw).unwrap();
+        assert_eq!(world.resource::<ConsoleUiState>().unread_errors(), 0);
+        assert_eq!(world.run_system_once(draw).unwrap(), "Console");
+    }
+}
@@ -0,0 +1,24 @@
+//! Saving console output through a native file dialog (`export-dialog` feature).
+
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+
+/// Ask for a file name and write `text` to it, without blocking the frame.
+pub(crate) fn save_with_dialog(text: String) {
+    IoTaskPool::get()
+        .spawn(async move {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_file_name("console.txt")
+                .add_filter("Text", &["txt"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            match std::fs::write(file.path(), text) {
+                Ok(()) => info!(name: "console_success", "Exported console output to '{}'", file.path().display()),
+                Err(e) => error!("Failed to export console output: {}", e),
+            }
+        })
+        .detach();
+}
@@ -0,0 +1,157 @@
+//! Syntax highlighting for the console input field.
+
+use std::ops::Range;
+
+use bevy_egui::egui::text::LayoutJob;
+
+use crate::config::ConsoleConfig;
+use crate::core::{tokenize_string, TokenizeError};
+
+/// Kind of a highlighted span in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpanKind {
+    /// The command name (first token).
+    Command,
+    /// A quoted string argument, including its quotes.
+    String,
+    /// A numeric argument.
+    Number,
+    /// Any other argument.
+    Plain,
+}
+
+/// Split `input` into highlighted spans.
+///
+/// Tokenizing is speculative: an unterminated quote colors the rest of the
+/// input as a string instead of failing.
+fn spans(input: &str) -> Vec<(Range<usize>, SpanKind)> {
+    let (tokenized, tail) = match tokenize_string(input) {
+        Ok(tokens) => (tokens, None),
+        Err(TokenizeError::UnterminatedString { position, .. }) => (
+            tokenize_string(&input[..position]).unwrap_or_default(),
+            Some(position..input.len()),
+        ),
+        Err(_) => (Vec::new(), None),
+    };
+
+    let mut spans: Vec<(Range<usize>, SpanKind)> = Vec::with_capacity(tokenized.len() + 1);
+    for (i, token) in tokenized.into_iter().enumerate() {
+        let start = token.as_ptr() as usize - input.as_ptr() as usize;
+        let end = start + token.len();
+        let prev_end = spans.last().map_or(0, |(range, _)| range.end);
+        // A quote right before the token only opens it if an earlier span didn't
+        // already claim it, as in `"a"b` where the closing quote precedes `b`
+        let quoted = start > prev_end && matches!(input.as_bytes()[start - 1], b'"' | b'\'');
+
+        let span = if quoted {
+            // Include the surrounding quotes
+            (start - 1..(end + 1).min(input.len()), SpanKind::String)
+        } else if i == 0 {
+            (start..end, SpanKind::Command)
+        } else if token.parse::<f64>().is_ok() {
+            (start..end, SpanKind::Number)
+        } else {
+            (start..end, SpanKind::Plain)
+        };
+        spans.push(span);
+    }
+
+    if let Some(tail) = tail {
+        spans.push((tail, SpanKind::String));
+    }
+
+    spans
+}
+
+/// Build a colored [`LayoutJob`] for the console input text.
+///
+/// `ghost` is appended in a dimmed color as a typeahead preview.
+pub fn highlight_input(input: &str, ghost: Option<&str>, config: &ConsoleConfig) -> LayoutJob {
+    let theme = &config.theme;
+    let mut job = LayoutJob::default();
+    let mut last = 0;
+
+    for (range, kind) in spans(input) {
+        // Never go back over text already appended
+        let range = range.start.max(last)..range.end.max(last);
+        job.append(&input[last..range.start], 0.0, theme.format_text());
+        let format = match kind {
+            SpanKind::Command => theme.format_command(),
+            SpanKind::String => theme.format_string_arg(),
+            SpanKind::Number => theme.format_number_arg(),
+            SpanKind::Plain => theme.format_text(),
+        };
+        job.append(&input[range.clone()], 0.0, format);
+        last = range.end;
+    }
+    job.append(&input[last..], 0.0, theme.format_text());
+
+    if let Some(ghost) = ghost {
+        job.append(ghost, 0.0, theme.format_dark());
+    }
+
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<(&str, SpanKind)> {
+        spans(input)
+            .into_iter()
+            .map(|(range, kind)| (&input[range], kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_spans() {
+        assert_eq!(
+            kinds(r#"bind F1 "toggle sv_cheats" 1.5"#),
+            vec![
+                ("bind", SpanKind::Command),
+                ("F1", SpanKind::Plain),
+                ("\"toggle sv_cheats\"", SpanKind::String),
+                ("1.5", SpanKind::Number),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_unterminated_string() {
+        assert_eq!(
+            kinds(r#"echo "hello wor"#),
+            vec![
+                ("echo", SpanKind::Command),
+                ("\"hello wor", SpanKind::String),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_quote_followed_by_text() {
+        assert_eq!(
+            kinds(r#"echo "a"b 'c'd"#),
+            vec![
+                ("echo", SpanKind::Command),
+                ("\"a\"", SpanKind::String),
+                ("b", SpanKind::Plain),
+                ("'c'", SpanKind::String),
+                ("d", SpanKind::Plain),
+            ]
+        );
+
+        let input = r#"echo "a"b"#;
+        assert_eq!(highlight_input(input, None, &ConsoleConfig::default()).text, input);
+    }
+
+    #[test]
+    fn test_highlight_preserves_text() {
+        let input = "  sv_gravity   800 ";
+        let job = highlight_input(input, None, &ConsoleConfig::default());
+        assert_eq!(job.text, input);
+
+        let job = highlight_input("sv_gr", Some("avity"), &ConsoleConfig::default());
+        assert_eq!(job.text, "sv_gravity");
+    }
+}
@@ -0,0 +1,82 @@
+//! Submitted command history.
+
+use crate::core::{subsequence_match, MatchResult};
+
+/// Commands submitted from the console input, most recent first.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleHistory {
+    entries: Vec<String>,
+}
+
+impl ConsoleHistory {
+    /// Record a submitted command, unless it repeats the most recent one.
+    pub fn push(&mut self, command: &str) {
+        if self.entries.first().map(String::as_str) != Some(command) {
+            self.entries.insert(0, command.to_string());
+        }
+    }
+
+    /// Get an entry by age, where 0 is the most recent.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no commands have been submitted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over entries, most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Find entries fuzzy-matching `query`, best match first.
+    ///
+    /// Each distinct command appears once; ties keep the more recent entry first.
+    pub fn search(&self, query: &str) -> Vec<&str> {
+        self.matches(query).into_iter().map(|(entry, _)| entry).collect()
+    }
+
+    /// Like [`search`](Self::search), with the matched character indices.
+    pub(crate) fn matches(&self, query: &str) -> Vec<(&str, MatchResult)> {
+        let mut matches: Vec<(&str, MatchResult)> = Vec::new();
+        for entry in self.iter() {
+            if matches.iter().any(|(seen, _)| *seen == entry) {
+                continue;
+            }
+            if let Some(result) = subsequence_match(query, entry) {
+                matches.push((entry, result));
+            }
+        }
+        // Stable, so equal scores stay in recency order
+        matches.sort_by_key(|(_, result)| std::cmp::Reverse(result.score));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_search() {
+        let mut history = ConsoleHistory::default();
+        for command in ["sv_gravity 400", "echo hi", "sv_cheats 1", "echo hi", "sv_gravity 800"] {
+            history.push(command);
+        }
+        history.push("sv_gravity 800");
+        assert_eq!(history.len(), 5);
+        assert_eq!(history.get(0), Some("sv_gravity 800"));
+
+        assert_eq!(history.search("grav"), vec!["sv_gravity 800", "sv_gravity 400"]);
+        assert_eq!(history.search("echo"), vec!["echo hi"]);
+        assert_eq!(history.search("ech")[0], "echo hi");
+        assert!(history.search("xyz").is_empty());
+    }
+}
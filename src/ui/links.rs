@@ -0,0 +1,81 @@
+//! Clickable URLs in console output (`url-links` feature).
+
+use std::ops::Range;
+
+use bevy_egui::egui::text::LayoutJob;
+
+use crate::config::ConsoleConfig;
+
+/// Find the byte ranges of whitespace-separated tokens that look like URLs.
+///
+/// Surrounding brackets, quotes and trailing punctuation such as a full stop
+/// are left out of the link.
+pub(crate) fn find_urls(message: &str) -> Vec<Range<usize>> {
+    message
+        .split(char::is_whitespace)
+        .map(|token| {
+            token
+                .trim_start_matches(['(', '[', '"', '\''])
+                .trim_end_matches(['.', ',', ';', ':', ')', ']', '"', '\''])
+        })
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .map(|url| {
+            let start = url.as_ptr() as usize - message.as_ptr() as usize;
+            start..start + url.len()
+        })
+        .collect()
+}
+
+/// The first URL in `message`, opened when the line is clicked.
+pub(crate) fn first_url(message: &str) -> Option<&str> {
+    find_urls(message).into_iter().next().map(|range| &message[range])
+}
+
+/// Append `message` up to and including its last URL, drawing URLs as links.
+///
+/// Returns the rest of the message, still to be appended.
+pub(crate) fn append_links<'a>(
+    text: &mut LayoutJob,
+    message: &'a str,
+    search_query: &str,
+    config: &ConsoleConfig,
+) -> &'a str {
+    let mut last = 0;
+    for range in find_urls(message) {
+        super::append_highlighted(text, &message[last..range.start], search_query, config);
+        text.append(&message[range.clone()], 0.0, config.theme.format_link());
+        last = range.end;
+    }
+    &message[last..]
+}
+
+/// Open `url` in the system browser.
+pub(crate) fn open_url(url: &str) {
+    if let Err(error) = webbrowser::open(url) {
+        bevy::log::warn!("Failed to open {}: {}", url, error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_urls() {
+        let message = "docs at https://bevy.org/learn. (see http://localhost:8080/a?b=c)";
+        let urls: Vec<_> = find_urls(message).into_iter().map(|range| &message[range]).collect();
+        assert_eq!(urls, vec!["https://bevy.org/learn", "http://localhost:8080/a?b=c"]);
+
+        assert_eq!(first_url("no links, just httpd"), None);
+        assert_eq!(first_url("https://example.com"), Some("https://example.com"));
+    }
+
+    #[test]
+    fn test_append_links_preserves_text() {
+        let message = "see https://example.com now";
+        let mut text = LayoutJob::default();
+        let rest = append_links(&mut text, message, "", &ConsoleConfig::default());
+        assert_eq!(rest, " now");
+        assert_eq!(text.text, "see https://example.com");
+    }
+}
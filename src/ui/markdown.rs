@@ -0,0 +1,158 @@
+//! Minimal Markdown rendering for console output.
+//!
+//! Supports `**bold**`, `*italic*`, `` `code` `` and `---` rules. Anything
+//! else, including unterminated markers, is shown as written.
+
+use std::ops::Range;
+
+use bevy_egui::egui::text::{LayoutJob, TextFormat};
+use bevy_egui::egui::FontId;
+
+use crate::config::ConsoleTheme;
+
+/// Number of characters in a drawn `---` rule.
+const RULE_WIDTH: usize = 40;
+
+/// Style of a span within a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Bold,
+    Italic,
+    Code,
+}
+
+/// Whether `line` is a horizontal rule.
+fn is_rule(line: &str) -> bool {
+    line.trim() == "---"
+}
+
+/// Split a single line into styled spans, leaving out the markers.
+fn spans(line: &str) -> Vec<(Range<usize>, Style)> {
+    let bytes = line.as_bytes();
+    let mut spans = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let (marker, style) = match bytes[i] {
+            b'*' if bytes[i..].starts_with(b"**") => ("**", Style::Bold),
+            b'*' => ("*", Style::Italic),
+            b'`' => ("`", Style::Code),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let content_start = i + marker.len();
+        // Like Markdown, emphasis can't start or end with whitespace, so `2 * 3 * 4` stays plain
+        let flanked = |content: &str| {
+            style == Style::Code
+                || !(content.starts_with(char::is_whitespace) || content.ends_with(char::is_whitespace))
+        };
+        let close = line[content_start..]
+            .find(marker)
+            .filter(|&len| len > 0 && flanked(&line[content_start..content_start + len]));
+        match close {
+            Some(len) => {
+                if plain_start < i {
+                    spans.push((plain_start..i, Style::Plain));
+                }
+                spans.push((content_start..content_start + len, style));
+                i = content_start + len + marker.len();
+                plain_start = i;
+            }
+            None => i = content_start,
+        }
+    }
+
+    if plain_start < line.len() {
+        spans.push((plain_start..line.len(), Style::Plain));
+    }
+    spans
+}
+
+/// Whether `text` contains any of the supported Markdown constructs.
+pub fn has_markdown(text: &str) -> bool {
+    text.lines().any(|line| {
+        is_rule(line) || spans(line).iter().any(|(_, style)| *style != Style::Plain)
+    })
+}
+
+/// Build a [`LayoutJob`] rendering the supported Markdown subset of `text`.
+pub fn format_markdown(text: &str, theme: &ConsoleTheme) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    append_markdown(&mut job, text, theme);
+    job
+}
+
+/// Append the rendered Markdown of `text` to `job`.
+pub(crate) fn append_markdown(job: &mut LayoutJob, text: &str, theme: &ConsoleTheme) {
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            job.append("\n", 0.0, theme.format_text());
+        }
+        if is_rule(line) {
+            job.append(&"─".repeat(RULE_WIDTH), 0.0, theme.format_dark());
+            continue;
+        }
+        for (range, style) in spans(line) {
+            let format = match style {
+                Style::Plain => theme.format_text(),
+                Style::Bold => theme.format_bold(),
+                Style::Italic => TextFormat { italics: true, ..theme.format_text() },
+                Style::Code => TextFormat {
+                    font_id: FontId::monospace(theme.font.size),
+                    ..theme.format_command()
+                },
+            };
+            job.append(&line[range], 0.0, format);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(line: &str) -> Vec<(&str, Style)> {
+        spans(line)
+            .into_iter()
+            .map(|(range, style)| (&line[range], style))
+            .collect()
+    }
+
+    #[test]
+    fn test_markdown_spans() {
+        assert_eq!(
+            styled("run **now** with `sv_cheats 1`, *maybe*"),
+            vec![
+                ("run ", Style::Plain),
+                ("now", Style::Bold),
+                (" with ", Style::Plain),
+                ("sv_cheats 1", Style::Code),
+                (", ", Style::Plain),
+                ("maybe", Style::Italic),
+            ]
+        );
+        // Unterminated and empty markers stay literal
+        assert_eq!(styled("2 * 3 * 4 **"), vec![("2 * 3 * 4 **", Style::Plain)]);
+        assert_eq!(styled("``é"), vec![("``é", Style::Plain)]);
+    }
+
+    #[test]
+    fn test_has_markdown() {
+        assert!(has_markdown("| `sv_gravity` | World gravity |"));
+        assert!(has_markdown("a\n---\nb"));
+        assert!(!has_markdown("sv_gravity* = \"800\""));
+        assert!(!has_markdown("3 * 4"));
+    }
+
+    #[test]
+    fn test_format_markdown_text() {
+        let job = format_markdown("**a** `b`\n---", &ConsoleTheme::ONE_DARK);
+        assert_eq!(job.text, format!("a b\n{}", "─".repeat(RULE_WIDTH)));
+        assert_eq!(job.sections[2].format.font_id.family, bevy_egui::egui::FontFamily::Monospace);
+    }
+}
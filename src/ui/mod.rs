@@ -1,17 +1,35 @@
 //! The module that handles the user interface of the console.
 
-use std::time::SystemTime;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_egui::egui::text::LayoutJob;
 use bevy_egui::*;
 
-use crate::config::ConsoleConfig;
+use crate::config::{ConsoleConfig, ToColor32};
 use crate::logging::LogMessage;
-use crate::core::{ConsoleInputEvent, ConsoleRegistry};
+use crate::core::{CommandSource, ConEntry, ConVarMeta, ConsoleClearEvent, ConsoleInputEvent, ConsolePaused, ConsoleRegistry};
 
 mod completions;
+#[cfg(feature = "export-dialog")]
+mod export;
+mod highlight;
+mod history;
+#[cfg(feature = "url-links")]
+mod links;
+mod markdown;
+mod var_panel;
+#[cfg(feature = "egui-dock")]
+mod dock;
 pub use completions::MAX_COMPLETION_SUGGESTIONS;
+#[cfg(feature = "egui-dock")]
+pub use dock::{ConsoleDock, ConsoleDockPlugin, ConsoleDockTab};
+pub use history::ConsoleHistory;
+pub use markdown::format_markdown;
+#[cfg(feature = "inspector")]
+pub(crate) use var_panel::NumberKind;
 
 /// Prefix for log messages that show a previous command.
 pub const COMMAND_MESSAGE_PREFIX: &str = "$ ";
@@ -21,6 +39,17 @@ pub const COMMAND_RESULT_PREFIX: &str = "> ";
 pub const COMMAND_MESSAGE_NAME: &str = "console_command";
 /// Identifier for log messages that show the result of a command.
 pub const COMMAND_RESULT_NAME: &str = "console_result";
+/// Identifier for log messages that confirm a command succeeded.
+pub const COMMAND_SUCCESS_NAME: &str = "console_success";
+
+/// Keyboard shortcuts listed in the shortcut reference panel.
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("Tab / →", "Accept completion"),
+    ("↑ / ↓", "History navigation"),
+    ("Ctrl + R", "Search history"),
+    ("Enter", "Submit"),
+    ("Esc", "Leave history search / close console"),
+];
 
 /// A suggestion for autocomplete.
 #[derive(Debug, Clone)]
@@ -29,6 +58,8 @@ pub struct CompletionSuggestion {
     pub suggestion: String,
     /// The character indices of the suggestion to highlight.
     pub highlighted_indices: Vec<usize>,
+    /// Shown for information only, such as a `<min..max>` range, and never inserted.
+    pub hint: bool,
 }
 
 /// Resource holding current autocomplete suggestions.
@@ -37,12 +68,14 @@ pub struct AutoCompletions(pub Vec<CompletionSuggestion>);
 
 /// Log level filter settings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize), serde(default))]
 pub struct LogFilter {
     pub show_error: bool,
     pub show_warn: bool,
     pub show_info: bool,
     pub show_debug: bool,
     pub show_trace: bool,
+    pub show_success: bool,
 }
 
 impl Default for LogFilter {
@@ -53,6 +86,7 @@ impl Default for LogFilter {
             show_info: true,
             show_debug: true,
             show_trace: true,
+            show_success: true,
         }
     }
 }
@@ -69,17 +103,50 @@ impl LogFilter {
             Level::TRACE => self.show_trace,
         }
     }
+
+    /// Check if a log message should be shown.
+    ///
+    /// Command echoes and results are always shown.
+    pub fn should_show_message(&self, message: &LogMessage) -> bool {
+        match message.name {
+            COMMAND_MESSAGE_NAME | COMMAND_RESULT_NAME => true,
+            COMMAND_SUCCESS_NAME => self.show_success,
+            _ => self.should_show(message.level),
+        }
+    }
+}
+
+/// Default maximum number of log entries kept by the console.
+pub const DEFAULT_MAX_LOG_SIZE: usize = 2000;
+/// Default width of the console window.
+pub const DEFAULT_WIDTH: f32 = 900.0;
+/// Default height of the console window.
+pub const DEFAULT_HEIGHT: f32 = 400.0;
+/// How long a copied output line stays highlighted.
+const COPY_FLASH_DURATION: Duration = Duration::from_millis(500);
+
+/// A log message kept by the console.
+pub(crate) struct LogEntry {
+    /// Identifies the entry while it stays in the log, unlike its index,
+    /// which shifts as older entries are evicted or cleared.
+    pub(crate) id: u64,
+    pub(crate) message: LogMessage,
+    /// Whether the entry hasn't been scrolled to yet.
+    pub(crate) is_new: bool,
 }
 
-#[derive(Default, Resource)]
+#[derive(Resource)]
 pub struct ConsoleUiState {
     /// Whether the console is open or not.
     pub(crate) open: bool,
+    /// How far the window has animated open, from 0.0 (hidden) to 1.0 (full height).
+    pub(crate) open_progress: f32,
     /// Whether we have set focus this open or not.
     pub(crate) text_focus: bool,
-    /// A list of all log messages received plus an
-    /// indicator indicating if the message is new.
-    pub(crate) log: Vec<(LogMessage, bool)>,
+    /// All log messages received, oldest first.
+    pub(crate) log: Vec<LogEntry>,
+    /// The id given to the next log entry.
+    pub(crate) next_log_id: u64,
     /// The command in the text bar.
     pub(crate) command: String,
     /// The selected completion index.
@@ -87,13 +154,94 @@ pub struct ConsoleUiState {
     /// Last command text that was used for autocomplete.
     pub(crate) last_autocomplete_text: String,
     /// Command history.
-    pub(crate) history: Vec<String>,
+    pub(crate) history: ConsoleHistory,
+    /// Whether the input is a fuzzy history search rather than a command.
+    pub(crate) history_search: bool,
     /// Current position in history (0 = current input, 1+ = history).
     pub(crate) history_index: usize,
     /// Saved current input when navigating history.
     pub(crate) history_draft: String,
     /// Log level filter.
     pub(crate) log_filter: LogFilter,
+    /// Maximum number of log entries to keep. Oldest entries are evicted first.
+    pub max_log_size: usize,
+    /// Whether new log entries scroll the output to the bottom.
+    ///
+    /// Cleared while the user has scrolled up so eviction doesn't jump the view.
+    pub(crate) auto_scroll: bool,
+    /// Whether new log entries may scroll the output at all.
+    ///
+    /// When `false`, the view stays put and a badge counts unseen entries.
+    pub scroll_lock: bool,
+    /// Whether long lines wrap. When `false`, the output scrolls horizontally.
+    pub word_wrap: bool,
+    /// Entries received while `scroll_lock` was off and not yet scrolled to.
+    pub(crate) unseen: usize,
+    /// Error entries received since the console content was last drawn.
+    pub(crate) unread_errors: usize,
+    /// Scroll the output to the bottom on the next frame.
+    pub(crate) scroll_to_bottom: bool,
+    /// Current width of the console window.
+    pub width: f32,
+    /// Current height of the console window.
+    pub height: f32,
+    /// Top-left corner of the window, once it has been shown or restored from config.
+    pub window_pos: Option<egui::Pos2>,
+    /// Size of the window's content, once it has been shown or restored from config.
+    pub window_size: Option<egui::Vec2>,
+    /// Whether the window ended up below [`ConsoleConfig::min_size`] and is
+    /// resized back to it next frame.
+    pub(crate) restore_size: bool,
+    /// Case-insensitive filter applied to the displayed log.
+    pub(crate) search_query: String,
+    /// Whether the ConVar slider panel is shown.
+    pub(crate) show_var_panel: bool,
+    /// In-progress slider values, keyed by ConVar name.
+    pub(crate) var_edits: HashMap<String, f64>,
+    /// Whether the keyboard shortcut reference is shown.
+    pub(crate) show_shortcuts: bool,
+    /// When each recently copied output line was clicked, keyed by log entry id.
+    pub(crate) copied_at: HashMap<u64, Instant>,
+    /// Whether the console is drawn in a dock instead of its own window.
+    pub(crate) docked: bool,
+}
+
+impl Default for ConsoleUiState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            open_progress: 0.0,
+            text_focus: false,
+            log: Vec::new(),
+            next_log_id: 0,
+            command: String::new(),
+            selected_completion: 0,
+            last_autocomplete_text: String::new(),
+            history: ConsoleHistory::default(),
+            history_search: false,
+            history_index: 0,
+            history_draft: String::new(),
+            log_filter: LogFilter::default(),
+            max_log_size: DEFAULT_MAX_LOG_SIZE,
+            auto_scroll: true,
+            scroll_lock: true,
+            word_wrap: true,
+            unseen: 0,
+            unread_errors: 0,
+            scroll_to_bottom: false,
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            window_pos: None,
+            window_size: None,
+            restore_size: false,
+            search_query: String::new(),
+            show_var_panel: false,
+            var_edits: HashMap::new(),
+            show_shortcuts: false,
+            copied_at: HashMap::new(),
+            docked: false,
+        }
+    }
 }
 
 impl ConsoleUiState {
@@ -101,6 +249,60 @@ impl ConsoleUiState {
     pub fn open(&self) -> bool {
         self.open
     }
+
+    /// Commands submitted from the console input.
+    pub fn history(&self) -> &ConsoleHistory {
+        &self.history
+    }
+
+    /// Number of error entries received since the console content was last drawn.
+    pub fn unread_errors(&self) -> usize {
+        self.unread_errors
+    }
+
+    /// Append log messages, evicting the oldest entries beyond `max_log_size`.
+    pub(crate) fn push_logs(&mut self, messages: impl IntoIterator<Item = LogMessage>) {
+        let scroll = self.auto_scroll && self.scroll_lock;
+        let before = self.log.len();
+        for message in messages {
+            self.log.push(LogEntry { id: self.next_log_id, message, is_new: scroll });
+            self.next_log_id += 1;
+        }
+        self.unread_errors += self.log[before..]
+            .iter()
+            .filter(|entry| entry.message.level == bevy::log::Level::ERROR)
+            .count();
+        if !self.scroll_lock {
+            self.unseen += self.log.len() - before;
+        }
+
+        let overflow = self.log.len().saturating_sub(self.max_log_size);
+        if overflow > 0 {
+            self.log.drain(0..overflow);
+        }
+    }
+
+    /// Store the window's content size, raised to [`ConsoleConfig::min_size`].
+    ///
+    /// If it was smaller, e.g. because the game window shrank, the console
+    /// window is resized back next frame.
+    pub(crate) fn record_window_size(&mut self, size: Option<egui::Vec2>, config: &ConsoleConfig) {
+        let min_size = egui::vec2(config.min_size.x, config.min_size.y);
+        self.restore_size = size.is_some_and(|size| size.x < min_size.x || size.y < min_size.y);
+        self.window_size = size.map(|size| size.max(min_size));
+    }
+
+    /// Forget copied lines whose highlight has faded.
+    pub(crate) fn expire_copy_flashes(&mut self) {
+        self.copied_at.retain(|_, copied| copied.elapsed() < COPY_FLASH_DURATION);
+    }
+
+    /// Store the window size, clamped to the configured bounds.
+    pub(crate) fn set_size(&mut self, size: Vec2, config: &ConsoleConfig) {
+        let size = size.clamp(config.min_size, config.max_size);
+        self.width = size.x;
+        self.height = size.y;
+    }
 }
 
 /// Format a SystemTime as HH:MM string.
@@ -112,19 +314,32 @@ fn format_time(t: SystemTime) -> String {
     format!("{:02}:{:02} ", hours, minutes)
 }
 
-pub(crate) fn read_logs(logs: Option<MessageReader<LogMessage>>, mut state: ResMut<ConsoleUiState>) {
+pub(crate) fn read_logs(
+    logs: Option<MessageReader<LogMessage>>,
+    mut state: ResMut<ConsoleUiState>,
+    mut buffer: ResMut<crate::core::ConsoleOutputBuffer>,
+) {
     let Some(mut logs) = logs else { return };
-    for log_message in logs.read() {
-        state.log.push((log_message.clone(), true));
+    if logs.is_empty() {
+        return;
     }
+    let logs: Vec<_> = logs.read().cloned().collect();
+    // Mirror into the core buffer so `grep` sees tracing output too
+    for log in &logs {
+        buffer.push(&log.message);
+    }
+    state.push_logs(logs);
 }
 
 pub(crate) fn handle_clear(
-    mut clear_events: MessageReader<crate::core::ConsoleClearEvent>,
+    mut clear_events: MessageReader<ConsoleClearEvent>,
     mut state: ResMut<ConsoleUiState>,
 ) {
-    for _ in clear_events.read() {
-        state.log.clear();
+    for clear in clear_events.read() {
+        match clear.filter {
+            None => state.log.clear(),
+            Some(level) => state.log.retain(|entry| entry.message.output_level() != level),
+        }
     }
 }
 
@@ -132,11 +347,34 @@ pub(crate) fn open_close_ui(
     mut state: ResMut<ConsoleUiState>,
     key: Res<ButtonInput<KeyCode>>,
     config: Res<ConsoleConfig>,
+    time: Res<Time>,
 ) {
     if key.just_pressed(config.open_key) {
         state.open = !state.open;
         state.text_focus = false;
+        state.history_search = false;
+    } else if state.open && key.just_pressed(KeyCode::Escape) {
+        if state.history_search {
+            // Back to the typed input, which is still in the field
+            state.history_search = false;
+            state.last_autocomplete_text.clear();
+        } else {
+            state.open = false;
+        }
     }
+
+    let target = if state.open { 1.0 } else { 0.0 };
+    let duration = config.open_animation_duration.as_secs_f32();
+    state.open_progress = if duration > 0.0 {
+        let step = time.delta_secs() / duration;
+        if target > state.open_progress {
+            (state.open_progress + step).min(target)
+        } else {
+            (state.open_progress - step).max(target)
+        }
+    } else {
+        target
+    };
 }
 
 /// System that updates autocomplete suggestions based on current input.
@@ -144,6 +382,7 @@ pub(crate) fn update_completions(
     mut state: ResMut<ConsoleUiState>,
     mut completions: ResMut<AutoCompletions>,
     registry: Res<ConsoleRegistry>,
+    config: Res<ConsoleConfig>,
 ) {
     // Only update if the command text changed
     if state.command == state.last_autocomplete_text {
@@ -151,51 +390,232 @@ pub(crate) fn update_completions(
     }
     state.last_autocomplete_text = state.command.clone();
 
+    // In history search mode the whole input is the query
+    if state.history_search {
+        completions.0 = state
+            .history
+            .matches(&state.command)
+            .into_iter()
+            .take(config.max_completions)
+            .map(|(entry, result)| CompletionSuggestion {
+                suggestion: entry.to_string(),
+                highlighted_indices: result.indices,
+                hint: false,
+            })
+            .collect();
+        return;
+    }
+
+    // Suggest values when typing the argument of `<convar> <value>`
+    if let Some((name, partial)) = value_position(&state.command)
+        && let Some(ConEntry::Var(meta)) = registry.get_entry(name)
+    {
+        completions.0 = value_suggestions(meta, partial, config.max_completions);
+        return;
+    }
+
     // Get the keyword being typed (last word)
     let keyword = state.command.split_whitespace().last().unwrap_or("");
 
-    if keyword.is_empty() {
+    if keyword.is_empty() || state.command.ends_with(char::is_whitespace) {
         completions.0.clear();
         return;
     }
 
     // Use our fuzzy matcher to find matches
-    let matches = registry.fuzzy_find(keyword);
+    let matches = registry.fuzzy_find_with_options(
+        keyword,
+        Some(config.autocomplete_min_score),
+        config.autocomplete_suffix_search,
+    );
 
     completions.0 = matches
         .into_iter()
-        .take(MAX_COMPLETION_SUGGESTIONS)
+        .take(config.max_completions)
         .map(|(name, _, result)| CompletionSuggestion {
             suggestion: name.to_string(),
             highlighted_indices: result.indices,
+            hint: false,
         })
         .collect();
 }
 
+/// The ConVar name and partial value when the input ends in `<name> <value>`.
+///
+/// Only the last command of a `;` chain is considered.
+fn value_position(command: &str) -> Option<(&str, &str)> {
+    let current = command.rsplit(';').next()?;
+    let mut words = current.split_whitespace();
+    let name = words.next()?;
+    match (words.next(), words.next()) {
+        (None, None) if current.ends_with(char::is_whitespace) => Some((name, "")),
+        (Some(partial), None) if !current.ends_with(char::is_whitespace) => Some((name, partial)),
+        _ => None,
+    }
+}
+
+/// Value completions for `meta` starting with `partial`.
+///
+/// Range hints such as `<0..100>` are always kept, since they describe rather than complete.
+fn value_suggestions(meta: &ConVarMeta, partial: &str, max: usize) -> Vec<CompletionSuggestion> {
+    let partial = partial.to_ascii_lowercase();
+    meta.value_completions()
+        .into_iter()
+        .filter_map(|value| {
+            let hint = value.starts_with('<') && value.ends_with('>');
+            (hint || value.to_ascii_lowercase().starts_with(&partial)).then(|| CompletionSuggestion {
+                highlighted_indices: if hint { Vec::new() } else { (0..partial.len()).collect() },
+                suggestion: value,
+                hint,
+            })
+        })
+        .take(max)
+        .collect()
+}
+
+/// The console's content as a system parameter, for drawing it outside its own window.
+///
+/// [`show`](Self::show) draws the console into any `egui::Ui`, such as a tab
+/// in a dock layout. Leave the console closed so its window isn't drawn too.
+/// With the `egui-dock` feature, `ConsoleDockPlugin` sets up such a dock.
+///
+/// ```ignore
+/// struct Tabs<'a, 'w> {
+///     console: &'a mut ConsolePanel<'w>,
+/// }
+///
+/// impl egui_dock::TabViewer for Tabs<'_, '_> {
+///     type Tab = MyTab;
+///
+///     fn title(&mut self, tab: &mut MyTab) -> egui::WidgetText {
+///         match tab {
+///             MyTab::Console => self.console.tab_title().into(),
+///             MyTab::Scene => "Scene".into(),
+///         }
+///     }
+///
+///     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut MyTab) {
+///         if let MyTab::Console = tab {
+///             self.console.show(ui);
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ConsolePanel<'w> {
+    state: ResMut<'w, ConsoleUiState>,
+    key: Res<'w, ButtonInput<KeyCode>>,
+    config: Res<'w, ConsoleConfig>,
+    completions: Res<'w, AutoCompletions>,
+    registry: Res<'w, ConsoleRegistry>,
+    input_events: MessageWriter<'w, ConsoleInputEvent>,
+    clear_events: MessageWriter<'w, ConsoleClearEvent>,
+}
+
+impl ConsolePanel<'_> {
+    /// Draw the console content (variable panel, log, and input) into `ui`.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        var_panel::var_panel(ui, &mut self.state, &self.registry, &mut self.input_events);
+        self.show_log(ui);
+    }
+
+    /// Draw the log and input without the variable panel.
+    fn show_log(&mut self, ui: &mut egui::Ui) {
+        render_ui(
+            ui,
+            &mut self.state,
+            &self.key,
+            &self.config,
+            &self.completions,
+            &mut self.input_events,
+            &mut self.clear_events,
+        );
+        self.state.unread_errors = 0;
+    }
+
+    /// Title for a tab holding the console, with the unread error count if any.
+    pub fn tab_title(&self) -> String {
+        console_tab_title(self.state.unread_errors)
+    }
+}
+
+/// `"Console"`, with the number of unread errors appended when nonzero.
+fn console_tab_title(unread_errors: usize) -> String {
+    if unread_errors == 0 {
+        "Console".to_string()
+    } else {
+        format!("Console ({})", unread_errors)
+    }
+}
+
 pub(crate) fn render_ui_system(
     mut contexts: EguiContexts,
-    mut state: ResMut<ConsoleUiState>,
-    key: Res<ButtonInput<KeyCode>>,
-    config: Res<ConsoleConfig>,
-    completions: Res<AutoCompletions>,
-    mut input_events: MessageWriter<ConsoleInputEvent>,
+    mut panel: ConsolePanel,
+    paused: Res<ConsolePaused>,
 ) -> Result<(), BevyError> {
-    egui::Window::new("Developer Console")
-        .collapsible(false)
-        .default_width(900.)
-        .show(contexts.ctx_mut()?, |ui| {
-            render_ui(
-                ui,
-                &mut state,
-                &key,
-                &config,
-                &completions,
-                &mut input_events,
-            )
-        });
+    let title = if paused.0 {
+        "Developer Console [PAUSED]"
+    } else {
+        "Developer Console"
+    };
+    let window = console_window(title, &panel.state, &panel.config);
+    let animating = panel.state.open_progress < 1.0;
+    let response = window.show(contexts.ctx_mut()?, |ui| {
+        // The content size, which is what `default_size` restores, excludes the title bar
+        let content_size = ui.max_rect().size();
+        panel.show(ui);
+        content_size
+    });
+
+    // Don't record the partial height while the window animates
+    if let Some(response) = response
+        && !animating
+    {
+        let rect = response.response.rect;
+        let config = &panel.config;
+        panel.state.set_size(Vec2::new(rect.width(), rect.height()), config);
+        panel.state.window_pos = Some(rect.min);
+        panel.state.record_window_size(response.inner, config);
+    }
     Ok(())
 }
 
+/// The console's egui window, sized from `state` and limited to the configured bounds.
+fn console_window<'a>(title: &'a str, state: &ConsoleUiState, config: &ConsoleConfig) -> egui::Window<'a> {
+    let min_size = egui::vec2(config.min_size.x, config.min_size.y);
+    // Fixed id so the window keeps its state when the title changes
+    let mut window = egui::Window::new(title)
+        .id(egui::Id::new("developer_console"))
+        .collapsible(false)
+        .resizable(true)
+        .default_size(state.window_size.unwrap_or(egui::vec2(state.width, state.height)).max(min_size))
+        .min_size(min_size)
+        .max_size([config.max_size.x, config.max_size.y]);
+    if let Some(pos) = state.window_pos {
+        window = window.default_pos(pos);
+    }
+    if state.open_progress < 1.0 {
+        window = window.fixed_size([state.width, state.height * state.open_progress]);
+    } else if state.restore_size
+        && let Some(size) = state.window_size
+    {
+        window = window.fixed_size(size);
+    }
+    window
+}
+
+/// Switch the input to fuzzy history search when Ctrl+R is pressed.
+fn start_history_search(state: &mut ConsoleUiState, key: &ButtonInput<KeyCode>) {
+    if !state.history_search
+        && key.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        && key.just_pressed(KeyCode::KeyR)
+    {
+        state.history_search = true;
+        // Recompute completions for the current input as a history query
+        state.last_autocomplete_text.clear();
+    }
+}
+
 /// The function that renders the UI of the developer console.
 pub fn render_ui(
     ui: &mut egui::Ui,
@@ -204,6 +624,7 @@ pub fn render_ui(
     config: &ConsoleConfig,
     completions: &AutoCompletions,
     input_events: &mut MessageWriter<ConsoleInputEvent>,
+    clear_events: &mut MessageWriter<ConsoleClearEvent>,
 ) {
     fn submit_command(state: &mut ConsoleUiState, input_events: &mut MessageWriter<ConsoleInputEvent>) {
         let command = state.command.trim();
@@ -211,12 +632,10 @@ pub fn render_ui(
             info!(name: COMMAND_MESSAGE_NAME, "{COMMAND_MESSAGE_PREFIX}{}", command);
 
             // Add to history (avoid duplicates at the top)
-            if state.history.first().map(|s| s.as_str()) != Some(command) {
-                state.history.insert(0, command.to_string());
-            }
+            state.history.push(command);
 
             let cmd = std::mem::take(&mut state.command);
-            input_events.write(ConsoleInputEvent::new(cmd));
+            input_events.write(ConsoleInputEvent::new(cmd).with_source(CommandSource::Ui));
 
             // Reset history navigation
             state.history_index = 0;
@@ -224,8 +643,20 @@ pub fn render_ui(
         }
     }
 
+    start_history_search(state, key);
+
     if key.just_pressed(config.submit_key) {
-        submit_command(state, input_events);
+        if state.history_search {
+            // Load the selected entry for editing instead of submitting
+            if let Some(entry) = completions.get(state.selected_completion) {
+                state.command = entry.suggestion.clone();
+                state.text_focus = false;
+            }
+            state.history_search = false;
+            state.last_autocomplete_text.clear();
+        } else {
+            submit_command(state, input_events);
+        }
     }
 
     // History navigation with up/down arrows (only when completions popup is closed)
@@ -237,22 +668,20 @@ pub fn render_ui(
             }
             if state.history_index < state.history.len() {
                 state.history_index += 1;
-                state.command = state.history[state.history_index - 1].clone();
+                state.command = state.history.get(state.history_index - 1).unwrap_or_default().to_string();
             }
         }
-        if key.just_pressed(KeyCode::ArrowDown) {
-            if state.history_index > 0 {
-                state.history_index -= 1;
-                if state.history_index == 0 {
-                    state.command = std::mem::take(&mut state.history_draft);
-                } else {
-                    state.command = state.history[state.history_index - 1].clone();
-                }
+        if key.just_pressed(KeyCode::ArrowDown) && state.history_index > 0 {
+            state.history_index -= 1;
+            if state.history_index == 0 {
+                state.command = std::mem::take(&mut state.history_draft);
+            } else {
+                state.command = state.history.get(state.history_index - 1).unwrap_or_default().to_string();
             }
         }
     }
 
-    completions::change_selected_completion(ui, state, &completions);
+    completions::change_selected_completion(ui, state, completions);
 
     // Log filter controls
     egui::TopBottomPanel::top("filter panel")
@@ -265,7 +694,43 @@ pub fn render_ui(
                 ui.checkbox(&mut state.log_filter.show_info, "Info");
                 ui.checkbox(&mut state.log_filter.show_debug, "Debug");
                 ui.checkbox(&mut state.log_filter.show_trace, "Trace");
+                ui.checkbox(&mut state.log_filter.show_success, "Success");
+                ui.separator();
+                ui.label("Search:");
+                ui.text_edit_singleline(&mut state.search_query);
+                ui.separator();
+                // Docked consoles have a Variables tab instead
+                if !state.docked {
+                    ui.toggle_value(&mut state.show_var_panel, "Vars");
+                }
+                ui.toggle_value(&mut state.word_wrap, "Wrap")
+                    .on_hover_text("Wrap long lines");
+                let lock_icon = if state.scroll_lock { "🔒" } else { "🔓" };
+                if ui.toggle_value(&mut state.scroll_lock, lock_icon)
+                    .on_hover_text("Scroll to new output")
+                    .clicked()
+                    && state.scroll_lock
+                {
+                    state.scroll_to_bottom = true;
+                }
+                if state.unseen > 0 && ui.button(format!("↓ {} new", state.unseen)).clicked() {
+                    state.scroll_to_bottom = true;
+                }
+                if config.show_help_button {
+                    ui.toggle_value(&mut state.show_shortcuts, "?")
+                        .on_hover_text("Keyboard shortcuts");
+                }
             });
+
+            if config.show_help_button && state.show_shortcuts {
+                egui::Grid::new("shortcuts").num_columns(2).show(ui, |ui| {
+                    for (keys, action) in SHORTCUTS {
+                        ui.label(egui::RichText::new(*keys).strong());
+                        ui.label(*action);
+                        ui.end_row();
+                    }
+                });
+            }
         });
 
     egui::TopBottomPanel::bottom("bottom panel")
@@ -284,11 +749,22 @@ pub fn render_ui(
                     ui.ctx().memory_mut(|mem| mem.request_focus(text_edit_id));
                 }
 
+                let ghost = completions::ghost_suffix(&state.command, completions)
+                    .filter(|_| !state.history_search)
+                    .map(str::to_string);
+                let mut layouter = |ui: &egui::Ui, buf: &dyn egui::TextBuffer, wrap_width: f32| {
+                    let mut job = highlight::highlight_input(buf.as_str(), ghost.as_deref(), config);
+                    job.wrap.max_width = wrap_width;
+                    ui.fonts_mut(|f| f.layout_job(job))
+                };
+
                 let text_edit = egui::TextEdit::singleline(&mut state.command)
                     .id(text_edit_id)
+                    .layouter(&mut layouter)
                     .desired_width(ui.available_width())
                     .margin(egui::Vec2::splat(4.0))
                     .font(config.theme.font.clone())
+                    .hint_text(config.input_placeholder.as_str())
                     .lock_focus(true)
                     .show(ui);
 
@@ -297,7 +773,7 @@ pub fn render_ui(
                     text_edit_id,
                     state,
                     ui,
-                    &completions,
+                    completions,
                     config,
                 );
 
@@ -308,49 +784,134 @@ pub fn render_ui(
             });
         });
 
-    egui::ScrollArea::new([false, true])
+    let mut clicked_var = None;
+    let scroll_output = egui::ScrollArea::new([!state.word_wrap, true])
         .auto_shrink([false, true])
         .show(ui, |ui| {
             ui.vertical(|ui| {
-                for (id, (message, is_new)) in state.log.iter_mut().enumerate() {
-                    // Apply log filter (always show command messages)
-                    if message.name != COMMAND_MESSAGE_NAME
-                        && message.name != COMMAND_RESULT_NAME
-                        && !state.log_filter.should_show(message.level)
-                    {
+                if !state.word_wrap {
+                    ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+                }
+                state.expire_copy_flashes();
+                if !state.copied_at.is_empty() {
+                    // Keep redrawing so the highlight goes away on time
+                    ui.ctx().request_repaint_after(COPY_FLASH_DURATION);
+                }
+                for entry in &mut state.log {
+                    if !is_shown(&entry.message, &state.log_filter, &state.search_query) {
                         continue;
                     }
-                    add_log(ui, id, message, is_new, config);
+                    let copied_at = &mut state.copied_at;
+                    if let Some(name) = add_log(ui, entry, &state.search_query, config, copied_at) {
+                        clicked_var = Some(name);
+                    }
+                }
+                if std::mem::take(&mut state.scroll_to_bottom) {
+                    ui.scroll_to_cursor(Some(egui::Align::Max));
                 }
             });
         });
+
+    // Right-clicking anywhere in the output opens the copy/export/clear menu
+    let output_area = ui.interact(
+        scroll_output.inner_rect,
+        egui::Id::new("console_output_menu"),
+        egui::Sense::hover(),
+    );
+    let right_clicked = ui.rect_contains_pointer(scroll_output.inner_rect)
+        && ui.input(|i| i.pointer.secondary_clicked());
+    egui::Popup::menu(&output_area)
+        .open_memory(right_clicked.then_some(egui::SetOpenCommand::Bool(true)))
+        .at_pointer_fixed()
+        .show(|ui| {
+            if ui.button("Copy selection").on_hover_text("Copy the lines shown by the filter and search").clicked() {
+                let shown = state.log.iter().map(|entry| &entry.message)
+                    .filter(|message| is_shown(message, &state.log_filter, &state.search_query));
+                ui.ctx().copy_text(export_text(shown));
+            }
+            #[cfg(feature = "export-dialog")]
+            if ui.button("Export all to file...").clicked() {
+                export::save_with_dialog(export_text(state.log.iter().map(|entry| &entry.message)));
+            }
+            if ui.button("Clear").clicked() {
+                clear_events.write(ConsoleClearEvent::all());
+            }
+        });
+
+    // Populate the input with a clicked ConVar name, ready for a new value
+    if let Some(name) = clicked_var {
+        state.command = format!("{name} ");
+        state.text_focus = false;
+    }
+
+    // Only follow new output while the view is at the bottom
+    let visible_bottom = scroll_output.state.offset.y + scroll_output.inner_rect.height();
+    state.auto_scroll = visible_bottom >= scroll_output.content_size.y - 1.0;
+    if state.auto_scroll {
+        state.unseen = 0;
+    }
 }
 
 fn add_log(
     ui: &mut egui::Ui,
-    id: usize,
-    event: &LogMessage,
-    is_new: &mut bool,
+    entry: &mut LogEntry,
+    search_query: &str,
     config: &ConsoleConfig,
-) {
+    copied_at: &mut HashMap<u64, Instant>,
+) -> Option<String> {
+    let (id, event, is_new) = (entry.id, &entry.message, &mut entry.is_new);
     ui.push_id(id, |ui| {
         let time_str = format_time(event.time);
 
-        let text = format_line(&time_str, config, event);
-        let label = ui.label(text);
+        let text = format_line(&time_str, config, event, search_query);
+        let var_name = if event.name == COMMAND_RESULT_NAME {
+            result_var_name(&event.message)
+        } else {
+            None
+        };
+        // Reserved before the label so the copy highlight is drawn behind the text
+        let background = ui.painter().add(egui::Shape::Noop);
+        let label = if var_name.is_some() {
+            ui.selectable_label(false, text)
+        } else {
+            ui.label(text)
+        };
 
         if *is_new {
             label.scroll_to_me(Some(egui::Align::Max));
             *is_new = false;
         }
 
-        // Copy message to clipboard on click
-        if label.clicked() {
-            ui.ctx().copy_text(event.message.clone());
+        #[cfg(feature = "url-links")]
+        let url = links::first_url(&event.message).filter(|_| var_name.is_none());
+        #[cfg(not(feature = "url-links"))]
+        let url: Option<&str> = None;
+
+        // ConVar results populate the input on click, links are opened, other messages are copied
+        let clicked = label.clicked();
+        if clicked && var_name.is_none() {
+            match url {
+                #[cfg(feature = "url-links")]
+                Some(url) => links::open_url(url),
+                _ => {
+                    ui.ctx().copy_text(event.message.clone());
+                    copied_at.insert(id, Instant::now());
+                }
+            }
+        }
+        if copied_at.get(&id).is_some_and(|copied| copied.elapsed() < COPY_FLASH_DURATION) {
+            let color = config.theme.copy_highlight_color.to_color32();
+            ui.painter().set(background, egui::Shape::rect_filled(label.rect, 2.0, color));
         }
 
         label.on_hover_ui(|ui| {
-            ui.label("Click to copy message");
+            if var_name.is_some() {
+                ui.label("Click to query");
+            } else if url.is_some() {
+                ui.label("Click to open in browser");
+            } else {
+                ui.label("Click to copy message");
+            }
             ui.separator();
 
             let mut text = LayoutJob::default();
@@ -373,7 +934,41 @@ fn add_log(
 
             ui.label(text);
         });
-    });
+
+        var_name.filter(|_| clicked).map(str::to_string)
+    })
+    .inner
+}
+
+/// Whether `message` passes the level filter and search query.
+fn is_shown(message: &LogMessage, filter: &LogFilter, search_query: &str) -> bool {
+    filter.should_show_message(message)
+        && (search_query.is_empty() || !find_matches(&message.message, search_query).is_empty())
+}
+
+/// Log messages as text, one `[HH:MM:SS] message` line per line, like `tee` writes.
+fn export_text<'a>(messages: impl IntoIterator<Item = &'a LogMessage>) -> String {
+    let mut text = String::new();
+    for message in messages {
+        let timestamp = crate::tee::format_timestamp(message.time);
+        for line in message.message.lines() {
+            text.push_str(&format!("[{}] {}\n", timestamp, line));
+        }
+    }
+    text
+}
+
+/// Extract the ConVar name from a result line such as `sv_gravity* = "800"`.
+fn result_var_name(message: &str) -> Option<&str> {
+    let message = message.strip_prefix(COMMAND_RESULT_PREFIX).unwrap_or(message);
+    let (name, _) = message.split_once(" = ")?;
+    let name = name.trim_matches('"').trim_end_matches('*');
+
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        None
+    } else {
+        Some(name)
+    }
 }
 
 fn format_line(
@@ -385,6 +980,7 @@ fn format_line(
         level,
         ..
     }: &LogMessage,
+    search_query: &str,
 ) -> LayoutJob {
     let mut text = LayoutJob::default();
     text.append(
@@ -399,24 +995,403 @@ fn format_line(
                 .strip_prefix(COMMAND_MESSAGE_PREFIX)
                 .unwrap_or(message);
             text.append(COMMAND_MESSAGE_PREFIX, 0.0, config.theme.format_dark());
-            text.append(message_stripped, 0.0, config.theme.format_text());
+            append_highlighted(&mut text, message_stripped, search_query, config);
             text
         }
         COMMAND_RESULT_NAME => {
             text.append(COMMAND_RESULT_PREFIX, 0.0, config.theme.format_dark());
-            text.append(
+            append_message(
+                &mut text,
                 message
                     .strip_prefix(COMMAND_RESULT_PREFIX)
                     .unwrap_or(message),
-                0.0,
-                config.theme.format_text(),
+                search_query,
+                config,
             );
             text
         }
+        COMMAND_SUCCESS_NAME => {
+            text.append("SUCCESS", 0.0, config.theme.format_success());
+            text.append(" ", 0.0, config.theme.format_text());
+            append_message(&mut text, message, search_query, config);
+            text
+        }
         _ => {
             text.append(level.as_str(), 0.0, config.theme.format_level(*level));
-            text.append(&format!(" {message}"), 0.0, config.theme.format_text());
+            text.append(" ", 0.0, config.theme.format_text());
+            append_message(&mut text, message, search_query, config);
             text
         }
     }
 }
+
+/// Find the byte ranges of every ASCII case-insensitive occurrence of `query` in `text`.
+fn find_matches(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+
+    haystack
+        .match_indices(&needle)
+        .map(|(start, matched)| start..start + matched.len())
+        .collect()
+}
+
+/// Append `message` to `text`, rendering Markdown unless a search is active.
+fn append_message(text: &mut LayoutJob, message: &str, search_query: &str, config: &ConsoleConfig) {
+    if config.render_markdown && search_query.is_empty() && markdown::has_markdown(message) {
+        markdown::append_markdown(text, message, &config.theme);
+    } else {
+        #[cfg(feature = "url-links")]
+        let message = links::append_links(text, message, search_query, config);
+        append_highlighted(text, message, search_query, config);
+    }
+}
+
+/// Append `message` to `text`, highlighting occurrences of the search query.
+fn append_highlighted(text: &mut LayoutJob, message: &str, search_query: &str, config: &ConsoleConfig) {
+    let mut last = 0;
+    for range in find_matches(message, search_query) {
+        text.append(&message[last..range.start], 0.0, config.theme.format_text());
+        text.append(&message[range.clone()], 0.0, config.theme.format_highlight());
+        last = range.end;
+    }
+    text.append(&message[last..], 0.0, config.theme.format_text());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::log::Level;
+    use crate::core::ConsoleOutputLevel;
+
+    fn log_message(message: &str) -> LogMessage {
+        LogMessage {
+            message: message.to_string(),
+            name: "test",
+            target: "test",
+            level: Level::INFO,
+            module_path: None,
+            file: None,
+            line: None,
+            time: SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn test_log_eviction() {
+        let mut state = ConsoleUiState {
+            max_log_size: 3,
+            ..default()
+        };
+
+        state.push_logs((0..2).map(|i| log_message(&i.to_string())));
+        assert_eq!(state.log.len(), 2);
+
+        state.push_logs((2..7).map(|i| log_message(&i.to_string())));
+        assert_eq!(state.log.len(), 3);
+
+        // Oldest entries are evicted first
+        let messages: Vec<_> = state.log.iter().map(|entry| entry.message.message.as_str()).collect();
+        assert_eq!(messages, vec!["4", "5", "6"]);
+        // Ids stay with their entries, so a copy highlight doesn't move to another line
+        let ids: Vec<_> = state.log.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_window_resize_updates_state() {
+        let config = ConsoleConfig { max_size: Vec2::new(1920.0, 1080.0), ..default() };
+        let mut state = ConsoleUiState::default();
+        assert_eq!((state.width, state.height), (DEFAULT_WIDTH, DEFAULT_HEIGHT));
+
+        state.set_size(Vec2::new(640.0, 300.0), &config);
+        assert_eq!((state.width, state.height), (640.0, 300.0));
+
+        // Sizes outside the configured bounds are clamped
+        state.set_size(Vec2::new(10.0, 100_000.0), &config);
+        assert_eq!((state.width, state.height), (config.min_size.x, config.max_size.y));
+
+        // By default there is no upper bound
+        state.set_size(Vec2::new(5000.0, 3000.0), &ConsoleConfig::default());
+        assert_eq!((state.width, state.height), (5000.0, 3000.0));
+    }
+
+    #[test]
+    fn test_window_min_size() {
+        let config = ConsoleConfig::default();
+        let min_size = egui::vec2(config.min_size.x, config.min_size.y);
+        let mut state = ConsoleUiState {
+            open_progress: 1.0,
+            window_size: Some(egui::vec2(50.0, 20.0)),
+            ..default()
+        };
+
+        let ctx = egui::Context::default();
+        let show = |state: &ConsoleUiState| {
+            let mut content_size = None;
+            let _ = ctx.run(egui::RawInput::default(), |ctx| {
+                content_size = console_window("Developer Console", state, &config)
+                    .show(ctx, |ui| ui.max_rect().size())
+                    .and_then(|response| response.inner);
+            });
+            content_size.unwrap()
+        };
+
+        // A size below the minimum, e.g. restored from an old config, is raised to it
+        let size = show(&state);
+        assert!(size.x >= min_size.x && size.y >= min_size.y, "{size:?}");
+
+        // A window that shrank anyway is resized back next frame
+        state.record_window_size(Some(egui::vec2(100.0, 50.0)), &config);
+        assert!(state.restore_size);
+        assert_eq!(state.window_size, Some(min_size));
+        let size = show(&state);
+        assert!(size.x >= min_size.x && size.y >= min_size.y, "{size:?}");
+
+        state.record_window_size(Some(size), &config);
+        assert!(!state.restore_size);
+    }
+
+    #[test]
+    fn test_copy_flash_expires() {
+        let mut state = ConsoleUiState::default();
+        let now = Instant::now();
+        state.copied_at.insert(0, now);
+        state.copied_at.insert(1, now.checked_sub(Duration::from_secs(1)).unwrap());
+
+        state.expire_copy_flashes();
+        assert_eq!(state.copied_at.keys().collect::<Vec<_>>(), [&0]);
+    }
+
+    #[test]
+    fn test_log_filter_success() {
+        let mut filter = LogFilter {
+            show_info: false,
+            ..default()
+        };
+        let success = LogMessage {
+            name: COMMAND_SUCCESS_NAME,
+            ..log_message("Saved config")
+        };
+
+        // Success messages are filtered separately from info
+        assert!(filter.should_show_message(&success));
+        assert!(!filter.should_show_message(&log_message("info")));
+
+        filter.show_success = false;
+        assert!(!filter.should_show_message(&success));
+    }
+
+    #[test]
+    fn test_find_matches() {
+        assert_eq!(find_matches("Failed to load Config", "config"), vec![15..21]);
+        assert_eq!(find_matches("error ERROR", "Error"), vec![0..5, 6..11]);
+        assert!(find_matches("all good", "error").is_empty());
+        assert!(find_matches("anything", "").is_empty());
+    }
+
+    #[test]
+    fn test_result_var_name() {
+        assert_eq!(result_var_name(r#"sv_gravity = "800""#), Some("sv_gravity"));
+        assert_eq!(result_var_name(r#"sv_gravity* = "800""#), Some("sv_gravity"));
+        assert_eq!(result_var_name(r#"> "cl_fov" = "90""#), Some("cl_fov"));
+        assert_eq!(result_var_name("12 convars"), None);
+        assert_eq!(result_var_name("not a var = 1"), None);
+    }
+
+    #[test]
+    fn test_export_text() {
+        let mut first = log_message("sv_gravity = \"800\"");
+        first.time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(3723);
+        let mut second = log_message("line one\nline two");
+        second.time = first.time;
+
+        assert_eq!(
+            export_text([&first, &second]),
+            "[01:02:03] sv_gravity = \"800\"\n[01:02:03] line one\n[01:02:03] line two\n"
+        );
+    }
+
+    #[test]
+    fn test_log_eviction_respects_auto_scroll() {
+        let mut state = ConsoleUiState {
+            max_log_size: 2,
+            auto_scroll: false,
+            ..default()
+        };
+
+        state.push_logs((0..4).map(|i| log_message(&i.to_string())));
+        assert_eq!(state.log.len(), 2);
+        assert!(state.log.iter().all(|entry| !entry.is_new));
+    }
+
+    #[test]
+    fn test_scroll_lock_counts_unseen() {
+        let mut state = ConsoleUiState::default();
+        state.push_logs([log_message("locked")]);
+        assert!(state.log[0].is_new);
+        assert_eq!(state.unseen, 0);
+
+        state.scroll_lock = false;
+        state.push_logs([log_message("a"), log_message("b")]);
+        assert!(!state.log[1].is_new && !state.log[2].is_new);
+        assert_eq!(state.unseen, 2);
+    }
+
+    #[test]
+    fn test_unread_errors() {
+        let mut state = ConsoleUiState::default();
+        let error = LogMessage { level: Level::ERROR, ..log_message("boom") };
+        state.push_logs([log_message("fine"), error.clone(), error]);
+        assert_eq!(state.unread_errors(), 2);
+        assert_eq!(console_tab_title(state.unread_errors()), "Console (2)");
+        assert_eq!(console_tab_title(0), "Console");
+    }
+
+    #[test]
+    fn test_clear_filter() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<Messages<ConsoleClearEvent>>();
+        let mut state = ConsoleUiState::default();
+        let error = LogMessage { level: Level::ERROR, ..log_message("boom") };
+        let warning = LogMessage { level: Level::WARN, ..log_message("careful") };
+        let success = LogMessage { name: COMMAND_SUCCESS_NAME, ..log_message("done") };
+        state.push_logs([log_message("fine"), error, warning, success]);
+        world.insert_resource(state);
+
+        world.write_message(ConsoleClearEvent::level(ConsoleOutputLevel::Warn));
+        world.run_system_once(handle_clear).unwrap();
+        let messages: Vec<_> = world.resource::<ConsoleUiState>().log.iter().map(|entry| entry.message.message.as_str()).collect();
+        assert_eq!(messages, ["fine", "boom", "done"]);
+
+        world.write_message(ConsoleClearEvent::level(ConsoleOutputLevel::Error));
+        world.run_system_once(handle_clear).unwrap();
+        let messages: Vec<_> = world.resource::<ConsoleUiState>().log.iter().map(|entry| entry.message.message.as_str()).collect();
+        assert_eq!(messages, ["fine", "done"]);
+
+        world.write_message(ConsoleClearEvent::all());
+        world.run_system_once(handle_clear).unwrap();
+        assert!(world.resource::<ConsoleUiState>().log.is_empty());
+    }
+
+    #[test]
+    fn test_open_animation_progress() {
+        use bevy::ecs::system::RunSystemOnce;
+        use std::time::Duration;
+
+        let mut world = World::new();
+        world.insert_resource(ConsoleUiState { open: true, ..default() });
+        world.insert_resource(ConsoleConfig {
+            open_animation_duration: Duration::from_millis(100),
+            ..default()
+        });
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(50));
+        world.insert_resource(time);
+
+        world.run_system_once(open_close_ui).unwrap();
+        assert!((world.resource::<ConsoleUiState>().open_progress - 0.5).abs() < 1e-4);
+        world.run_system_once(open_close_ui).unwrap();
+        world.run_system_once(open_close_ui).unwrap();
+        assert_eq!(world.resource::<ConsoleUiState>().open_progress, 1.0);
+
+        world.resource_mut::<ConsoleUiState>().open = false;
+        world.run_system_once(open_close_ui).unwrap();
+        assert!((world.resource::<ConsoleUiState>().open_progress - 0.5).abs() < 1e-4);
+
+        // Without a duration the window opens and closes instantly
+        world.resource_mut::<ConsoleConfig>().open_animation_duration = Duration::ZERO;
+        world.run_system_once(open_close_ui).unwrap();
+        assert_eq!(world.resource::<ConsoleUiState>().open_progress, 0.0);
+    }
+
+    #[test]
+    fn test_history_search_shortcut() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut state = ConsoleUiState { open: true, ..default() };
+        let mut key = ButtonInput::<KeyCode>::default();
+
+        // Other Ctrl shortcuts like paste leave the input alone
+        key.press(KeyCode::ControlLeft);
+        key.press(KeyCode::KeyV);
+        start_history_search(&mut state, &key);
+        assert!(!state.history_search);
+
+        key.clear();
+        key.press(KeyCode::KeyR);
+        start_history_search(&mut state, &key);
+        assert!(state.history_search);
+
+        // Escape leaves the search first, and closes the console only after that
+        let mut world = World::new();
+        world.insert_resource(state);
+        world.init_resource::<ConsoleConfig>();
+        world.init_resource::<Time>();
+        let mut key = ButtonInput::<KeyCode>::default();
+        key.press(KeyCode::Escape);
+        world.insert_resource(key);
+
+        world.run_system_once(open_close_ui).unwrap();
+        let state = world.resource::<ConsoleUiState>();
+        assert!(!state.history_search);
+        assert!(state.open);
+
+        world.run_system_once(open_close_ui).unwrap();
+        assert!(!world.resource::<ConsoleUiState>().open);
+    }
+
+    #[test]
+    fn test_max_completions_config() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let mut registry = ConsoleRegistry::new();
+        for name in ["sv_a", "sv_b", "sv_c", "sv_d", "sv_e"] {
+            registry.register_var(crate::core::ConVar::new(name, 0));
+        }
+        world.insert_resource(registry);
+        world.insert_resource(ConsoleConfig { max_completions: 3, ..default() });
+        world.insert_resource(ConsoleUiState { command: "sv_".into(), ..default() });
+        world.init_resource::<AutoCompletions>();
+
+        world.run_system_once(update_completions).unwrap();
+        assert_eq!(world.resource::<AutoCompletions>().0.len(), 3);
+    }
+
+    #[test]
+    fn test_value_completions() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        assert_eq!(value_position("cl_showfps "), Some(("cl_showfps", "")));
+        assert_eq!(value_position("echo a; cl_fov 9"), Some(("cl_fov", "9")));
+        assert_eq!(value_position("cl_showfps"), None);
+        assert_eq!(value_position("bind F1 "), None);
+
+        let mut world = World::new();
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(crate::core::ConVar::new("cl_showfps", false));
+        registry.register_var(crate::core::ConVar::new("cl_fov", 90).min(60).max(120));
+        world.insert_resource(registry);
+        world.init_resource::<ConsoleConfig>();
+        world.insert_resource(ConsoleUiState { command: "cl_showfps ".into(), ..default() });
+        world.init_resource::<AutoCompletions>();
+
+        let suggestions = |world: &mut World, command: &str| {
+            world.resource_mut::<ConsoleUiState>().command = command.into();
+            world.run_system_once(update_completions).unwrap();
+            world.resource::<AutoCompletions>().iter().map(|c| (c.suggestion.clone(), c.hint)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(suggestions(&mut world, "cl_showfps "), [("0".into(), false), ("1".into(), false)]);
+        assert_eq!(suggestions(&mut world, "cl_showfps 1"), [("1".into(), false)]);
+        assert_eq!(suggestions(&mut world, "cl_fov 7"), [("<60..120>".into(), true)]);
+    }
+}
@@ -0,0 +1,169 @@
+//! Side panel with sliders for bounded numeric ConVars.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::core::{CommandSource, ConVarFlags, ConVarMeta, ConsoleInputEvent, ConsoleRegistry};
+
+use super::ConsoleUiState;
+
+/// The backing type of a numeric ConVar edited through an `f64` widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberKind {
+    /// `f32`.
+    F32,
+    /// `f64`.
+    F64,
+    /// `i32` or `i64`.
+    Integer,
+}
+
+impl NumberKind {
+    /// Format a widget value as a console argument of this type.
+    ///
+    /// `f32` values are formatted as `f32`, so 0.1 doesn't turn into
+    /// 0.10000000149011612 on its way through `f64`.
+    pub(crate) fn format(self, value: f64) -> String {
+        match self {
+            NumberKind::F32 => (value as f32).to_string(),
+            NumberKind::F64 => value.to_string(),
+            NumberKind::Integer => (value.round() as i64).to_string(),
+        }
+    }
+}
+
+/// A numeric ConVar that can be edited with a slider.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SliderVar {
+    value: f64,
+    min: f64,
+    max: f64,
+    kind: NumberKind,
+}
+
+/// Get slider info for a ConVar with both a min and a max bound.
+fn slider_var(meta: &ConVarMeta) -> Option<SliderVar> {
+    if let Some(cvar) = meta.downcast_ref::<f32>() {
+        return Some(SliderVar {
+            value: cvar.get() as f64,
+            min: *cvar.get_min()? as f64,
+            max: *cvar.get_max()? as f64,
+            kind: NumberKind::F32,
+        });
+    }
+    if let Some(cvar) = meta.downcast_ref::<f64>() {
+        return Some(SliderVar {
+            value: cvar.get(),
+            min: *cvar.get_min()?,
+            max: *cvar.get_max()?,
+            kind: NumberKind::F64,
+        });
+    }
+    if let Some(cvar) = meta.downcast_ref::<i32>() {
+        return Some(SliderVar {
+            value: cvar.get() as f64,
+            min: *cvar.get_min()? as f64,
+            max: *cvar.get_max()? as f64,
+            kind: NumberKind::Integer,
+        });
+    }
+    if let Some(cvar) = meta.downcast_ref::<i64>() {
+        return Some(SliderVar {
+            value: cvar.get() as f64,
+            min: *cvar.get_min()? as f64,
+            max: *cvar.get_max()? as f64,
+            kind: NumberKind::Integer,
+        });
+    }
+    None
+}
+
+/// Render a collapsible side panel with sliders for ARCHIVE numeric ConVars.
+///
+/// Slider edits are kept in `state` while dragging and sent as a
+/// [`ConsoleInputEvent`] once committed.
+pub fn var_panel(
+    ui: &mut egui::Ui,
+    state: &mut ConsoleUiState,
+    registry: &ConsoleRegistry,
+    input_events: &mut MessageWriter<ConsoleInputEvent>,
+) {
+    if !state.show_var_panel {
+        return;
+    }
+
+    egui::SidePanel::right("var panel")
+        .resizable(true)
+        .show_inside(ui, |ui| {
+            ui.heading("Variables");
+            var_sliders(ui, state, registry, input_events);
+        });
+}
+
+/// Render the scrollable list of sliders shown in the [`var_panel`].
+pub fn var_sliders(
+    ui: &mut egui::Ui,
+    state: &mut ConsoleUiState,
+    registry: &ConsoleRegistry,
+    input_events: &mut MessageWriter<ConsoleInputEvent>,
+) {
+    let mut vars: Vec<_> = registry
+        .archive_vars()
+        .filter(|(_, meta)| !meta.flags.contains(ConVarFlags::HIDDEN))
+        .filter_map(|(name, meta)| slider_var(meta).map(|var| (name, var)))
+        .collect();
+    vars.sort_by(|a, b| a.0.cmp(b.0));
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (name, var) in vars {
+            let mut value = state.var_edits.get(name).copied().unwrap_or(var.value);
+
+            ui.label(name);
+            let slider = egui::Slider::new(&mut value, var.min..=var.max);
+            let slider = if var.kind == NumberKind::Integer { slider.integer() } else { slider };
+            let response = ui.add(slider);
+
+            if response.dragged() {
+                state.var_edits.insert(name.to_string(), value);
+            } else if response.drag_stopped() || response.changed() {
+                state.var_edits.remove(name);
+                input_events.write(ConsoleInputEvent::new(format!("{} {}", name, var.kind.format(value))).with_source(CommandSource::Ui));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConEntry, ConVar};
+
+    #[test]
+    fn test_slider_var() {
+        let mut registry = ConsoleRegistry::new();
+        registry.register_var(ConVar::new("cl_fov", 90i32).min(60).max(120));
+        registry.register_var(ConVar::new("sv_gravity", 800.0f32).min(0.0));
+        registry.register_var(ConVar::new("sv_hostname", String::new()));
+
+        let slider = |name| match registry.get_entry(name) {
+            Some(ConEntry::Var(meta)) => slider_var(meta),
+            _ => None,
+        };
+
+        assert_eq!(
+            slider("cl_fov"),
+            Some(SliderVar { value: 90.0, min: 60.0, max: 120.0, kind: NumberKind::Integer })
+        );
+        // Both bounds are required
+        assert_eq!(slider("sv_gravity"), None);
+        assert_eq!(slider("sv_hostname"), None);
+    }
+
+    #[test]
+    fn test_number_format() {
+        assert_eq!(NumberKind::Integer.format(4.6), "5");
+        assert_eq!(NumberKind::F64.format(4.5), "4.5");
+        assert_eq!(NumberKind::F32.format(0.1f32 as f64), "0.1");
+        assert_eq!(NumberKind::F64.format(0.1), "0.1");
+    }
+}